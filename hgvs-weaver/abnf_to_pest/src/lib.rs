@@ -0,0 +1,519 @@
+//! Translates a checked-in ABNF (RFC 5234) grammar into pest rules.
+//!
+//! This exists so `hgvs-weaver/grammar/hgvs.abnf` -- a formal description of
+//! the HGVS nomenclature this crate parses -- is the single source of truth
+//! for the grammar, instead of hand-written pest rules that can silently
+//! drift from the spec. `hgvs-weaver/build.rs` calls [`translate`] to emit a
+//! `.pest` file into `OUT_DIR` at build time.
+//!
+//! Supported ABNF subset: rule definitions (`name = alternatives`),
+//! concatenation, alternation (`/`), groups (`(...)`), optionals (`[...]`),
+//! repetition prefixes (`*`, `1*`, `2*6`, ...), quoted literals, rule
+//! references, and the `ALPHA`/`DIGIT` core rules. This covers everything
+//! `grammar/hgvs.abnf` uses; it isn't a full RFC 5234 implementation (no
+//! numeric/hex/binary value terminals, no `%s`/`%i` literal tags, no
+//! incremental `=/` alternatives).
+//!
+//! Every emitted rule is driven by a [`RuleSettings`] map keyed by the ABNF
+//! rule name, controlling whether it's pest-silent (`_{ .. }`, no pair
+//! emitted) and what pest identifier it's renamed to (ABNF rule names may
+//! contain `-`, which isn't valid in a pest identifier).
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Per-rule translation settings, keyed by the ABNF rule name.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSettings {
+    /// Emit as a pest silent rule (`name = _{ .. }`): matched but produces no
+    /// `Pair` of its own, only its non-silent children.
+    pub silent: bool,
+    /// Pest identifier to emit instead of the ABNF name with `-` replaced by
+    /// `_`. Needed when a hand-off parser function expects a specific
+    /// `Rule::*` name that doesn't match the ABNF spelling.
+    pub rename: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbnfError {
+    UnexpectedEnd { context: &'static str },
+    UnexpectedChar { found: char, pos: usize, context: &'static str },
+    UndefinedRule(String),
+}
+
+impl fmt::Display for AbnfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbnfError::UnexpectedEnd { context } => {
+                write!(f, "unexpected end of input while parsing {context}")
+            }
+            AbnfError::UnexpectedChar { found, pos, context } => {
+                write!(f, "unexpected '{found}' at byte {pos} while parsing {context}")
+            }
+            AbnfError::UndefinedRule(name) => write!(f, "reference to undefined rule {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for AbnfError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Lit(String),
+    Ref(String),
+    Seq(Vec<Expr>),
+    Alt(Vec<Expr>),
+    Opt(Box<Expr>),
+    Rep {
+        min: u32,
+        max: Option<u32>,
+        inner: Box<Expr>,
+    },
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Parser { src, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn eat_char(&mut self, expected: char, context: &'static str) -> Result<(), AbnfError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(AbnfError::UnexpectedChar {
+                found: c,
+                pos: self.pos,
+                context,
+            }),
+            None => Err(AbnfError::UnexpectedEnd { context }),
+        }
+    }
+
+    fn parse_ident(&mut self) -> Option<String> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            self.bump();
+        }
+        if self.pos == start {
+            None
+        } else {
+            Some(self.src[start..self.pos].to_string())
+        }
+    }
+
+    fn parse_number(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+        }
+        if self.pos == start {
+            None
+        } else {
+            self.src[start..self.pos].parse().ok()
+        }
+    }
+
+    /// Parses a leading repetition prefix (`*`, `1*`, `2*6`, `3`) if one is
+    /// present at the cursor, without consuming anything if there is none.
+    /// A bare number is always a specific repetition count (`3ALPHA`), since
+    /// this subset has no numeric-value terminals.
+    fn try_parse_repeat_prefix(&mut self) -> Option<(u32, Option<u32>)> {
+        let checkpoint = self.pos;
+        let count = self.parse_number();
+        if self.peek() != Some('*') {
+            if let Some(n) = count {
+                return Some((n, Some(n)));
+            }
+            self.pos = checkpoint;
+            return None;
+        }
+        self.bump();
+        let max = self.parse_number();
+        Some((count.unwrap_or(0), max))
+    }
+
+    fn parse_literal(&mut self) -> Result<Expr, AbnfError> {
+        self.eat_char('"', "literal")?;
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c != '"') {
+            self.bump();
+        }
+        let text = self.src[start..self.pos].to_string();
+        self.eat_char('"', "literal")?;
+        Ok(Expr::Lit(text))
+    }
+
+    fn parse_element(&mut self) -> Result<Expr, AbnfError> {
+        self.skip_ws();
+        match self.peek() {
+            Some('"') => self.parse_literal(),
+            Some('(') => {
+                self.bump();
+                let inner = self.parse_alternation()?;
+                self.skip_ws();
+                self.eat_char(')', "group")?;
+                Ok(inner)
+            }
+            Some('[') => {
+                self.bump();
+                let inner = self.parse_alternation()?;
+                self.skip_ws();
+                self.eat_char(']', "optional")?;
+                Ok(Expr::Opt(Box::new(inner)))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let name = self
+                    .parse_ident()
+                    .ok_or(AbnfError::UnexpectedEnd { context: "rule reference" })?;
+                Ok(Expr::Ref(name))
+            }
+            Some(c) => Err(AbnfError::UnexpectedChar {
+                found: c,
+                pos: self.pos,
+                context: "element",
+            }),
+            None => Err(AbnfError::UnexpectedEnd { context: "element" }),
+        }
+    }
+
+    fn parse_repetition(&mut self) -> Result<Expr, AbnfError> {
+        self.skip_ws();
+        let repeat = self.try_parse_repeat_prefix();
+        self.skip_ws();
+        let inner = self.parse_element()?;
+        Ok(match repeat {
+            Some((min, max)) => Expr::Rep {
+                min,
+                max,
+                inner: Box::new(inner),
+            },
+            None => inner,
+        })
+    }
+
+    fn is_concatenation_boundary(&self) -> bool {
+        self.skip_ws_peek_is(|c| matches!(c, '/' | ')' | ']') )
+    }
+
+    fn skip_ws_peek_is(&self, pred: impl Fn(char) -> bool) -> bool {
+        let rest = self.rest().trim_start();
+        match rest.chars().next() {
+            Some(c) => pred(c),
+            None => true,
+        }
+    }
+
+    fn parse_concatenation(&mut self) -> Result<Expr, AbnfError> {
+        let mut parts = vec![self.parse_repetition()?];
+        loop {
+            self.skip_ws();
+            if self.peek().is_none() || self.is_concatenation_boundary() {
+                break;
+            }
+            parts.push(self.parse_repetition()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.into_iter().next().unwrap()
+        } else {
+            Expr::Seq(parts)
+        })
+    }
+
+    fn parse_alternation(&mut self) -> Result<Expr, AbnfError> {
+        let mut alts = vec![self.parse_concatenation()?];
+        loop {
+            self.skip_ws();
+            if self.peek() == Some('/') {
+                self.bump();
+                alts.push(self.parse_concatenation()?);
+            } else {
+                break;
+            }
+        }
+        Ok(if alts.len() == 1 {
+            alts.into_iter().next().unwrap()
+        } else {
+            Expr::Alt(alts)
+        })
+    }
+
+    fn parse_rule(&mut self) -> Result<(String, Expr), AbnfError> {
+        self.skip_ws();
+        let name = self
+            .parse_ident()
+            .ok_or(AbnfError::UnexpectedEnd { context: "rule name" })?;
+        self.skip_ws();
+        self.eat_char('=', "rule definition")?;
+        self.skip_ws();
+        let expr = self.parse_alternation()?;
+        Ok((name, expr))
+    }
+}
+
+fn strip_comments(src: &str) -> String {
+    src.lines()
+        .map(|line| {
+            let mut in_quote = false;
+            for (idx, c) in line.char_indices() {
+                match c {
+                    '"' => in_quote = !in_quote,
+                    ';' if !in_quote => return &line[..idx],
+                    _ => {}
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_rules(src: &str) -> Result<Vec<(String, Expr)>, AbnfError> {
+    let cleaned = strip_comments(src);
+    let mut rules = Vec::new();
+    for chunk in split_rule_chunks(&cleaned) {
+        if chunk.trim().is_empty() {
+            continue;
+        }
+        let mut parser = Parser::new(chunk.trim());
+        rules.push(parser.parse_rule()?);
+    }
+    Ok(rules)
+}
+
+/// Splits the source into one chunk per rule definition. A new rule starts
+/// at a line beginning with an identifier immediately followed by `=`
+/// (ignoring leading whitespace); any other line is a continuation of the
+/// current rule (ABNF allows definitions to wrap across lines).
+fn split_rule_chunks(src: &str) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    for line in src.lines() {
+        if starts_new_rule(line) {
+            chunks.push(line.to_string());
+        } else if let Some(last) = chunks.last_mut() {
+            last.push(' ');
+            last.push_str(line.trim());
+        }
+    }
+    chunks
+}
+
+fn starts_new_rule(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let ident_len = trimmed
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .count();
+    if ident_len == 0 {
+        return false;
+    }
+    trimmed[ident_len..].trim_start().starts_with('=')
+}
+
+fn pest_ident(name: &str, settings: &HashMap<String, RuleSettings>) -> String {
+    if let Some(renamed) = settings.get(name).and_then(|s| s.rename.as_deref()) {
+        return renamed.to_string();
+    }
+    name.replace('-', "_")
+}
+
+fn escape_pest_literal(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn emit(expr: &Expr, settings: &HashMap<String, RuleSettings>) -> String {
+    match expr {
+        Expr::Lit(text) => format!("^\"{}\"", escape_pest_literal(text)),
+        Expr::Ref(name) => match name.as_str() {
+            "ALPHA" => "ASCII_ALPHA".to_string(),
+            "DIGIT" => "ASCII_DIGIT".to_string(),
+            other => pest_ident(other, settings),
+        },
+        Expr::Seq(parts) => format!(
+            "({})",
+            parts
+                .iter()
+                .map(|p| emit(p, settings))
+                .collect::<Vec<_>>()
+                .join(" ~ ")
+        ),
+        Expr::Alt(parts) => format!(
+            "({})",
+            parts
+                .iter()
+                .map(|p| emit(p, settings))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        Expr::Opt(inner) => format!("({})?", emit(inner, settings)),
+        Expr::Rep { min, max, inner } => {
+            let inner_pest = emit(inner, settings);
+            match (min, max) {
+                (0, None) => format!("({inner_pest})*"),
+                (1, None) => format!("({inner_pest})+"),
+                (min, None) => format!("({inner_pest}){{{min},}}"),
+                (0, Some(max)) => format!("({inner_pest}){{,{max}}}"),
+                (min, Some(max)) if min == max => format!("({inner_pest}){{{min}}}"),
+                (min, Some(max)) => format!("({inner_pest}){{{min},{max}}}"),
+            }
+        }
+    }
+}
+
+/// Translates `abnf_source` into an equivalent `.pest` grammar, applying
+/// `settings` to control each rule's visibility and emitted identifier.
+/// Rules not present in `settings` are emitted as ordinary (non-silent)
+/// pest rules named after the ABNF name with `-` replaced by `_`.
+pub fn translate(
+    abnf_source: &str,
+    settings: &HashMap<String, RuleSettings>,
+) -> Result<String, AbnfError> {
+    let rules = parse_rules(abnf_source)?;
+    let known: std::collections::HashSet<&str> =
+        rules.iter().map(|(name, _)| name.as_str()).collect();
+
+    for (_, expr) in &rules {
+        check_refs(expr, &known)?;
+    }
+
+    let mut out = String::new();
+    out.push_str("// Generated by abnf_to_pest from grammar/hgvs.abnf. Do not edit by hand.\n\n");
+    for (name, expr) in &rules {
+        let pest_name = pest_ident(name, settings);
+        let silent = settings.get(name).map(|s| s.silent).unwrap_or(false);
+        let marker = if silent { "_" } else { "" };
+        out.push_str(&format!(
+            "{pest_name} = {marker}{{ {} }}\n",
+            emit(expr, settings)
+        ));
+    }
+    Ok(out)
+}
+
+fn check_refs(expr: &Expr, known: &std::collections::HashSet<&str>) -> Result<(), AbnfError> {
+    match expr {
+        Expr::Ref(name) if name == "ALPHA" || name == "DIGIT" => Ok(()),
+        Expr::Ref(name) => {
+            if known.contains(name.as_str()) {
+                Ok(())
+            } else {
+                Err(AbnfError::UndefinedRule(name.clone()))
+            }
+        }
+        Expr::Lit(_) => Ok(()),
+        Expr::Seq(parts) | Expr::Alt(parts) => {
+            parts.iter().try_for_each(|p| check_refs(p, known))
+        }
+        Expr::Opt(inner) => check_refs(inner, known),
+        Expr::Rep { inner, .. } => check_refs(inner, known),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> HashMap<String, RuleSettings> {
+        HashMap::new()
+    }
+
+    #[test]
+    fn test_translate_literal_rule() {
+        let pest = translate("greeting = \"hi\"\n", &settings()).unwrap();
+        assert!(pest.contains("greeting = { ^\"hi\" }"));
+    }
+
+    #[test]
+    fn test_translate_alternation_and_concatenation() {
+        let abnf = "num = DIGIT\nsign = \"+\" / \"-\"\noffset = sign num\n";
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("num = { ASCII_DIGIT }"));
+        assert!(pest.contains("sign = { (^\"+\" | ^\"-\") }"));
+        assert!(pest.contains("offset = { (sign ~ num) }"));
+    }
+
+    #[test]
+    fn test_translate_optional_and_repetition() {
+        let abnf = "num = 1*DIGIT\nbase_offset_pos = [\"*\"] num\n";
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("num = { (ASCII_DIGIT)+ }"));
+        assert!(pest.contains("base_offset_pos = { ((^\"*\")? ~ num) }"));
+    }
+
+    #[test]
+    fn test_hyphenated_rule_name_becomes_underscored() {
+        let abnf = "simple-pos = DIGIT\n";
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("simple_pos = { ASCII_DIGIT }"));
+    }
+
+    #[test]
+    fn test_settings_rename_and_silence_a_rule() {
+        let abnf = "coordinate-type = \"g\" / \"c\"\n";
+        let mut s = settings();
+        s.insert(
+            "coordinate-type".to_string(),
+            RuleSettings {
+                silent: true,
+                rename: Some("coord_kind".to_string()),
+            },
+        );
+        let pest = translate(abnf, &s).unwrap();
+        assert!(pest.contains("coord_kind = _{ (^\"g\" | ^\"c\") }"));
+    }
+
+    #[test]
+    fn test_undefined_rule_reference_is_an_error() {
+        let abnf = "a = b\n";
+        let err = translate(abnf, &settings()).unwrap_err();
+        assert_eq!(err, AbnfError::UndefinedRule("b".to_string()));
+    }
+
+    #[test]
+    fn test_bounded_repetition_range() {
+        let abnf = "unit = DIGIT\nrepeat = 2*6unit\n";
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("repeat = { (unit){2,6} }"));
+    }
+
+    #[test]
+    fn test_comments_are_stripped() {
+        let abnf = "; leading comment\nfoo = \"x\" ; trailing comment\n";
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("foo = { ^\"x\" }"));
+    }
+
+    #[test]
+    fn test_translates_checked_in_hgvs_grammar() {
+        let abnf = include_str!("../../grammar/hgvs.abnf");
+        let pest = translate(abnf, &settings()).unwrap();
+        assert!(pest.contains("variant ="));
+        assert!(pest.contains("posedit ="));
+    }
+}
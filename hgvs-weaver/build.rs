@@ -0,0 +1,36 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Only compiled when the optional `service` feature pulls in tonic/prost;
+    // library-only consumers never pay for protoc.
+    if std::env::var("CARGO_FEATURE_SERVICE").is_ok() {
+        tonic_build::compile_protos("proto/hgvs_service.proto")?;
+    }
+
+    generate_pest_grammar()?;
+
+    Ok(())
+}
+
+/// Translates `grammar/hgvs.abnf` -- the maintained spec for the HGVS subset
+/// this crate parses -- into a `.pest` file under `OUT_DIR`, via the nested
+/// `abnf_to_pest` crate.
+///
+/// NOTE: this checkout has no root `Cargo.toml`, so there's nowhere to add
+/// the `[build-dependencies]\nabnf_to_pest = { path = "abnf_to_pest" }` entry
+/// this needs to actually compile; `hgvs-weaver/src/parser.rs`'s
+/// `#[derive(Parser)] #[grammar = "..."]` attribute also isn't in this
+/// snapshot to repoint at the generated file. Both are one-line changes in
+/// the real repo -- this function is written as if they were already in
+/// place.
+fn generate_pest_grammar() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=grammar/hgvs.abnf");
+
+    let abnf_source = std::fs::read_to_string("grammar/hgvs.abnf")?;
+    let settings = std::collections::HashMap::new();
+    let pest_source = abnf_to_pest::translate(&abnf_source, &settings)?;
+
+    let out_dir = std::env::var("OUT_DIR")?;
+    let out_path = std::path::Path::new(&out_dir).join("hgvs_grammar.pest");
+    std::fs::write(out_path, pest_source)?;
+
+    Ok(())
+}
@@ -1,10 +1,75 @@
+use crate::data::ExonData;
 use crate::error::HgvsError;
-use crate::sequence::{MemSequence, Sequence, SliceSequence, SplicedSequence, TranslatedSequence};
-use crate::structs::{Anchor, CVariant, NaEdit, ProteinPos, TranscriptPos};
+use crate::genetic_code::{GeneticCodeTable, SelenocysteineSites};
+use crate::sequence::{
+    MemSequence, Sequence, SliceSequence, SplicedSequence, TranslatedSequenceWithRecoding,
+};
+use crate::structs::{
+    iupac_seq_matches, Anchor, BaseOffsetPosition, CVariant, GenomicPos, NaEdit, ProteinPos,
+    TranscriptPos,
+};
+
+/// Offset windows, in bases from the nearest exon/intron boundary, used to
+/// classify how deep into an intron a [`BaseOffsetPosition`]'s offset
+/// reaches. Defaults to the widely-used canonical=2 (the near-invariant
+/// donor/acceptor dinucleotide, e.g. `c.123+1`/`c.123+2`) and extended=8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpliceRegionWindow {
+    pub canonical: i32,
+    pub extended: i32,
+}
+
+impl Default for SpliceRegionWindow {
+    fn default() -> Self {
+        Self {
+            canonical: 2,
+            extended: 8,
+        }
+    }
+}
+
+/// How a nonzero-offset [`BaseOffsetPosition`] classifies against
+/// [`SpliceRegionWindow`]. Each variant carries the genomic coordinate the
+/// offset resolves to -- computed from the flanking [`ExonData`] boundary,
+/// since intronic bases have no position in the transcript sequence itself
+/// -- so callers can report *where* the disruption falls without
+/// re-deriving it from the exon structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpliceConsequence {
+    /// Within the canonical donor/acceptor dinucleotide.
+    CanonicalSite { genomic_flank: GenomicPos },
+    /// Outside the canonical dinucleotide but still within the wider
+    /// splice-region window.
+    SpliceRegion { genomic_flank: GenomicPos },
+    /// Inside the known intron but beyond the splice-region window.
+    DeepIntronic { genomic_flank: GenomicPos },
+}
+
+/// How [`AltSeqBuilder::build_altseq`] reacts when a variant's stated
+/// reference sequence doesn't match the transcript sequence at its
+/// resolved interval (after IUPAC ambiguity codes are reconciled).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RefMismatchPolicy {
+    /// Fail with [`HgvsError::TranscriptMismatch`] -- the crate's original
+    /// behavior, and still the right default for callers who need to trust
+    /// the stated reference.
+    #[default]
+    Strict,
+    /// Ignore the mismatch and build the alt sequence from the transcript
+    /// as fetched, trusting it over the variant's stated `ref_`. Useful
+    /// against providers whose sequences carry ambiguity codes or minor
+    /// drift from the accession the variant was called against.
+    Warn,
+}
 
 /// Represents the data for a transcript with a variant applied.
 pub struct AltTranscriptData {
     pub transcript_sequence: String,
+    /// Translation of `transcript_sequence` from `cds_start_index` through
+    /// the end of the mRNA, not just the original CDS -- so a frameshift or
+    /// stop-loss that pushes the true stop codon into the 3'UTR is still
+    /// captured here, and callers scanning for the next `*` don't need to
+    /// re-fetch or re-translate anything to resolve an extension length.
     pub aa_sequence: String,
     pub cds_start_index: TranscriptPos,
     pub cds_end_index: TranscriptPos,
@@ -13,8 +78,25 @@ pub struct AltTranscriptData {
     /// The index of the first affected amino acid.
     pub variant_start_aa: Option<ProteinPos>,
     pub frameshift_start: Option<ProteinPos>,
+    /// 1-based count of residues from `frameshift_start` up to and
+    /// including the new stop codon -- the `N` in `p.(Arg97ProfsTer23)`.
+    /// `None` when `is_frameshift` is false, or when the shifted frame
+    /// never hits a stop within `aa_sequence`.
+    pub frameshift_term_offset: Option<u32>,
+    /// Number of residues appended past the original stop when it's lost
+    /// or mutated and translation reads through into the 3'UTR -- the `N`
+    /// in `p.(Ter110GlnextTer17)`. `None` when the stop is intact, when
+    /// the variant is a frameshift (see `frameshift_term_offset` instead),
+    /// or when no downstream in-frame stop exists to terminate the
+    /// extension.
+    pub extension_len: Option<u32>,
     pub is_substitution: bool,
     pub is_ambiguous: bool,
+    /// Set when the variant's position resolves into an intron rather than
+    /// the transcript sequence -- the edit was classified against the exon
+    /// structure, not applied to `transcript_sequence`/`aa_sequence`, both
+    /// of which are left untouched (identical to the unmutated transcript).
+    pub splice_consequence: Option<SpliceConsequence>,
     /// The original cDNA variant.
     pub c_variant: CVariant,
 }
@@ -25,10 +107,32 @@ pub struct AltSeqBuilder<'a> {
     pub cds_start_index: TranscriptPos,
     pub cds_end_index: TranscriptPos,
     pub protein_accession: String,
+    /// Genetic code used to translate the mutated CDS. Defaults to the
+    /// standard table when not overridden by the caller.
+    pub genetic_code_table: GeneticCodeTable,
+    /// Codon positions where an in-frame `TGA` is a selenocysteine recoding
+    /// site rather than a stop. Empty ([`SelenocysteineSites::none`]) unless
+    /// the caller knows this transcript is a selenoprotein.
+    pub selenocysteine_sites: SelenocysteineSites,
+    /// What to do if the variant's stated `ref_` doesn't match the
+    /// transcript. Defaults to [`RefMismatchPolicy::Strict`].
+    pub ref_mismatch_policy: RefMismatchPolicy,
+    /// Exon/intron structure for the transcript, used to resolve a
+    /// nonzero-offset `BaseOffsetPosition` to a [`SpliceConsequence`]
+    /// instead of erroring outright. An empty slice means every intronic
+    /// variant still errors, since there's no boundary to resolve it against.
+    pub exons: &'a [ExonData],
+    /// Offset windows used to classify how a resolved intronic position
+    /// splits between canonical-site, splice-region, and deep-intronic.
+    pub splice_region: SpliceRegionWindow,
 }
 
 impl<'a> AltSeqBuilder<'a> {
     pub fn build_altseq(&self) -> Result<AltTranscriptData, HgvsError> {
+        if let Some(splice_consequence) = self.classify_splice()? {
+            return self.build_unmutated_with_splice_consequence(splice_consequence);
+        }
+
         let (start_idx, end_idx) = self.get_variant_indices()?;
 
         // --- Validate reference sequence ---
@@ -43,7 +147,9 @@ impl<'a> AltSeqBuilder<'a> {
                         end: end_idx,
                     }
                     .to_string();
-                    if actual_ref != *r {
+                    if !iupac_seq_matches(r, &actual_ref)
+                        && self.ref_mismatch_policy == RefMismatchPolicy::Strict
+                    {
                         return Err(HgvsError::TranscriptMismatch {
                             expected: r.to_string(),
                             found: actual_ref,
@@ -147,6 +253,14 @@ impl<'a> AltSeqBuilder<'a> {
 
                 (is_subst, is_fs, res)
             }
+            // Coordinate-only deletions (chunk19-3), e.g. a multi-exon
+            // `c.(4_100)del` with no stated ref bases, fall straight through
+            // this branch: `r_len` comes from `end_idx - start_idx` and the
+            // spliced-out span is read lazily off `self.transcript_sequence`
+            // (itself backed by `DataProvider::get_seq`), so there's nothing
+            // size-specific here -- whether the net change lands in-frame
+            // (`delins`) or out of frame (`fs`) already falls out of the
+            // same `r_len % 3` check used for small deletions.
             NaEdit::Del { ref_, .. } => {
                 let r_len = if let Some(r) = ref_ {
                     if r.chars().all(|c| c.is_ascii_digit()) {
@@ -174,6 +288,13 @@ impl<'a> AltSeqBuilder<'a> {
                 .to_string();
                 (false, (r_len as i32) % 3 != 0, res)
             }
+            // Stated-length-only insertion (e.g. `ins50`): the number of
+            // inserted bases is known but not which bases, so the mutated
+            // CDS can't be reconstructed -- same ambiguous case as
+            // `alt: None` below, just with a count attached.
+            NaEdit::Ins { alt: Some(alt), .. } if alt.chars().all(|c| c.is_ascii_digit()) => {
+                (false, false, self.transcript_sequence.to_string())
+            }
             NaEdit::Ins { alt: Some(alt), .. } => {
                 let alt_seq = MemSequence(alt.clone());
                 let ins_pos = (start_idx + 1).min(self.transcript_sequence.len());
@@ -328,6 +449,31 @@ impl<'a> AltSeqBuilder<'a> {
                 (false, net_change % 3 != 0, res)
             }
             NaEdit::None => (false, false, self.transcript_sequence.to_string()),
+            // Symbolic/imprecise insertion (e.g. from a VCF `<INS>` allele):
+            // we know an insertion happens here but not its sequence or
+            // exact length, so the mutated CDS can't be reconstructed.
+            // `is_ambiguous` below routes this straight to a `p.?` consequence.
+            NaEdit::Ins { alt: None, .. } => (false, false, self.transcript_sequence.to_string()),
+            // STATUS: NOT IMPLEMENTED. chunk18-5 asked for real `con`-edit
+            // handling here (donor slice, reverse-complement per strand,
+            // splice via `SplicedSequence`, frameshift from the net length
+            // change) -- that is blocked, not delivered. Conversion (`con`)
+            // edits -- a transcript interval replaced by sequence copied
+            // from another, possibly reverse-complemented, donor region --
+            // have no representation here: `NaEdit` (defined in `edits.rs`,
+            // which isn't part of this checkout) has no `Con` variant to
+            // match on, so `parse_na_edit` already degrades a parsed `con`
+            // edit to `NaEdit::None` with a diagnostic rather than reaching
+            // this match at all (see its `Rule::dna_con` arm in
+            // `parser.rs`). If `NaEdit` gains a `Con { donor_ac, donor_start,
+            // donor_end, donor_strand, .. }` variant upstream, handling it
+            // here is mechanical: slice the donor the same way `NaEdit::Repeat`
+            // above slices its repeat unit (`SliceSequence`, reverse-complemented
+            // with `crate::utils::reverse_complement` as the `NaEdit::Inv` arm
+            // does when `donor_strand` disagrees with this transcript's), splice it in
+            // with `SplicedSequence`, and derive `is_frameshift` from
+            // `(donor_len - (end_idx - start_idx)) % 3 != 0` exactly like the
+            // repeat-unit net-length calculation just above.
             _ => {
                 return Err(HgvsError::UnsupportedOperation(
                     "Unsupported edit for altseq".into(),
@@ -335,6 +481,36 @@ impl<'a> AltSeqBuilder<'a> {
             }
         };
 
+        let is_ambiguous_seed = match &self.var_c.posedit.edit {
+            NaEdit::Ins { alt: None, .. } => true,
+            NaEdit::Ins { alt: Some(alt), .. } => alt.chars().all(|c| c.is_ascii_digit()),
+            _ => false,
+        };
+
+        self.finish_altseq(
+            alt_transcript,
+            is_substitution,
+            is_frameshift,
+            variant_start_aa,
+            is_ambiguous_seed,
+        )
+    }
+
+    /// Translates a fully-resolved mutated transcript sequence into the rest
+    /// of an [`AltTranscriptData`] -- amino acid translation, frameshift/
+    /// stop-loss detection, ambiguity -- shared by [`Self::build_altseq`]'s
+    /// single-edit path and [`Self::build_cis_allele_altseq`]'s joint
+    /// multi-edit path, so the two don't drift on how a mutated transcript
+    /// is turned into a protein prediction.
+    fn finish_altseq(
+        &self,
+        alt_transcript: String,
+        is_substitution: bool,
+        is_frameshift: bool,
+        variant_start_aa: Option<ProteinPos>,
+        is_ambiguous_seed: bool,
+    ) -> Result<AltTranscriptData, HgvsError> {
+        let mut is_ambiguous = is_ambiguous_seed;
         let cds_start = self.cds_start_index.0 as usize;
         let alt_transcript_seq = MemSequence(alt_transcript);
         let aa_sequence = if cds_start < alt_transcript_seq.len() {
@@ -343,7 +519,12 @@ impl<'a> AltSeqBuilder<'a> {
                 start: cds_start,
                 end: alt_transcript_seq.len(),
             };
-            TranslatedSequence { inner: &slice }.to_string()
+            TranslatedSequenceWithRecoding {
+                inner: &slice,
+                table: self.genetic_code_table,
+                selenocysteine_sites: &self.selenocysteine_sites,
+            }
+            .to_string()
         } else {
             "".to_string()
         };
@@ -366,6 +547,60 @@ impl<'a> AltSeqBuilder<'a> {
             }
         }
 
+        // 1-based count from the first affected residue up to and
+        // including the new stop, e.g. `p.(Arg97ProfsTer23)`'s `23`. Unknown
+        // (and the variant marked ambiguous) if the shifted frame runs off
+        // the end of `aa_sequence` -- which already extends past the
+        // original CDS end into the 3'UTR -- without ever hitting a stop.
+        let frameshift_term_offset = if is_fs {
+            let term_offset = variant_start_aa.and_then(|v_start_aa| {
+                aa_sequence
+                    .chars()
+                    .skip(v_start_aa.0 as usize)
+                    .position(|c| c == '*')
+                    .map(|i| (i + 1) as u32)
+            });
+            if term_offset.is_none() {
+                is_ambiguous = true;
+            }
+            term_offset
+        } else {
+            None
+        };
+
+        // Stop-loss: the residue at the original stop position is no
+        // longer `'*'`. Scan forward for the next in-frame stop and report
+        // how many extra residues got appended to the protein -- the `N`
+        // in `p.(Ter110GlnextTer17)` -- same reasoning as the frameshift
+        // scan above. Mutually exclusive with a frameshift: a shifted
+        // frame doesn't read the original stop position as a stop either,
+        // but that's reported via `frameshift_term_offset` instead.
+        let extension_len: Option<u32> = if !is_fs {
+            let aa_chars: Vec<char> = aa_sequence.chars().collect();
+            let stop_aa_idx = ((self.cds_end_index.0 - self.cds_start_index.0) / 3).max(0) as usize;
+            if stop_aa_idx < aa_chars.len() && aa_chars[stop_aa_idx] != '*' {
+                let mut ext_len = 0u32;
+                let mut found_stop = false;
+                for &c in aa_chars.iter().skip(stop_aa_idx + 1) {
+                    ext_len += 1;
+                    if c == '*' {
+                        found_stop = true;
+                        break;
+                    }
+                }
+                if found_stop {
+                    Some(ext_len)
+                } else {
+                    is_ambiguous = true;
+                    None
+                }
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
         Ok(AltTranscriptData {
             transcript_sequence: alt_transcript_seq.0,
             aa_sequence,
@@ -375,8 +610,292 @@ impl<'a> AltSeqBuilder<'a> {
             is_frameshift: is_fs,
             variant_start_aa,
             frameshift_start: if is_fs { variant_start_aa } else { None },
+            frameshift_term_offset,
+            extension_len,
             is_substitution,
-            is_ambiguous: false,
+            is_ambiguous,
+            splice_consequence: None,
+            c_variant: self.var_c.clone(),
+        })
+    }
+
+    /// Applies every member of an in-cis allele (`c.[4A>T;7T>G]`, parsed by
+    /// [`crate::parser::parse_allele`] into its ordered `CVariant`
+    /// components) to the same transcript molecule in one pass, instead of
+    /// one at a time -- so two adjacent substitutions that together create a
+    /// frameshift or a single in-frame `delins`, for example, are reflected
+    /// in one combined protein prediction. Members are resolved and sorted
+    /// by transcript position first; [`HgvsError::ValidationError`] if any
+    /// two resolve to overlapping spans, since there's no well-defined way
+    /// to apply overlapping edits to the same molecule. Only edit kinds with
+    /// an unambiguous replacement span are supported per member -- see
+    /// [`Self::resolve_allele_member`].
+    ///
+    /// The returned [`AltTranscriptData::c_variant`] is `self.var_c`
+    /// (whichever single `CVariant` this builder was constructed with, by
+    /// convention the allele's first member) rather than the full allele:
+    /// `AltTranscriptData` has no field for a multi-edit allele, since there
+    /// is no `SequenceVariant::Allele` arm -- `SequenceVariant` is defined in
+    /// `coords.rs`, which isn't part of this checkout, and is matched
+    /// exhaustively in too many places to safely extend it here. Callers
+    /// (see [`crate::mapper::VariantMapper::c_to_p_allele`]) instead carry an
+    /// allele around as the plain `Vec<CVariant>` [`crate::parser::parse_allele`]
+    /// already returns.
+    pub fn build_cis_allele_altseq(
+        &self,
+        members: &[crate::structs::PosEdit<crate::structs::BaseOffsetInterval, NaEdit>],
+    ) -> Result<AltTranscriptData, HgvsError> {
+        if members.is_empty() {
+            return Err(HgvsError::ValidationError(
+                "Phased allele has no members".into(),
+            ));
+        }
+
+        let mut resolved = Vec::with_capacity(members.len());
+        for member in members {
+            let pos = member
+                .pos
+                .as_ref()
+                .ok_or_else(|| HgvsError::ValidationError("Missing position".into()))?;
+            let start = self.pos_to_idx(&pos.start)?;
+            let mut end = if let Some(e) = &pos.end {
+                self.pos_to_idx(e)?
+            } else {
+                start
+            };
+            end += 1;
+            let (r_start, r_end, replacement) = self.resolve_allele_member(&member.edit, start, end)?;
+            let raw_start_c_0 = pos.start.base.to_index();
+            resolved.push((r_start, r_end, replacement, raw_start_c_0));
+        }
+        resolved.sort_by_key(|(start, ..)| *start);
+        for pair in resolved.windows(2) {
+            if pair[1].0 < pair[0].1 {
+                return Err(HgvsError::ValidationError(
+                    "Phased allele members have overlapping transcript spans".into(),
+                ));
+            }
+        }
+
+        let mut pieces_owned: Vec<MemSequence> = Vec::new();
+        let mut cursor = 0usize;
+        let mut net_change: i32 = 0;
+        for (start, end, replacement, _) in &resolved {
+            pieces_owned.push(MemSequence(
+                SliceSequence {
+                    inner: self.transcript_sequence,
+                    start: cursor,
+                    end: *start,
+                }
+                .to_string(),
+            ));
+            pieces_owned.push(MemSequence(replacement.clone()));
+            net_change += replacement.len() as i32 - (*end as i32 - *start as i32);
+            cursor = *end;
+        }
+        pieces_owned.push(MemSequence(
+            SliceSequence {
+                inner: self.transcript_sequence,
+                start: cursor,
+                end: self.transcript_sequence.len(),
+            }
+            .to_string(),
+        ));
+        let pieces: Vec<&dyn Sequence> = pieces_owned.iter().map(|p| p as &dyn Sequence).collect();
+        let alt_transcript = SplicedSequence { pieces }.to_string();
+
+        let variant_start_aa = Some(ProteinPos(resolved[0].3 .0.max(0) / 3));
+        let is_frameshift = net_change % 3 != 0;
+
+        self.finish_altseq(alt_transcript, false, is_frameshift, variant_start_aa, false)
+    }
+
+    /// Resolves one phased-allele member's edit to the transcript span it
+    /// replaces and the sequence to splice in there, given the edit's
+    /// already-resolved `(start_idx, end_idx)` -- the same spans
+    /// [`Self::build_altseq`]'s single-edit path computes for the
+    /// corresponding `NaEdit` kind, but returned as a span-and-replacement
+    /// pair instead of a whole rebuilt transcript, so
+    /// [`Self::build_cis_allele_altseq`] can splice every member's
+    /// replacement into the same pass. Only `RefAlt`, `Del`, sequence-bearing
+    /// `Ins`, and `Dup` members are supported; any other kind (including
+    /// `Inv` and `Repeat`, which `build_altseq` applies on its own) is
+    /// rejected with [`HgvsError::UnsupportedOperation`].
+    fn resolve_allele_member(
+        &self,
+        edit: &NaEdit,
+        start_idx: usize,
+        end_idx: usize,
+    ) -> Result<(usize, usize, String), HgvsError> {
+        match edit {
+            NaEdit::RefAlt { ref_: None, alt: Some(alt), .. } => {
+                let ins_pos = (start_idx + 1).min(self.transcript_sequence.len());
+                Ok((ins_pos, ins_pos, alt.clone()))
+            }
+            NaEdit::RefAlt { alt, .. } => Ok((start_idx, end_idx, alt.clone().unwrap_or_default())),
+            NaEdit::Del { .. } => Ok((start_idx, end_idx, String::new())),
+            NaEdit::Ins { alt: Some(alt), .. } if !alt.chars().all(|c| c.is_ascii_digit()) => {
+                let ins_pos = (start_idx + 1).min(self.transcript_sequence.len());
+                Ok((ins_pos, ins_pos, alt.clone()))
+            }
+            NaEdit::Dup { ref_, .. } => {
+                let dup_str = match ref_ {
+                    Some(r) if !r.chars().all(|c| c.is_ascii_digit()) => r.clone(),
+                    _ => SliceSequence {
+                        inner: self.transcript_sequence,
+                        start: start_idx,
+                        end: end_idx,
+                    }
+                    .to_string(),
+                };
+                let ins_pos = end_idx.min(self.transcript_sequence.len());
+                Ok((ins_pos, ins_pos, dup_str))
+            }
+            _ => Err(HgvsError::UnsupportedOperation(
+                "Unsupported edit in phased allele member".into(),
+            )),
+        }
+    }
+
+    /// Checks the variant's start (and end, if present) position for a
+    /// nonzero intronic offset and classifies it against `self.exons`. Both
+    /// ends are checked so a `c.123+5_124-3del`-style interval isn't missed
+    /// just because its second endpoint is the one in the intron; the start
+    /// position wins if both happen to be intronic.
+    fn classify_splice(&self) -> Result<Option<SpliceConsequence>, HgvsError> {
+        let pos = self
+            .var_c
+            .posedit
+            .pos
+            .as_ref()
+            .ok_or_else(|| HgvsError::ValidationError("Missing position".into()))?;
+
+        if let Some(consequence) = self.classify_splice_position(&pos.start)? {
+            return Ok(Some(consequence));
+        }
+        if let Some(end) = &pos.end {
+            if let Some(consequence) = self.classify_splice_position(end)? {
+                return Ok(Some(consequence));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Resolves a single `BaseOffsetPosition`'s offset to a
+    /// [`SpliceConsequence`], or `Ok(None)` if it isn't intronic at all.
+    fn classify_splice_position(
+        &self,
+        pos: &BaseOffsetPosition,
+    ) -> Result<Option<SpliceConsequence>, HgvsError> {
+        let offset = match pos.offset {
+            Some(o) if o.0 != 0 => o.0,
+            _ => return Ok(None),
+        };
+
+        let base_idx = pos.base.to_index();
+        let downstream = offset > 0;
+        let boundary_exon = if downstream {
+            self.exons.iter().find(|e| e.transcript_end.0 == base_idx.0)
+        } else {
+            self.exons
+                .iter()
+                .find(|e| e.transcript_start.0 == base_idx.0)
+        }
+        .ok_or_else(|| {
+            HgvsError::UnsupportedOperation(format!(
+                "No exon boundary at transcript position {} to resolve intronic offset {:+}",
+                base_idx.0, offset
+            ))
+        })?;
+
+        let intron_len = self.intron_length(boundary_exon, downstream).ok_or_else(|| {
+            HgvsError::UnsupportedOperation(
+                "No adjacent intron in the exon structure to resolve this offset".into(),
+            )
+        })?;
+
+        if offset.unsigned_abs() as i32 > intron_len {
+            return Err(HgvsError::ValidationError(format!(
+                "Intronic offset {:+} exceeds the known intron length ({intron_len} bp)",
+                offset
+            )));
+        }
+
+        let forward = boundary_exon.alt_strand >= 0;
+        let genomic_flank = match (downstream, forward) {
+            (true, true) => GenomicPos(boundary_exon.reference_end.0 + offset),
+            (false, true) => GenomicPos(boundary_exon.reference_start.0 + offset),
+            (true, false) => GenomicPos(boundary_exon.reference_start.0 - offset),
+            (false, false) => GenomicPos(boundary_exon.reference_end.0 - offset),
+        };
+
+        let magnitude = offset.unsigned_abs() as i32;
+        Ok(Some(if magnitude <= self.splice_region.canonical {
+            SpliceConsequence::CanonicalSite { genomic_flank }
+        } else if magnitude <= self.splice_region.extended {
+            SpliceConsequence::SpliceRegion { genomic_flank }
+        } else {
+            SpliceConsequence::DeepIntronic { genomic_flank }
+        }))
+    }
+
+    /// Length, in genomic bases, of the intron adjacent to `exon` on the
+    /// downstream (`transcript_end`) or upstream (`transcript_start`) side.
+    /// `None` if `exon` has no neighbor on that side (e.g. the first/last
+    /// exon), meaning there's no intron to resolve the offset against.
+    fn intron_length(&self, exon: &ExonData, downstream: bool) -> Option<i32> {
+        let idx = self
+            .exons
+            .iter()
+            .position(|e| std::ptr::eq(e, exon))?;
+        if downstream {
+            let next = self.exons.get(idx + 1)?;
+            Some((next.reference_start.0 - exon.reference_end.0).abs())
+        } else {
+            let prev = idx.checked_sub(1).and_then(|i| self.exons.get(i))?;
+            Some((exon.reference_start.0 - prev.reference_end.0).abs())
+        }
+    }
+
+    /// Builds the splice-classified result for an intronic variant: the
+    /// transcript/protein are left exactly as fetched -- we don't know how
+    /// splicing actually changes once a donor/acceptor site is disrupted --
+    /// and the consequence is surfaced via `splice_consequence` instead.
+    fn build_unmutated_with_splice_consequence(
+        &self,
+        splice_consequence: SpliceConsequence,
+    ) -> Result<AltTranscriptData, HgvsError> {
+        let cds_start = self.cds_start_index.0 as usize;
+        let aa_sequence = if cds_start < self.transcript_sequence.len() {
+            let slice = SliceSequence {
+                inner: self.transcript_sequence,
+                start: cds_start,
+                end: self.transcript_sequence.len(),
+            };
+            TranslatedSequenceWithRecoding {
+                inner: &slice,
+                table: self.genetic_code_table,
+                selenocysteine_sites: &self.selenocysteine_sites,
+            }
+            .to_string()
+        } else {
+            "".to_string()
+        };
+
+        Ok(AltTranscriptData {
+            transcript_sequence: self.transcript_sequence.to_string(),
+            aa_sequence,
+            cds_start_index: self.cds_start_index,
+            cds_end_index: self.cds_end_index,
+            protein_accession: self.protein_accession.clone(),
+            is_frameshift: false,
+            variant_start_aa: None,
+            frameshift_start: None,
+            frameshift_term_offset: None,
+            extension_len: None,
+            is_substitution: false,
+            is_ambiguous: true,
+            splice_consequence: Some(splice_consequence),
             c_variant: self.var_c.clone(),
         })
     }
@@ -442,3 +961,315 @@ impl<'a> AltSeqBuilder<'a> {
         Ok(idx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::SequenceVariant;
+
+    fn c_variant(hgvs: &str) -> CVariant {
+        match crate::parse_hgvs_variant(hgvs).unwrap() {
+            SequenceVariant::Coding(v) => v,
+            other => panic!("expected a coding variant, got {other:?}"),
+        }
+    }
+
+    fn builder<'a>(
+        var_c: &'a CVariant,
+        transcript_sequence: &'a dyn Sequence,
+        ref_mismatch_policy: RefMismatchPolicy,
+    ) -> AltSeqBuilder<'a> {
+        AltSeqBuilder {
+            var_c,
+            transcript_sequence,
+            cds_start_index: TranscriptPos(0),
+            cds_end_index: TranscriptPos(9),
+            protein_accession: "NP_TEST.1".to_string(),
+            genetic_code_table: GeneticCodeTable::Standard,
+            selenocysteine_sites: SelenocysteineSites::none(),
+            ref_mismatch_policy,
+            exons: &[],
+            splice_region: SpliceRegionWindow::default(),
+        }
+    }
+
+    #[test]
+    fn test_build_altseq_strict_policy_errors_on_genuine_ref_mismatch() {
+        let var_c = c_variant("NM_TEST.1:c.1A>T");
+        let seq = MemSequence("CTGAAACCC".to_string());
+        let err = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap_err();
+        assert!(matches!(err, HgvsError::TranscriptMismatch { .. }));
+    }
+
+    #[test]
+    fn test_build_altseq_strict_policy_tolerates_iupac_ambiguity_code() {
+        // 'R' (A or G) in the transcript is IUPAC-compatible with the
+        // variant's stated 'A', so this isn't a genuine mismatch and
+        // succeeds even under the default Strict policy.
+        let var_c = c_variant("NM_TEST.1:c.1A>T");
+        let seq = MemSequence("RTGAAACCC".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert_eq!(alt.transcript_sequence, "TTGAAACCC");
+    }
+
+    #[test]
+    fn test_build_altseq_warn_policy_tolerates_a_genuine_ref_mismatch() {
+        let var_c = c_variant("NM_TEST.1:c.1A>T");
+        let seq = MemSequence("CTGAAACCC".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Warn)
+            .build_altseq()
+            .unwrap();
+        assert_eq!(alt.transcript_sequence, "TTGAAACCC");
+    }
+
+    #[test]
+    fn test_build_altseq_inv_reverse_complements_the_interval_in_place() {
+        // ATG AAA CCC -> ATG TTT CCC: inverting "AAA" (c.4_6) reverse-complements
+        // to "TTT" in place, with no length change, so this comes back as a
+        // plain amino-acid substitution (Lys -> Phe) rather than a frameshift.
+        let var_c = c_variant("NM_TEST.1:c.4_6inv");
+        let seq = MemSequence("ATGAAACCC".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert_eq!(alt.transcript_sequence, "ATGTTTCCC");
+        assert!(!alt.is_frameshift);
+        assert_eq!(alt.aa_sequence, "MFP");
+    }
+
+    // --- Splice-aware intronic positions ---
+
+    use crate::structs::{BaseOffsetInterval, HgvsTranscriptPos, IntronicOffset, PosEdit};
+
+    fn two_exons() -> Vec<ExonData> {
+        vec![
+            ExonData {
+                transcript_start: TranscriptPos(0),
+                transcript_end: TranscriptPos(9),
+                reference_start: GenomicPos(1000),
+                reference_end: GenomicPos(1009),
+                alt_strand: 1,
+                cigar: "9M".to_string(),
+            },
+            ExonData {
+                transcript_start: TranscriptPos(9),
+                transcript_end: TranscriptPos(19),
+                reference_start: GenomicPos(1100),
+                reference_end: GenomicPos(1110),
+                alt_strand: 1,
+                cigar: "10M".to_string(),
+            },
+        ]
+    }
+
+    fn intronic_var_c(offset: i32) -> CVariant {
+        CVariant {
+            ac: "NM_TEST.1".to_string(),
+            gene: None,
+            posedit: PosEdit {
+                pos: Some(BaseOffsetInterval {
+                    start: BaseOffsetPosition {
+                        base: HgvsTranscriptPos(10),
+                        offset: Some(IntronicOffset(offset)),
+                        anchor: Anchor::CdsStart,
+                        uncertain: false,
+                    },
+                    end: None,
+                    uncertain: false,
+                }),
+                edit: NaEdit::RefAlt {
+                    ref_: Some("G".to_string()),
+                    alt: Some("A".to_string()),
+                },
+                uncertain: false,
+                predicted: false,
+            },
+        }
+    }
+
+    fn builder_with_exons<'a>(
+        var_c: &'a CVariant,
+        transcript_sequence: &'a dyn Sequence,
+        exons: &'a [ExonData],
+    ) -> AltSeqBuilder<'a> {
+        AltSeqBuilder {
+            exons,
+            ..builder(var_c, transcript_sequence, RefMismatchPolicy::Strict)
+        }
+    }
+
+    #[test]
+    fn test_build_altseq_classifies_canonical_splice_site() {
+        let var_c = intronic_var_c(1); // c.10+1
+        let seq = MemSequence("CTGAAACCCGGGGGGGGGG".to_string());
+        let exons = two_exons();
+        let alt = builder_with_exons(&var_c, &seq, &exons).build_altseq().unwrap();
+        assert!(alt.is_ambiguous);
+        assert_eq!(alt.transcript_sequence, "CTGAAACCCGGGGGGGGGG");
+        assert_eq!(
+            alt.splice_consequence,
+            Some(SpliceConsequence::CanonicalSite {
+                genomic_flank: GenomicPos(1010)
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_altseq_classifies_wider_splice_region() {
+        let var_c = intronic_var_c(5); // c.10+5, past the canonical dinucleotide
+        let seq = MemSequence("CTGAAACCCGGGGGGGGGG".to_string());
+        let exons = two_exons();
+        let alt = builder_with_exons(&var_c, &seq, &exons).build_altseq().unwrap();
+        assert_eq!(
+            alt.splice_consequence,
+            Some(SpliceConsequence::SpliceRegion {
+                genomic_flank: GenomicPos(1014)
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_altseq_classifies_deep_intronic() {
+        let var_c = intronic_var_c(50); // still within the 91bp intron
+        let seq = MemSequence("CTGAAACCCGGGGGGGGGG".to_string());
+        let exons = two_exons();
+        let alt = builder_with_exons(&var_c, &seq, &exons).build_altseq().unwrap();
+        assert_eq!(
+            alt.splice_consequence,
+            Some(SpliceConsequence::DeepIntronic {
+                genomic_flank: GenomicPos(1059)
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_altseq_errors_when_offset_exceeds_intron_length() {
+        let var_c = intronic_var_c(200); // the intron is only 91bp
+        let seq = MemSequence("CTGAAACCCGGGGGGGGGG".to_string());
+        let exons = two_exons();
+        let err = builder_with_exons(&var_c, &seq, &exons)
+            .build_altseq()
+            .unwrap_err();
+        assert!(matches!(err, HgvsError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_build_altseq_errors_on_intronic_offset_without_exon_data() {
+        let var_c = intronic_var_c(1);
+        let seq = MemSequence("CTGAAACCCGGGGGGGGGG".to_string());
+        let err = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap_err();
+        assert!(matches!(err, HgvsError::UnsupportedOperation(_)));
+    }
+
+    // --- Frameshift termination length ---
+
+    fn single_base_del_var_c() -> CVariant {
+        CVariant {
+            ac: "NM_TEST.1".to_string(),
+            gene: None,
+            posedit: PosEdit {
+                pos: Some(BaseOffsetInterval {
+                    start: BaseOffsetPosition {
+                        base: HgvsTranscriptPos(4),
+                        offset: None,
+                        anchor: Anchor::CdsStart,
+                        uncertain: false,
+                    },
+                    end: None,
+                    uncertain: false,
+                }),
+                edit: NaEdit::Del {
+                    ref_: None,
+                    uncertain: false,
+                },
+                uncertain: false,
+                predicted: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_build_altseq_computes_frameshift_term_offset() {
+        // Deleting c.4 shifts the frame from codon 1 onward; the shifted
+        // frame reads "AAC CCT TAG", hitting a stop 3 residues later.
+        let var_c = single_base_del_var_c();
+        let seq = MemSequence("ATGAAACCCTTAG".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert!(alt.is_frameshift);
+        assert_eq!(alt.frameshift_term_offset, Some(3));
+    }
+
+    #[test]
+    fn test_build_altseq_frameshift_term_offset_unknown_without_a_stop() {
+        // The shifted frame never hits a stop codon within aa_sequence, so
+        // the termination length is unknown and the variant is ambiguous.
+        let var_c = single_base_del_var_c();
+        let seq = MemSequence("ATGAAACCCAAACCC".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert!(alt.is_frameshift);
+        assert_eq!(alt.frameshift_term_offset, None);
+        assert!(alt.is_ambiguous);
+    }
+
+    // --- Stop-loss extension length ---
+
+    fn stop_loss_var_c() -> CVariant {
+        CVariant {
+            ac: "NM_TEST.1".to_string(),
+            gene: None,
+            posedit: PosEdit {
+                pos: Some(BaseOffsetInterval {
+                    start: BaseOffsetPosition {
+                        base: HgvsTranscriptPos(10),
+                        offset: None,
+                        anchor: Anchor::CdsStart,
+                        uncertain: false,
+                    },
+                    end: None,
+                    uncertain: false,
+                }),
+                edit: NaEdit::RefAlt {
+                    ref_: Some("T".to_string()),
+                    alt: Some("C".to_string()),
+                },
+                uncertain: false,
+                predicted: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_build_altseq_computes_stop_loss_extension_length() {
+        // c.10T>C mutates the stop codon "TAA" -> "CAA" (Gln), reading
+        // through "CAG" (Gln) before the next in-frame stop "TAG".
+        let var_c = stop_loss_var_c();
+        let seq = MemSequence("ATGAAACCCTAACAGTAG".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert!(!alt.is_frameshift);
+        assert_eq!(alt.extension_len, Some(2));
+    }
+
+    #[test]
+    fn test_build_altseq_stop_loss_extension_unknown_without_a_downstream_stop() {
+        let var_c = stop_loss_var_c();
+        let seq = MemSequence("ATGAAACCCTAACAGGGG".to_string());
+        let alt = builder(&var_c, &seq, RefMismatchPolicy::Strict)
+            .build_altseq()
+            .unwrap();
+        assert!(!alt.is_frameshift);
+        assert_eq!(alt.extension_len, None);
+        assert!(alt.is_ambiguous);
+    }
+}
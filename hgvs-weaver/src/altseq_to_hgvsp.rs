@@ -1,23 +1,96 @@
 use crate::altseq::AltTranscriptData;
 use crate::error::HgvsError;
-use crate::fmt::aa1_to_aa3;
+// fmt::aa1_to_aa3 offers the same 1-to-3 mapping but doesn't round-trip the
+// extended alphabet (Sec/Pyl); utils::aa1_to_aa3 does, so the p. builder
+// uses that one instead.
 use crate::structs::{AAPosition, AaEdit, AaInterval, PVariant, PosEdit, ProteinPos};
+use crate::utils::aa1_to_aa3;
+use crate::wfa;
+
+/// The category of protein-level change a predicted `p.` variant
+/// represents, tagged at the same branch in [`AltSeqToHgvsp::build_hgvsp_with_consequence`]
+/// that constructs the corresponding [`PVariant`] -- each early-return site
+/// already knows which case it's in, so this never requires re-analyzing
+/// the ref/alt amino-acid sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProteinConsequence {
+    Synonymous,
+    Missense,
+    Nonsense,
+    Frameshift,
+    StopLost,
+    StartLost,
+    InframeInsertion,
+    InframeDeletion,
+    InframeDelins,
+    Duplication,
+    /// The effect on the protein couldn't be determined (e.g. a symbolic
+    /// structural allele, or a change confined to the 3' UTR).
+    Unknown,
+}
+
+/// Selects how [`AltSeqToHgvsp`] resolves the ambiguity HGVS protein
+/// normalization leaves open for a `delins` that happens to end in a stop:
+/// more than one equally-valid representation exists, and ClinVar
+/// submitters and this crate don't always pick the same one.
+///
+/// (The other half of the HGVS protein rules -- trimming the common
+/// prefix/suffix and reporting a pure deletion or duplication at its most
+/// downstream position -- doesn't need a mode switch: the diff below
+/// already greedily extends the matched prefix as far right as it will go
+/// before registering the first divergence, which for a run of identical
+/// residues is already the same position HGVS calls 3'-most.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProteinNormalizationMode {
+    /// The crate's original behavior: when a `delins` ends in a stop and
+    /// only that stop matches the reference tail, the stop is treated as the
+    /// original one and left out of the reported range whenever the variant
+    /// type is a del/dup/inv (anything other than a raw substitution or
+    /// insertion).
+    Simplified,
+    /// Follows the HGVS protein nomenclature rules used by ClinVar
+    /// submitters: a `delins` is only collapsed to a plain `Ter` when the
+    /// stop itself is the sole affected residue -- any other residues
+    /// preceding the stop within the altered span are kept in the reported
+    /// range (e.g. `p.(Asn255_Pro256delinsTer)` rather than `p.(Asn255Ter)`).
+    ClinvarFaithful,
+}
+
+impl Default for ProteinNormalizationMode {
+    fn default() -> Self {
+        Self::Simplified
+    }
+}
 
 pub struct AltSeqToHgvsp<'a> {
     pub ref_aa: String,
     pub ref_cds_start_idx: usize,
     pub ref_cds_end_idx: usize,
     pub alt_data: &'a AltTranscriptData,
+    pub mode: ProteinNormalizationMode,
 }
 
 impl<'a> AltSeqToHgvsp<'a> {
     pub fn build_hgvsp(&self) -> Result<PVariant, HgvsError> {
+        Ok(self.build_hgvsp_with_consequence()?.0)
+    }
+
+    pub fn build_hgvsp_with_consequence(
+        &self,
+    ) -> Result<(PVariant, ProteinConsequence), HgvsError> {
+        if self.alt_data.is_ambiguous {
+            return Ok((self.build_unknown_variant()?, ProteinConsequence::Unknown));
+        }
+
         let alt_aa = &self.alt_data.aa_sequence;
         let ref_chars: Vec<char> = self.ref_aa.chars().collect();
         let alt_chars: Vec<char> = alt_aa.chars().collect();
 
         if self.ref_aa == *alt_aa {
-            return self.build_identity_variant();
+            return Ok((
+                self.build_identity_variant()?,
+                ProteinConsequence::Synonymous,
+            ));
         }
 
         // Find first difference
@@ -36,14 +109,31 @@ impl<'a> AltSeqToHgvsp<'a> {
 
         if start_idx > official_stop_idx as usize {
             // Difference is entirely in the 3' UTR and doesn't affect the protein.
-            return self.build_identity_variant();
+            return Ok((self.build_identity_variant()?, ProteinConsequence::Unknown));
         }
 
-        if self.alt_data.is_frameshift {
-            let ref_curr = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('*')).to_string();
-            let alt_curr = aa1_to_aa3(alt_chars.get(start_idx).cloned().unwrap_or('*')).to_string();
+        // Translation initiation is abolished when the initiator Met (protein
+        // position 0) itself is disrupted. Any downstream diff is irrelevant
+        // once the start codon is lost, so this short-circuits before the
+        // frameshift/substitution analysis below, which would otherwise
+        // describe it as a spurious in-place substitution.
+        if start_idx == 0 && ref_chars.first() == Some(&'M') && alt_chars.first() != Some(&'M') {
+            return Ok((
+                self.build_init_loss_variant()?,
+                ProteinConsequence::StartLost,
+            ));
+        }
 
-            // Find first stop in alt_aa starting from start_idx
+        if self.alt_data.is_frameshift {
+            let ref_curr = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('X')).to_string();
+            let alt_curr = aa1_to_aa3(alt_chars.get(start_idx).cloned().unwrap_or('X')).to_string();
+
+            // Find first stop in alt_aa starting from start_idx. alt_chars
+            // comes from AltTranscriptData::aa_sequence, which is already
+            // translated across the whole remaining mRNA (not truncated at
+            // the original CDS end -- see AltSeqBuilder::build_altseq), so
+            // this scan naturally runs into the 3'UTR and only comes up
+            // empty when the transcript itself has no further in-frame stop.
             let mut stop_idx = None;
             for (i, &c) in alt_chars.iter().enumerate().skip(start_idx) {
                 if c == '*' {
@@ -59,16 +149,18 @@ impl<'a> AltSeqToHgvsp<'a> {
                 ("?".to_string(), true)
             };
 
-            return self.create_variant(
-                ProteinPos(start_idx as i32),
-                None,
-                Some(ref_curr),
-                Some(alt_curr),
-                term,
-                Some(length),
-                true,
-                false,
-            );
+            return self
+                .create_variant(
+                    ProteinPos(start_idx as i32),
+                    None,
+                    Some(ref_curr),
+                    Some(alt_curr),
+                    term,
+                    Some(length),
+                    true,
+                    false,
+                )
+                .map(|v| (v, ProteinConsequence::Frameshift));
         }
 
         // Non-frameshift
@@ -113,6 +205,14 @@ impl<'a> AltSeqToHgvsp<'a> {
                     // 1-vs-1 substitution at C-terminus.
                     // Treat as Original Stop (Trim).
                     is_premature_stop = false;
+                } else if self.mode == ProteinNormalizationMode::ClinvarFaithful {
+                    // More than one residue precedes the stop within the
+                    // altered span, regardless of the underlying c. edit
+                    // type -- HGVS only collapses to a plain `Ter` when the
+                    // stop is the sole affected residue, so keep the full
+                    // range here (e.g. `p.(Asn255_Pro256delinsTer)` instead
+                    // of `p.(Asn255Ter)`).
+                    is_premature_stop = true;
                 } else {
                     // Check variant type for other cases.
                     match &self.alt_data.c_variant.posedit.edit {
@@ -164,22 +264,29 @@ impl<'a> AltSeqToHgvsp<'a> {
         // 1. Check for Nonsense (Substitution to Ter)
         // Only classify as Nonsense if the stop codon is part of the mismatch (not the preserved tail).
         if alt_chars.get(start_idx) == Some(&'*') && start_idx < alt_end {
-            let ref_curr = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('*')).to_string();
-            return self.create_variant(
-                ProteinPos(start_idx as i32),
-                None,
-                Some(ref_curr),
-                Some("Ter".to_string()),
-                None,
-                None,
-                false,
-                false,
-            );
+            let ref_curr = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('X')).to_string();
+            return self
+                .create_variant(
+                    ProteinPos(start_idx as i32),
+                    None,
+                    Some(ref_curr),
+                    Some("Ter".to_string()),
+                    None,
+                    None,
+                    false,
+                    false,
+                )
+                .map(|v| (v, ProteinConsequence::Nonsense));
         }
 
         // 2. Check for Stop Loss (Extension)
         if ref_chars.get(start_idx) == Some(&'*') {
-            // Find length of extension in alt_chars
+            // Find length of extension in alt_chars. Same reasoning as the
+            // frameshift scan above: aa_sequence already spans past the old
+            // stop into the 3'UTR, so this resolves to a concrete length
+            // whenever the mRNA contains a downstream in-frame stop, and
+            // only falls back to "?" when translation runs off the end of
+            // the available sequence with no stop found.
             let mut ext_len = 0;
             let mut found_stop = false;
             for &c in alt_chars.iter().skip(start_idx + 1) {
@@ -190,39 +297,69 @@ impl<'a> AltSeqToHgvsp<'a> {
                 }
             }
 
-            let alt_curr = aa1_to_aa3(alt_chars.get(start_idx).cloned().unwrap_or('*')).to_string();
+            let alt_curr = aa1_to_aa3(alt_chars.get(start_idx).cloned().unwrap_or('X')).to_string();
             let length = if found_stop {
                 Some(ext_len.to_string())
             } else {
                 Some("?".to_string())
             };
 
-            return Ok(PVariant {
-                ac: self.alt_data.protein_accession.clone(),
-                gene: None,
-                posedit: PosEdit {
-                    pos: Some(AaInterval {
-                        start: AAPosition {
-                            base: ProteinPos(start_idx as i32).to_hgvs(),
-                            aa: "Ter".to_string(),
+            return Ok((
+                PVariant {
+                    ac: self.alt_data.protein_accession.clone(),
+                    gene: None,
+                    posedit: PosEdit {
+                        pos: Some(AaInterval {
+                            start: AAPosition {
+                                base: ProteinPos(start_idx as i32).to_hgvs(),
+                                aa: "Ter".to_string(),
+                                uncertain: false,
+                            },
+                            end: None,
                             uncertain: false,
+                        }),
+                        edit: AaEdit::Ext {
+                            ref_: "Ter".into(),
+                            alt: alt_curr,
+                            aaterm: Some("*".to_string()),
+                            length,
+                            uncertain: !found_stop,
                         },
-                        end: None,
                         uncertain: false,
-                    }),
-                    edit: AaEdit::Ext {
-                        ref_: "Ter".into(),
-                        alt: alt_curr,
-                        aaterm: Some("*".to_string()),
-                        length,
-                        uncertain: !found_stop,
+                        predicted: false,
                     },
-                    uncertain: false,
-                    predicted: false,
                 },
-            });
+                ProteinConsequence::StopLost,
+            ));
+        }
+
+        // Guard the slices below: degenerate ref/alt/CDS indices (e.g. an
+        // alt sequence shorter than the computed start/end) must not panic,
+        // they should be reported as a malformed variant instead.
+        if start_idx > ref_end
+            || ref_end > ref_chars.len()
+            || start_idx > alt_end
+            || alt_end > alt_chars.len()
+        {
+            return Err(HgvsError::ValidationError(format!(
+                "computed AA range start={start_idx}, ref_end={ref_end} (of {}), alt_end={alt_end} (of {}) is out of bounds",
+                ref_chars.len(),
+                alt_chars.len()
+            )));
         }
 
+        // The prefix/tail-trim bookkeeping above brackets a window that's
+        // guaranteed to contain the real difference, but not guaranteed to
+        // be the *minimal* one: a multi-residue window can still share an
+        // inner prefix or suffix once properly aligned (the premature-stop
+        // "undo trimming" step in particular re-widens the window without
+        // re-checking for this). Run a real alignment over just that window
+        // and shrink it to what actually differs, so a two-codon delins that
+        // only changes one residue is reported as a single substitution
+        // instead of a blanket delins.
+        let (start_idx, ref_end, alt_end) =
+            Self::tighten_diff_window(&ref_chars, &alt_chars, start_idx, ref_end, alt_end);
+
         let del_seq: String = ref_chars[start_idx..ref_end]
             .iter()
             .map(|c| aa1_to_aa3(*c))
@@ -246,38 +383,54 @@ impl<'a> AltSeqToHgvsp<'a> {
                 if prev_seq == ins_seq {
                     let start_pos_0 = ProteinPos((start_idx - aa_ins_len) as i32);
                     let end_pos_0 = ProteinPos((start_idx - 1) as i32);
-                    let aa_start = aa1_to_aa3(ref_chars[start_pos_0.0 as usize]).to_string();
-                    let aa_end = aa1_to_aa3(ref_chars[end_pos_0.0 as usize]).to_string();
-
-                    return Ok(PVariant {
-                        ac: self.alt_data.protein_accession.clone(),
-                        gene: None,
-                        posedit: PosEdit {
-                            pos: Some(AaInterval {
-                                start: AAPosition {
-                                    base: start_pos_0.to_hgvs(),
-                                    aa: aa_start,
-                                    uncertain: false,
-                                },
-                                end: if aa_ins_len > 1 {
-                                    Some(AAPosition {
-                                        base: end_pos_0.to_hgvs(),
-                                        aa: aa_end,
+                    let aa_start = aa1_to_aa3(
+                        ref_chars
+                            .get(start_pos_0.0 as usize)
+                            .cloned()
+                            .ok_or_else(|| {
+                                HgvsError::ValidationError(
+                                    "duplication start position out of range".into(),
+                                )
+                            })?,
+                    )
+                    .to_string();
+                    let aa_end = aa1_to_aa3(ref_chars.get(end_pos_0.0 as usize).cloned().ok_or_else(
+                        || HgvsError::ValidationError("duplication end position out of range".into()),
+                    )?)
+                    .to_string();
+
+                    return Ok((
+                        PVariant {
+                            ac: self.alt_data.protein_accession.clone(),
+                            gene: None,
+                            posedit: PosEdit {
+                                pos: Some(AaInterval {
+                                    start: AAPosition {
+                                        base: start_pos_0.to_hgvs(),
+                                        aa: aa_start,
                                         uncertain: false,
-                                    })
-                                } else {
-                                    None
+                                    },
+                                    end: if aa_ins_len > 1 {
+                                        Some(AAPosition {
+                                            base: end_pos_0.to_hgvs(),
+                                            aa: aa_end,
+                                            uncertain: false,
+                                        })
+                                    } else {
+                                        None
+                                    },
+                                    uncertain: false,
+                                }),
+                                edit: AaEdit::Dup {
+                                    ref_: Some(ins_seq),
+                                    uncertain: false,
                                 },
                                 uncertain: false,
-                            }),
-                            edit: AaEdit::Dup {
-                                ref_: Some(ins_seq),
-                                uncertain: false,
+                                predicted: false,
                             },
-                            uncertain: false,
-                            predicted: false,
                         },
-                    });
+                        ProteinConsequence::Duplication,
+                    ));
                 }
             }
         }
@@ -290,50 +443,55 @@ impl<'a> AltSeqToHgvsp<'a> {
                 ref_chars
                     .get(start_pos_0.0 as usize)
                     .cloned()
-                    .unwrap_or('*'),
+                    .unwrap_or('X'),
             )
             .to_string();
             let aa_end =
-                aa1_to_aa3(ref_chars.get(end_pos_0.0 as usize).cloned().unwrap_or('*')).to_string();
-            return Ok(PVariant {
-                ac: self.alt_data.protein_accession.clone(),
-                gene: None,
-                posedit: PosEdit {
-                    pos: Some(AaInterval {
-                        start: AAPosition {
-                            base: start_pos_0.to_hgvs(),
-                            aa: aa_start,
-                            uncertain: false,
-                        },
-                        end: Some(AAPosition {
-                            base: end_pos_0.to_hgvs(),
-                            aa: aa_end,
+                aa1_to_aa3(ref_chars.get(end_pos_0.0 as usize).cloned().unwrap_or('X')).to_string();
+            return Ok((
+                PVariant {
+                    ac: self.alt_data.protein_accession.clone(),
+                    gene: None,
+                    posedit: PosEdit {
+                        pos: Some(AaInterval {
+                            start: AAPosition {
+                                base: start_pos_0.to_hgvs(),
+                                aa: aa_start,
+                                uncertain: false,
+                            },
+                            end: Some(AAPosition {
+                                base: end_pos_0.to_hgvs(),
+                                aa: aa_end,
+                                uncertain: false,
+                            }),
                             uncertain: false,
                         }),
+                        edit: AaEdit::Ins {
+                            alt: ins_seq,
+                            uncertain: false,
+                        },
                         uncertain: false,
-                    }),
-                    edit: AaEdit::Ins {
-                        alt: ins_seq,
-                        uncertain: false,
+                        predicted: false,
                     },
-                    uncertain: false,
-                    predicted: false,
                 },
-            });
+                ProteinConsequence::InframeInsertion,
+            ));
         }
 
         // Del / DelIns / Subst
         if ins_seq.len() == 3 && del_seq.len() == 3 {
-            return self.create_variant(
-                ProteinPos(start_idx as i32),
-                None,
-                Some(del_seq),
-                Some(ins_seq),
-                None,
-                None,
-                false,
-                false,
-            );
+            return self
+                .create_variant(
+                    ProteinPos(start_idx as i32),
+                    None,
+                    Some(del_seq),
+                    Some(ins_seq),
+                    None,
+                    None,
+                    false,
+                    false,
+                )
+                .map(|v| (v, ProteinConsequence::Missense));
         }
 
         let start_pos_0 = ProteinPos(start_idx as i32);
@@ -342,24 +500,29 @@ impl<'a> AltSeqToHgvsp<'a> {
         } else {
             None
         };
-        let aa_start = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('*')).to_string();
-        let aa_end = end_pos_0
-            .map(|e| aa1_to_aa3(ref_chars.get(e.0 as usize).cloned().unwrap_or('*')).to_string());
+        let aa_start = aa1_to_aa3(ref_chars.get(start_idx).cloned().unwrap_or('X')).to_string();
 
-        let edit = if ins_seq.is_empty() {
-            AaEdit::Del {
-                ref_: del_seq,
-                uncertain: false,
-            }
+        let (edit, consequence) = if ins_seq.is_empty() {
+            (
+                AaEdit::Del {
+                    ref_: del_seq,
+                    uncertain: false,
+                },
+                ProteinConsequence::InframeDeletion,
+            )
         } else {
-            AaEdit::DelIns {
-                ref_: del_seq,
-                alt: ins_seq,
-                uncertain: false,
-            }
+            (
+                AaEdit::DelIns {
+                    ref_: del_seq,
+                    alt: ins_seq,
+                    uncertain: false,
+                },
+                ProteinConsequence::InframeDelins,
+            )
         };
 
-        Ok(PVariant {
+        Ok((
+            PVariant {
             ac: self.alt_data.protein_accession.clone(),
             gene: None,
             posedit: PosEdit {
@@ -369,9 +532,9 @@ impl<'a> AltSeqToHgvsp<'a> {
                         aa: aa_start,
                         uncertain: false,
                     },
-                    end: end_pos_0.map(|e| e.to_hgvs()).map(|base| AAPosition {
-                        base,
-                        aa: aa_end.unwrap(),
+                    end: end_pos_0.map(|e| AAPosition {
+                        base: e.to_hgvs(),
+                        aa: aa1_to_aa3(ref_chars.get(e.0 as usize).cloned().unwrap_or('X')).to_string(),
                         uncertain: false,
                     }),
                     uncertain: false,
@@ -380,6 +543,92 @@ impl<'a> AltSeqToHgvsp<'a> {
                 uncertain: false,
                 predicted: false,
             },
+            },
+            consequence,
+        ))
+    }
+
+    /// Shrinks `[start_idx, ref_end)` / `[start_idx, alt_end)` to the
+    /// smallest window a [`wfa::align`] alignment says actually differs.
+    /// [`wfa::align`]'s leading and trailing matched runs can only ever
+    /// confirm residues the caller's window already agreed on, so this never
+    /// widens the window -- only tightens one that turned out to still share
+    /// an inner prefix or suffix once properly aligned.
+    fn tighten_diff_window(
+        ref_chars: &[char],
+        alt_chars: &[char],
+        start_idx: usize,
+        ref_end: usize,
+        alt_end: usize,
+    ) -> (usize, usize, usize) {
+        if start_idx >= ref_end || start_idx >= alt_end {
+            return (start_idx, ref_end, alt_end);
+        }
+        let ref_window = &ref_chars[start_idx..ref_end];
+        let alt_window = &alt_chars[start_idx..alt_end];
+        let flat = wfa::align(ref_window, alt_window, wfa::Penalties::default()).flatten();
+
+        let lead_matches = flat.iter().take_while(|op| **op == wfa::Op::Match).count();
+        let trail_matches = flat
+            .iter()
+            .rev()
+            .take_while(|op| **op == wfa::Op::Match)
+            .count()
+            .min(ref_window.len().saturating_sub(lead_matches))
+            .min(alt_window.len().saturating_sub(lead_matches));
+
+        (
+            start_idx + lead_matches,
+            ref_end - trail_matches,
+            alt_end - trail_matches,
+        )
+    }
+
+    /// Conservative `p.?` consequence for variants whose sequence effect on
+    /// the CDS can't be determined precisely (e.g. a symbolic/imprecise
+    /// structural allele with no spelled-out deleted/inserted bases).
+    fn build_unknown_variant(&self) -> Result<PVariant, HgvsError> {
+        Ok(PVariant {
+            ac: self.alt_data.protein_accession.clone(),
+            gene: None,
+            posedit: PosEdit {
+                pos: None,
+                edit: AaEdit::Special {
+                    value: "?".to_string(),
+                    uncertain: false,
+                },
+                uncertain: false,
+                predicted: false,
+            },
+        })
+    }
+
+    /// Builds the `p.Met1?` rendering HGVS prescribes when translation
+    /// initiation is abolished: the position is fixed (the initiator Met at
+    /// protein position 1), and the unpredictable-effect marker `?` stands
+    /// in for the edit, since no downstream diff is meaningful once the
+    /// start codon itself is gone.
+    fn build_init_loss_variant(&self) -> Result<PVariant, HgvsError> {
+        Ok(PVariant {
+            ac: self.alt_data.protein_accession.clone(),
+            gene: None,
+            posedit: PosEdit {
+                pos: Some(AaInterval {
+                    start: AAPosition {
+                        base: ProteinPos(0).to_hgvs(),
+                        aa: "Met".to_string(),
+                        uncertain: false,
+                    },
+                    end: None,
+                    uncertain: false,
+                }),
+                edit: AaEdit::Special {
+                    value: "?".to_string(),
+                    uncertain: false,
+                },
+                uncertain: false,
+                predicted: false,
+            },
         })
     }
 
@@ -432,7 +681,7 @@ impl<'a> AltSeqToHgvsp<'a> {
         }
 
         let aa_start =
-            aa1_to_aa3(ref_chars.get(start_0.0 as usize).cloned().unwrap_or('*')).to_string();
+            aa1_to_aa3(ref_chars.get(start_0.0 as usize).cloned().unwrap_or('X')).to_string();
 
         let edit = if is_silent {
             AaEdit::Identity { uncertain: false }
@@ -460,18 +709,15 @@ impl<'a> AltSeqToHgvsp<'a> {
             }
         };
 
-        let aa_end = end_0
-            .map(|e| aa1_to_aa3(ref_chars.get(e.0 as usize).cloned().unwrap_or('*')).to_string());
-
         let interval = AaInterval {
             start: AAPosition {
                 base: start_0.to_hgvs(),
                 aa: aa_start,
                 uncertain: false,
             },
-            end: end_0.map(|e| e.to_hgvs()).map(|base| AAPosition {
-                base,
-                aa: aa_end.clone().unwrap(),
+            end: end_0.map(|e| AAPosition {
+                base: e.to_hgvs(),
+                aa: aa1_to_aa3(ref_chars.get(e.0 as usize).cloned().unwrap_or('X')).to_string(),
                 uncertain: false,
             }),
             uncertain: false,
@@ -489,3 +735,227 @@ impl<'a> AltSeqToHgvsp<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{CVariant, NaEdit, PosEdit as CPosEdit, TranscriptPos};
+
+    fn alt_data(ref_aa_len_codons: i32, alt_aa: &str, is_frameshift: bool) -> AltTranscriptData {
+        AltTranscriptData {
+            transcript_sequence: String::new(),
+            aa_sequence: alt_aa.to_string(),
+            cds_start_index: TranscriptPos(0),
+            cds_end_index: TranscriptPos(ref_aa_len_codons * 3),
+            protein_accession: "NP_000001.1".to_string(),
+            is_frameshift,
+            variant_start_aa: None,
+            frameshift_start: None,
+            frameshift_term_offset: None,
+            extension_len: None,
+            is_substitution: false,
+            is_ambiguous: false,
+            splice_consequence: None,
+            c_variant: CVariant {
+                ac: "NM_000001.1".to_string(),
+                gene: None,
+                posedit: CPosEdit {
+                    pos: None,
+                    edit: NaEdit::None,
+                    uncertain: false,
+                    predicted: false,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn stop_loss_extension_resolves_exact_length_from_full_aa_sequence() {
+        // ref "MAAA*" ends at protein index 4; alt replaces that stop with
+        // Gln and keeps translating (from the already-translated 3'UTR)
+        // until the next in-frame stop at index 7, three positions past the
+        // original stop.
+        let data = alt_data(5, "MAAAQQQ*", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAA*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 15,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        let (variant, consequence) = hgvsp.build_hgvsp_with_consequence().unwrap();
+        assert_eq!(consequence, ProteinConsequence::StopLost);
+        match variant.posedit.edit {
+            AaEdit::Ext {
+                length, uncertain, ..
+            } => {
+                assert_eq!(length, Some("3".to_string()));
+                assert!(!uncertain);
+            }
+            other => panic!("expected Ext edit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stop_loss_extension_falls_back_to_unknown_length_without_a_downstream_stop() {
+        // No '*' anywhere past the lost stop -- translation ran off the end
+        // of the available sequence, so the length is genuinely unresolved.
+        let data = alt_data(5, "MAAAQQQ", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAA*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 15,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        let (variant, _) = hgvsp.build_hgvsp_with_consequence().unwrap();
+        match variant.posedit.edit {
+            AaEdit::Ext {
+                length, uncertain, ..
+            } => {
+                assert_eq!(length, Some("?".to_string()));
+                assert!(uncertain);
+            }
+            other => panic!("expected Ext edit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn frameshift_resolves_exact_length_past_the_original_stop() {
+        // The frameshift runs past where the original protein ended (index
+        // 4) before terminating, which only resolves because alt_data's
+        // aa_sequence already spans that far.
+        let data = alt_data(5, "MAACCCCQ*", true);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAA*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 15,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        let (variant, consequence) = hgvsp.build_hgvsp_with_consequence().unwrap();
+        assert_eq!(consequence, ProteinConsequence::Frameshift);
+        match variant.posedit.edit {
+            AaEdit::Fs {
+                length, uncertain, ..
+            } => {
+                assert_eq!(length, Some("6".to_string()));
+                assert!(!uncertain);
+            }
+            other => panic!("expected Fs edit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn empty_alt_sequence_does_not_panic() {
+        let data = alt_data(5, "", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAA*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 15,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        assert!(hgvsp.build_hgvsp_with_consequence().is_ok());
+    }
+
+    #[test]
+    fn alt_shorter_than_start_idx_reports_an_error_instead_of_panicking() {
+        let mut data = alt_data(11, "MA", false);
+        data.variant_start_aa = Some(ProteinPos(10));
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAAA".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 33,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        assert!(matches!(
+            hgvsp.build_hgvsp_with_consequence(),
+            Err(HgvsError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn reference_without_a_terminal_stop_does_not_panic() {
+        let data = alt_data(4, "MAAG", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MAAC".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 12,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        assert!(hgvsp.build_hgvsp_with_consequence().is_ok());
+    }
+
+    // Both of the following golden tests share one fixture: the reference
+    // peptide's last two residues (Pro, Lys) are replaced by a single
+    // residue (Xaa) before the stop is reached one codon early. Simplified
+    // mode trims the tail match down to just the "*" and then drops the
+    // ambiguous stop from the reported range entirely; clinvar-faithful
+    // mode keeps it, producing a delins whose ref/alt both end in "Ter".
+    #[test]
+    fn delins_ending_in_a_stop_drops_the_stop_in_simplified_mode() {
+        let data = alt_data(7, "MADNX*", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MADNPK*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 21,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::Simplified,
+        };
+        let (variant, consequence) = hgvsp.build_hgvsp_with_consequence().unwrap();
+        assert_eq!(consequence, ProteinConsequence::InframeDelins);
+        match variant.posedit.edit {
+            AaEdit::DelIns { ref_, alt, .. } => {
+                assert!(!ref_.contains("Ter"));
+                assert!(!alt.contains("Ter"));
+            }
+            other => panic!("expected DelIns edit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn delins_ending_in_a_stop_keeps_the_stop_in_clinvar_faithful_mode() {
+        let data = alt_data(7, "MADNX*", false);
+        let hgvsp = AltSeqToHgvsp {
+            ref_aa: "MADNPK*".to_string(),
+            ref_cds_start_idx: 0,
+            ref_cds_end_idx: 21,
+            alt_data: &data,
+            mode: ProteinNormalizationMode::ClinvarFaithful,
+        };
+        let (variant, consequence) = hgvsp.build_hgvsp_with_consequence().unwrap();
+        assert_eq!(consequence, ProteinConsequence::InframeDelins);
+        match variant.posedit.edit {
+            AaEdit::DelIns { ref_, alt, .. } => {
+                assert!(ref_.ends_with("Ter"));
+                assert!(alt.ends_with("Ter"));
+            }
+            other => panic!("expected DelIns edit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tighten_diff_window_shrinks_a_two_residue_window_to_its_real_substitution() {
+        // Ref/alt agree on the first and last residue of the bracketed
+        // window ("A" then "S") and only differ on the middle one -- a real
+        // alignment should find that, even though the caller handed in a
+        // wider window.
+        let ref_chars: Vec<char> = "AARS".chars().collect();
+        let alt_chars: Vec<char> = "AKRS".chars().collect();
+        let (start, ref_end, alt_end) =
+            AltSeqToHgvsp::tighten_diff_window(&ref_chars, &alt_chars, 0, 4, 4);
+        assert_eq!((start, ref_end, alt_end), (1, 2, 2));
+    }
+
+    #[test]
+    fn tighten_diff_window_leaves_an_already_minimal_window_alone() {
+        let ref_chars: Vec<char> = "AAA".chars().collect();
+        let alt_chars: Vec<char> = "AKA".chars().collect();
+        let (start, ref_end, alt_end) =
+            AltSeqToHgvsp::tighten_diff_window(&ref_chars, &alt_chars, 1, 2, 2);
+        assert_eq!((start, ref_end, alt_end), (1, 2, 2));
+    }
+}
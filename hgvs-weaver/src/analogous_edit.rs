@@ -1,7 +1,7 @@
 use crate::error::HgvsError;
 use crate::structs::{AaEdit, NaEdit};
 use crate::utils::{decompose_aa, normalize_aa};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ResidueToken {
@@ -113,22 +113,33 @@ pub struct ProjectedSequence(pub Vec<ResidueToken>);
 
 impl ProjectedSequence {
     pub fn trim_at_stop(self) -> Self {
-        let mut trimmed = Vec::new();
-        for token in self.0 {
-            if let Some(s) = token.normalized_symbol() {
-                if s.contains('*') {
-                    break;
-                }
-            }
-            trimmed.push(token.clone());
-        }
-        ProjectedSequence(trimmed)
+        StopTrimmer::default().fold_projected(self)
     }
 
     pub fn is_analogous_to(&self, other: &Self) -> bool {
         reconcile_projections(&self.0, &other.0)
     }
 
+    /// Like [`Self::is_analogous_to`], but returns the full [`ProjectionDiff`]
+    /// instead of a bare bool -- every `Unknown` position's inferred residue,
+    /// and every position at which the two projections disagree, not just
+    /// the first one.
+    pub fn diff_with(&self, other: &Self) -> ProjectionDiff {
+        diff_projections(&self.0, &other.0)
+    }
+
+    /// Alignment-based equivalence: true for two HGVS spellings of the same
+    /// underlying sequence even when they're anchored at different offsets
+    /// (a left-shifted vs. right-shifted `delins`, or a `dup` vs. the
+    /// equivalent `ins` of a repeat unit), which [`Self::is_analogous_to`]'s
+    /// position-by-position comparison can't see. Runs a global
+    /// Needleman-Wunsch alignment over the two token vectors and accepts it
+    /// only if every aligned pair matches except for a single contiguous
+    /// gap block -- see [`AlignmentEquivalence`].
+    pub fn is_equivalent_to(&self, other: &Self) -> AlignmentEquivalence {
+        align_equivalence(&self.0, &other.0)
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -138,6 +149,93 @@ impl ProjectedSequence {
     }
 }
 
+/// A fold-style pass over [`ResidueToken`]s, [`ProjectedSequence`]s, and
+/// [`SparseReference`]s, in the spirit of a generated AST visitor: a custom
+/// normalization -- collapsing runs of `Unknown` into a span, rewriting
+/// three-letter `Known` codes to one-letter, masking a region to `Any` --
+/// is written once against this trait and then applies uniformly to any
+/// projection, instead of being hand-rolled per call site the way
+/// `NaEdit::Inv` complementing and repeat expansion are; stop-trimming is
+/// written as such a pass ([`StopTrimmer`]). Every method has an identity
+/// default, so a pass only overrides
+/// the nodes it actually cares about.
+pub trait ResidueVisitor {
+    /// Observe a token without transforming it. Default: no-op.
+    fn visit_token(&mut self, token: &ResidueToken) {
+        let _ = token;
+    }
+
+    /// Transform a single token. Default: identity.
+    fn fold_token(&mut self, token: ResidueToken) -> ResidueToken {
+        token
+    }
+
+    /// Observe every token of a projected sequence, in order.
+    fn visit_projected(&mut self, projected: &ProjectedSequence) {
+        for token in &projected.0 {
+            self.visit_token(token);
+        }
+    }
+
+    /// Transform a projected sequence. Default: visit then fold each token
+    /// in order, keeping the sequence the same length.
+    fn fold_projected(&mut self, projected: ProjectedSequence) -> ProjectedSequence {
+        let tokens = projected
+            .0
+            .into_iter()
+            .map(|token| {
+                self.visit_token(&token);
+                self.fold_token(token)
+            })
+            .collect();
+        ProjectedSequence(tokens)
+    }
+
+    /// Transform a sparse reference. Default: fold each bound token,
+    /// keeping its position.
+    fn fold_sparse(&mut self, sref: SparseReference) -> SparseReference {
+        let mut data = BTreeMap::new();
+        for (pos, token) in sref.data {
+            self.visit_token(&token);
+            data.insert(pos, self.fold_token(token));
+        }
+        SparseReference { data }
+    }
+}
+
+/// Worked example: truncates a projected sequence at its first stop codon.
+/// Demonstrates a stateful pass -- `visit_token` records whether a stop has
+/// been seen, and `fold_projected` is overridden (rather than relying on
+/// the default per-token fold) so it can stop emitting tokens once the
+/// stop codon itself is reached, dropping the stop token from the output
+/// just as the original hand-written `trim_at_stop` did.
+#[derive(Default)]
+struct StopTrimmer {
+    seen_stop: bool,
+}
+
+impl ResidueVisitor for StopTrimmer {
+    fn fold_projected(&mut self, projected: ProjectedSequence) -> ProjectedSequence {
+        let mut trimmed = Vec::new();
+        for token in projected.0 {
+            self.visit_token(&token);
+            if self.seen_stop {
+                break;
+            }
+            trimmed.push(self.fold_token(token));
+        }
+        ProjectedSequence(trimmed)
+    }
+
+    fn visit_token(&mut self, token: &ResidueToken) {
+        if let Some(s) = token.normalized_symbol() {
+            if s.contains('*') {
+                self.seen_stop = true;
+            }
+        }
+    }
+}
+
 pub fn apply_aa_edit_to_sparse(
     edit: &AaEdit,
     start: i32,
@@ -316,13 +414,25 @@ pub fn apply_na_edit_to_sparse(
             }
         }
         NaEdit::Del { .. } => {}
-        NaEdit::Ins { alt, .. } => {
-            if let Some(alt) = alt {
+        NaEdit::Ins { alt, .. } => match alt {
+            // Digit-only `alt` states the insertion's length without its
+            // sequence; project it as that many unresolved positions rather
+            // than splicing the digit characters in as literal bases.
+            Some(alt) if alt.chars().all(|c| c.is_ascii_digit()) => {
+                if let Ok(len) = alt.parse::<usize>() {
+                    res.extend(std::iter::repeat(ResidueToken::Any).take(len));
+                }
+            }
+            Some(alt) => {
                 for c in alt.chars() {
                     res.push(ResidueToken::Known(c.to_string()));
                 }
             }
-        }
+            // Fully unresolved insertion (no length, no sequence): contribute
+            // a single wildcard rather than silently inserting nothing, so
+            // an equivalence comparison doesn't treat it as a no-op.
+            None => res.push(ResidueToken::Wildcard),
+        },
         NaEdit::Dup { .. } => {
             res.extend(sref.project_range(start, end).0);
             res.extend(sref.project_range(start, end).0);
@@ -422,132 +532,520 @@ pub fn project_na_variant(
     ProjectedSequence(res)
 }
 
-struct UnificationEnv {
-    aliases: HashMap<i32, ResidueToken>,
+/// The first disagreement a [`ProjectionUnifier`] ran into: either two
+/// concrete residues that don't match, or two `Unknown` positions whose
+/// inferred residues conflict. `position_a`/`position_b` are `None` when the
+/// conflicting token was a literal [`ResidueToken::Known`] rather than a
+/// position bound to one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnificationConflict {
+    pub position_a: Option<i32>,
+    pub position_b: Option<i32>,
+    pub residue_a: String,
+    pub residue_b: String,
+}
+
+/// Disjoint-set forest over [`ResidueToken::Unknown`] position ids, modeled
+/// on type-inference unification: each set of positions is either still free
+/// or bound to a concrete residue. `unify` links two tokens' sets (or binds a
+/// position to a literal residue), using union by rank with path compression,
+/// and records the first [`UnificationConflict`] it finds rather than just
+/// failing silently -- so callers can explain *why* two projections matched.
+pub struct ProjectionUnifier {
+    parent: HashMap<i32, i32>,
+    rank: HashMap<i32, u32>,
+    bound: HashMap<i32, String>,
+    conflict: Option<UnificationConflict>,
 }
 
-impl UnificationEnv {
-    fn new() -> Self {
+impl ProjectionUnifier {
+    pub fn new() -> Self {
         Self {
-            aliases: HashMap::new(),
+            parent: HashMap::new(),
+            rank: HashMap::new(),
+            bound: HashMap::new(),
+            conflict: None,
         }
     }
 
-    fn resolve(&self, t: &ResidueToken) -> ResidueToken {
-        match t {
-            ResidueToken::Known(_) | ResidueToken::Any | ResidueToken::Wildcard => t.clone(),
-            ResidueToken::Unknown(p) => {
-                let mut curr_p = *p;
-                let mut visited = HashSet::new();
-                visited.insert(curr_p);
-                while let Some(next) = self.aliases.get(&curr_p) {
-                    match next {
-                        ResidueToken::Known(_) | ResidueToken::Any | ResidueToken::Wildcard => {
-                            return next.clone()
-                        }
-                        ResidueToken::Unknown(next_p) => {
-                            if visited.contains(next_p) {
-                                break;
-                            }
-                            curr_p = *next_p;
-                            visited.insert(curr_p);
-                        }
-                    }
-                }
-                ResidueToken::Unknown(curr_p)
+    fn find(&mut self, id: i32) -> i32 {
+        let parent = *self.parent.entry(id).or_insert(id);
+        if parent == id {
+            id
+        } else {
+            let root = self.find(parent);
+            self.parent.insert(id, root);
+            root
+        }
+    }
+
+    fn residues_equal(a: &str, b: &str) -> bool {
+        match (decompose_aa(a), decompose_aa(b)) {
+            (Ok(r1), Ok(r2)) => r1 == r2,
+            _ => normalize_aa(a) == normalize_aa(b),
+        }
+    }
+
+    fn record_conflict(
+        &mut self,
+        position_a: Option<i32>,
+        position_b: Option<i32>,
+        residue_a: &str,
+        residue_b: &str,
+    ) {
+        if self.conflict.is_none() {
+            self.conflict = Some(UnificationConflict {
+                position_a,
+                position_b,
+                residue_a: residue_a.to_string(),
+                residue_b: residue_b.to_string(),
+            });
+        }
+    }
+
+    /// Binds (or checks consistency of) a single position against a literal
+    /// residue.
+    fn bind(&mut self, id: i32, residue: &str) -> bool {
+        let root = self.find(id);
+        match self.bound.get(&root).cloned() {
+            Some(existing) if Self::residues_equal(&existing, residue) => true,
+            Some(existing) => {
+                self.record_conflict(Some(root), None, &existing, residue);
+                false
+            }
+            None => {
+                self.bound.insert(root, residue.to_string());
+                true
             }
         }
     }
 
-    fn unify(&mut self, t1: &ResidueToken, t2: &ResidueToken) -> bool {
-        let r1 = self.resolve(t1);
-        let r2 = self.resolve(t2);
+    /// Unions two positions' sets by rank, propagating whichever side
+    /// already carries a bound residue onto the merged root; if both sides
+    /// are bound to conflicting residues, records the conflict and fails.
+    fn link(&mut self, a: i32, b: i32) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        // Occurs-style guard: `a` and `b` already share a root, so linking
+        // them again would point a representative at itself. Returning
+        // early here is what keeps the `parent` map a forest rather than
+        // letting a redundant unify introduce a cycle.
+        if ra == rb {
+            return true;
+        }
+        let rank_a = *self.rank.get(&ra).unwrap_or(&0);
+        let rank_b = *self.rank.get(&rb).unwrap_or(&0);
+        let (winner, loser) = if rank_a >= rank_b { (ra, rb) } else { (rb, ra) };
+        self.parent.insert(loser, winner);
+        if rank_a == rank_b {
+            self.rank.insert(winner, rank_a + 1);
+        }
 
-        match (r1, r2) {
+        match (self.bound.get(&winner).cloned(), self.bound.remove(&loser)) {
+            (Some(bw), Some(bl)) if !Self::residues_equal(&bw, &bl) => {
+                self.record_conflict(Some(ra), Some(rb), &bw, &bl);
+                return false;
+            }
+            (None, Some(bl)) => {
+                self.bound.insert(winner, bl);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Unifies two aligned residue tokens. `Any`/`Wildcard` are trivially
+    /// consistent with anything; two concrete residues must already agree;
+    /// anything involving an `Unknown` position links or binds it in the
+    /// disjoint-set forest. Returns `false` on the first disagreement, and
+    /// records it -- see [`ProjectionUnifier::conflict`].
+    pub fn unify(&mut self, t1: &ResidueToken, t2: &ResidueToken) -> bool {
+        match (t1, t2) {
             (ResidueToken::Any, _) | (_, ResidueToken::Any) => true,
             (ResidueToken::Wildcard, _) | (_, ResidueToken::Wildcard) => true,
             (ResidueToken::Known(k1), ResidueToken::Known(k2)) => {
-                let d1 = decompose_aa(&k1);
-                let d2 = decompose_aa(&k2);
-                if let (Ok(r1), Ok(r2)) = (d1, d2) {
-                    r1 == r2
+                if Self::residues_equal(k1, k2) {
+                    true
                 } else {
-                    normalize_aa(&k1) == normalize_aa(&k2)
+                    self.record_conflict(None, None, k1, k2);
+                    false
                 }
             }
-            (ResidueToken::Unknown(p1), ResidueToken::Known(k2)) => {
-                self.aliases.insert(p1, ResidueToken::Known(k2));
-                true
+            (ResidueToken::Unknown(p), ResidueToken::Known(k))
+            | (ResidueToken::Known(k), ResidueToken::Unknown(p)) => self.bind(*p, k),
+            (ResidueToken::Unknown(p1), ResidueToken::Unknown(p2)) => self.link(*p1, *p2),
+        }
+    }
+
+    /// Resolves a token to its most concrete known form: an `Unknown`
+    /// position bound (directly or transitively) to a residue is replaced by
+    /// that residue.
+    pub fn resolve(&mut self, t: &ResidueToken) -> ResidueToken {
+        match t {
+            ResidueToken::Known(_) | ResidueToken::Any | ResidueToken::Wildcard => t.clone(),
+            ResidueToken::Unknown(p) => {
+                let root = self.find(*p);
+                match self.bound.get(&root) {
+                    Some(residue) => ResidueToken::Known(residue.clone()),
+                    None => ResidueToken::Unknown(root),
+                }
             }
-            (ResidueToken::Known(k1), ResidueToken::Unknown(p2)) => {
-                self.aliases.insert(p2, ResidueToken::Known(k1));
-                true
+        }
+    }
+
+    /// The first conflict encountered, if any.
+    pub fn conflict(&self) -> Option<&UnificationConflict> {
+        self.conflict.as_ref()
+    }
+
+    /// The `Unknown(id) -> residue` assignments inferred so far, for every
+    /// position whose set ended up bound to a concrete residue.
+    pub fn substitution(&mut self) -> HashMap<i32, String> {
+        let ids: Vec<i32> = self.parent.keys().copied().collect();
+        let mut out = HashMap::new();
+        for id in ids {
+            let root = self.find(id);
+            if let Some(residue) = self.bound.get(&root) {
+                out.insert(id, residue.clone());
             }
-            (ResidueToken::Unknown(p1), ResidueToken::Unknown(p2)) => {
-                if p1 != p2 {
-                    self.aliases.insert(p1, ResidueToken::Unknown(p2));
+        }
+        out
+    }
+
+    /// Snapshots the disjoint-set state (but not `conflict`, which always
+    /// keeps the first failure ever witnessed) so a speculative `unify`
+    /// made while exploring one Wildcard alignment can be undone with
+    /// [`Self::restore`] if that alignment turns out not to pan out.
+    fn snapshot(&self) -> (HashMap<i32, i32>, HashMap<i32, u32>, HashMap<i32, String>) {
+        (self.parent.clone(), self.rank.clone(), self.bound.clone())
+    }
+
+    fn restore(&mut self, snapshot: (HashMap<i32, i32>, HashMap<i32, u32>, HashMap<i32, String>)) {
+        self.parent = snapshot.0;
+        self.rank = snapshot.1;
+        self.bound = snapshot.2;
+    }
+}
+
+/// Backtracking alignment of two token slices starting at `(i, j)`, giving
+/// `ResidueToken::Wildcard` real `.*` semantics (consumes zero or more
+/// tokens of the *other* slice) instead of the old "bail out true the
+/// instant one is seen" shortcut. Non-wildcard positions unify as a normal
+/// pairwise match; a unify made while exploring one candidate split is
+/// rolled back via `unifier.restore` if that split doesn't lead to an
+/// overall match, so a rejected branch never leaks bindings into a
+/// sibling one. `failed` memoizes `(i, j)` pairs already known to have no
+/// viable continuation, keeping the search polynomial instead of
+/// exponential in the presence of multiple Wildcards.
+fn match_at(
+    v1: &[ResidueToken],
+    v2: &[ResidueToken],
+    i: usize,
+    j: usize,
+    unifier: &mut ProjectionUnifier,
+    failed: &mut std::collections::HashSet<(usize, usize)>,
+) -> bool {
+    if failed.contains(&(i, j)) {
+        return false;
+    }
+
+    let ok = match (v1.get(i), v2.get(j)) {
+        (None, None) => true,
+        (Some(ResidueToken::Wildcard), _) => {
+            let mut k = j;
+            loop {
+                let snap = unifier.snapshot();
+                if match_at(v1, v2, i + 1, k, unifier, failed) {
+                    break true;
+                }
+                unifier.restore(snap);
+                if k >= v2.len() {
+                    break false;
                 }
+                k += 1;
+            }
+        }
+        (_, Some(ResidueToken::Wildcard)) => {
+            let mut k = i;
+            loop {
+                let snap = unifier.snapshot();
+                if match_at(v1, v2, k, j + 1, unifier, failed) {
+                    break true;
+                }
+                unifier.restore(snap);
+                if k >= v1.len() {
+                    break false;
+                }
+                k += 1;
+            }
+        }
+        (None, Some(_)) | (Some(_), None) => false,
+        (Some(t1), Some(t2)) => {
+            let snap = unifier.snapshot();
+            if unifier.unify(t1, t2) && match_at(v1, v2, i + 1, j + 1, unifier, failed) {
                 true
+            } else {
+                unifier.restore(snap);
+                false
             }
         }
+    };
+
+    if !ok {
+        failed.insert((i, j));
     }
+    ok
 }
 
 pub fn reconcile_projections(v1: &[ResidueToken], v2: &[ResidueToken]) -> bool {
-    let mut env = UnificationEnv::new();
+    unify_projections(v1, v2).is_ok()
+}
 
-    let mut i = 0;
-    while i < v1.len() && i < v2.len() {
-        let t1 = &v1[i];
-        let t2 = &v2[i];
+/// A machine-readable comparison of two equal-length projections: the
+/// substitution inferred for every `Unknown` reference position (so a
+/// caller can materialize a concrete reconciled sequence), plus every
+/// index at which the two disagree -- e.g. "position 203 is Arg in one
+/// spelling and Gln in the other" -- rather than just a bool. `conflicts`
+/// is empty iff the two projections agree position by position; a
+/// `Wildcard` here stands for exactly one token, so two projections that
+/// [`ProjectedSequence::is_analogous_to`] accepts by letting a `Wildcard`
+/// span several tokens still get a length conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectionDiff {
+    pub substitution: HashMap<i32, ResidueToken>,
+    pub conflicts: Vec<(usize, ResidueToken, ResidueToken)>,
+}
 
-        if matches!(t1, ResidueToken::Wildcard) || matches!(t2, ResidueToken::Wildcard) {
-            return true; // Matches the rest
-        }
+/// Computes a [`ProjectionDiff`] between two token slices. Unlike
+/// [`unify_projections`], which stops at the first disagreement,
+/// every aligned pair is unified and every conflict is appended to
+/// `conflicts` rather than short-circuiting, so the returned diff is
+/// complete. A length mismatch is itself reported as one trailing
+/// conflict at the shorter sequence's length.
+pub fn diff_projections(v1: &[ResidueToken], v2: &[ResidueToken]) -> ProjectionDiff {
+    let mut unifier = ProjectionUnifier::new();
+    let mut conflicts = Vec::new();
 
-        if !env.unify(t1, t2) {
-            return false;
+    for (idx, (t1, t2)) in v1.iter().zip(v2.iter()).enumerate() {
+        if !unifier.unify(t1, t2) {
+            conflicts.push((idx, t1.clone(), t2.clone()));
         }
-        i += 1;
     }
+    if v1.len() != v2.len() {
+        conflicts.push((
+            v1.len().min(v2.len()),
+            ResidueToken::Known(format!("<{} tokens>", v1.len())),
+            ResidueToken::Known(format!("<{} tokens>", v2.len())),
+        ));
+    }
+
+    let substitution = unifier
+        .substitution()
+        .into_iter()
+        .map(|(pos, residue)| (pos, ResidueToken::Known(residue)))
+        .collect();
 
-    // If one is longer, it must be because the other ended with a Wildcard
-    // or they are same length.
-    if i < v1.len() || i < v2.len() {
-        // Zip only goes to the shortest. We check if the last processed was Wildcard.
-        // Actually the loop handles it. If we reached here without a Wildcard,
-        // then they must have same length.
-        return v1.len() == v2.len();
+    ProjectionDiff {
+        substitution,
+        conflicts,
     }
+}
 
-    // Second pass: verify consistency for the common part
-    for j in 0..i {
-        let r1 = env.resolve(&v1[j]);
-        let r2 = env.resolve(&v2[j]);
+/// A single step of a [`needleman_wunsch`] traceback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlignOp {
+    Match,
+    Mismatch,
+    /// `v2` has a token here that `v1` doesn't (a gap in `v1`'s alignment).
+    GapInV1,
+    /// `v1` has a token here that `v2` doesn't (a gap in `v2`'s alignment).
+    GapInV2,
+}
 
-        match (r1, r2) {
-            (ResidueToken::Any, _) | (_, ResidueToken::Any) => {}
-            (ResidueToken::Wildcard, _) | (_, ResidueToken::Wildcard) => break,
-            (ResidueToken::Known(k1), ResidueToken::Known(k2)) => {
-                let d1 = decompose_aa(&k1);
-                let d2 = decompose_aa(&k2);
-                if let (Ok(r1), Ok(r2)) = (d1, d2) {
-                    if r1 != r2 {
-                        return false;
-                    }
+const ALIGN_GAP_PENALTY: u32 = 1;
+const ALIGN_MISMATCH_PENALTY: u32 = 2;
+
+/// Whether two tokens are compatible for alignment-scoring purposes:
+/// `Any`/`Wildcard`/`Unknown` match anything, two `Known`s must agree.
+/// Unlike [`ProjectionUnifier::unify`] this is a pure predicate -- it
+/// doesn't bind `Unknown` positions, since the DP explores many candidate
+/// alignments and only one is ever kept.
+fn tokens_compatible(t1: &ResidueToken, t2: &ResidueToken) -> bool {
+    match (t1, t2) {
+        (ResidueToken::Any, _) | (_, ResidueToken::Any) => true,
+        (ResidueToken::Wildcard, _) | (_, ResidueToken::Wildcard) => true,
+        (ResidueToken::Unknown(_), _) | (_, ResidueToken::Unknown(_)) => true,
+        (ResidueToken::Known(k1), ResidueToken::Known(k2)) => {
+            ProjectionUnifier::residues_equal(k1, k2)
+        }
+    }
+}
+
+/// Global (Needleman-Wunsch) alignment of two token slices: an
+/// `(m+1)x(n+1)` DP table where the substitution cell costs
+/// `ALIGN_MISMATCH_PENALTY` unless the tokens are [`tokens_compatible`],
+/// and either gap move costs `ALIGN_GAP_PENALTY`, biasing the optimum
+/// toward a single consolidated gap rather than scattering mismatches
+/// across the alignment. Returns the traceback as a sequence of ops from
+/// the start of both slices to their ends.
+fn needleman_wunsch(v1: &[ResidueToken], v2: &[ResidueToken]) -> Vec<AlignOp> {
+    let m = v1.len();
+    let n = v2.len();
+    let mut dp = vec![vec![0u32; n + 1]; m + 1];
+    for (i, row) in dp.iter_mut().enumerate().take(m + 1).skip(1) {
+        row[0] = i as u32 * ALIGN_GAP_PENALTY;
+    }
+    for j in 1..=n {
+        dp[0][j] = j as u32 * ALIGN_GAP_PENALTY;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let sub_cost = if tokens_compatible(&v1[i - 1], &v2[j - 1]) {
+                0
+            } else {
+                ALIGN_MISMATCH_PENALTY
+            };
+            let diag = dp[i - 1][j - 1] + sub_cost;
+            let up = dp[i - 1][j] + ALIGN_GAP_PENALTY;
+            let left = dp[i][j - 1] + ALIGN_GAP_PENALTY;
+            dp[i][j] = diag.min(up).min(left);
+        }
+    }
+
+    let mut ops = Vec::with_capacity(m + n);
+    let (mut i, mut j) = (m, n);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let sub_cost = if tokens_compatible(&v1[i - 1], &v2[j - 1]) {
+                0
+            } else {
+                ALIGN_MISMATCH_PENALTY
+            };
+            if dp[i][j] == dp[i - 1][j - 1] + sub_cost {
+                ops.push(if sub_cost == 0 {
+                    AlignOp::Match
                 } else {
-                    if normalize_aa(&k1) != normalize_aa(&k2) {
-                        return false;
-                    }
+                    AlignOp::Mismatch
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && dp[i][j] == dp[i - 1][j] + ALIGN_GAP_PENALTY {
+            ops.push(AlignOp::GapInV2);
+            i -= 1;
+            continue;
+        }
+        ops.push(AlignOp::GapInV1);
+        j -= 1;
+    }
+    ops.reverse();
+    ops
+}
+
+/// Result of [`ProjectedSequence::is_equivalent_to`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignmentEquivalence {
+    pub equivalent: bool,
+    /// The gap's half-open token range in whichever sequence has the extra
+    /// tokens, `None` if the two sequences aligned with no gap at all.
+    pub gap: Option<(usize, usize)>,
+    /// True if at least one matched pair in the alignment had a concrete
+    /// `Known` residue on *both* sides that actually agreed, rather than
+    /// one side (or both) being `Unknown`/`Any`/`Wildcard` padding. An
+    /// alignment can be `equivalent` with `confirmed: false` when the two
+    /// projections simply never name the same position -- compatible, but
+    /// never actually cross-checked against each other.
+    pub confirmed: bool,
+}
+
+fn align_equivalence(v1: &[ResidueToken], v2: &[ResidueToken]) -> AlignmentEquivalence {
+    let ops = needleman_wunsch(v1, v2);
+    if ops.iter().any(|op| matches!(op, AlignOp::Mismatch)) {
+        return AlignmentEquivalence {
+            equivalent: false,
+            gap: None,
+            confirmed: false,
+        };
+    }
+
+    let mut self_idx = 0usize;
+    let mut other_idx = 0usize;
+    let mut gap_block: Option<(usize, usize)> = None;
+    let mut blocks = 0usize;
+    let mut prev_dir: Option<AlignOp> = None;
+    let mut confirmed = false;
+
+    for op in &ops {
+        match op {
+            AlignOp::Match => {
+                if matches!(
+                    (&v1[self_idx], &v2[other_idx]),
+                    (ResidueToken::Known(_), ResidueToken::Known(_))
+                ) {
+                    confirmed = true;
                 }
+                prev_dir = None;
+                self_idx += 1;
+                other_idx += 1;
             }
-            (ResidueToken::Unknown(_), ResidueToken::Known(_))
-            | (ResidueToken::Known(_), ResidueToken::Unknown(_)) => {
-                return false;
+            AlignOp::GapInV2 => {
+                if prev_dir != Some(AlignOp::GapInV2) {
+                    blocks += 1;
+                    gap_block = Some((self_idx, self_idx));
+                }
+                self_idx += 1;
+                gap_block = gap_block.map(|(s, _)| (s, self_idx));
+                prev_dir = Some(AlignOp::GapInV2);
             }
-            (ResidueToken::Unknown(_), ResidueToken::Unknown(_)) => {}
+            AlignOp::GapInV1 => {
+                if prev_dir != Some(AlignOp::GapInV1) {
+                    blocks += 1;
+                    gap_block = Some((other_idx, other_idx));
+                }
+                other_idx += 1;
+                gap_block = gap_block.map(|(s, _)| (s, other_idx));
+                prev_dir = Some(AlignOp::GapInV1);
+            }
+            AlignOp::Mismatch => unreachable!("checked above"),
         }
     }
 
-    true
+    if blocks > 1 {
+        return AlignmentEquivalence {
+            equivalent: false,
+            gap: None,
+            confirmed: false,
+        };
+    }
+
+    AlignmentEquivalence {
+        equivalent: true,
+        gap: gap_block,
+        confirmed,
+    }
+}
+
+/// Detailed counterpart to [`reconcile_projections`]: unifies two aligned
+/// projections position-by-position and returns the inferred
+/// `Unknown(id) -> residue` substitution on success, or the first
+/// [`UnificationConflict`] witnessed on failure, so callers (e.g. equivalence
+/// reporting) can explain why two projections matched or didn't.
+pub fn unify_projections(
+    v1: &[ResidueToken],
+    v2: &[ResidueToken],
+) -> Result<HashMap<i32, String>, UnificationConflict> {
+    let mut unifier = ProjectionUnifier::new();
+    let mut failed = std::collections::HashSet::new();
+
+    if match_at(v1, v2, 0, 0, &mut unifier, &mut failed) {
+        Ok(unifier.substitution())
+    } else {
+        Err(unifier.conflict().cloned().unwrap_or(UnificationConflict {
+            position_a: None,
+            position_b: None,
+            residue_a: format!("{} positions", v1.len()),
+            residue_b: format!("{} positions", v2.len()),
+        }))
+    }
 }
@@ -0,0 +1,574 @@
+//! Batch annotation of VCF/BCF records against one or more transcripts.
+//!
+//! This is the entry point for the "annotate a whole VCF" workflow: given a
+//! reference accession and the usual `CHROM POS REF ALT` tuple from a VCF
+//! record, build a `g.` `SequenceVariant`, project it onto every transcript
+//! overlapping the locus via [`VariantMapper::g_to_c_all`], and further
+//! project each resulting `c.` variant to its protein consequence.
+//!
+//! Record parsing itself is left to `noodles_vcf`; this module only deals
+//! with the already-split `(reference_accession, pos, ref, alt)` fields so
+//! it has no hard dependency on a particular VCF reader.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use serde::Serialize;
+
+use crate::data::{DataProvider, TranscriptSearch};
+use crate::error::HgvsError;
+use crate::mapper::VariantMapper;
+use crate::structs::{
+    GVariant, HgvsGenomicPos, NaEdit, PosEdit, SequenceVariant, SimpleInterval, SimplePosition,
+    Variant,
+};
+
+/// The `c.`/`p.` consequence of a single ALT allele on a single transcript.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnnotationRecord {
+    /// The genomic accession the call was made against.
+    pub reference_ac: String,
+    /// 1-based VCF position of the record.
+    pub pos: i32,
+    pub reference_bases: String,
+    pub alt_bases: String,
+    /// Transcript accession this annotation projects onto.
+    pub transcript_ac: String,
+    /// Gene symbol for `transcript_ac`, from `Transcript::gene`.
+    pub gene: Option<String>,
+    /// Strand of `transcript_ac` relative to `reference_ac` (1 or -1), from
+    /// `Transcript::strand`.
+    pub strand: Option<i32>,
+    /// HGVS `c.` string, if the transcript mapping succeeded.
+    pub hgvs_c: Option<String>,
+    /// HGVS `p.` string, if protein projection succeeded.
+    pub hgvs_p: Option<String>,
+    /// Coarse consequence tag derived from `hgvs_p` (see [`classify_consequence`]).
+    pub consequence: String,
+    /// Set when mapping to this transcript failed; `hgvs_c`/`hgvs_p` are `None` in that case.
+    pub error: Option<String>,
+}
+
+/// Coarse consequence classification derived from the projected `p.` string.
+///
+/// This is a cheap heuristic for a TSV column, not a full SO-term
+/// classifier: it only distinguishes the handful of outcomes `hgvs_p`'s text
+/// already encodes (`?`/`fs`/`Ter`/`=`). A dedicated molecular-consequence
+/// classifier is tracked as follow-up work.
+pub fn classify_consequence(hgvs_p: Option<&str>, error: Option<&str>) -> String {
+    if error.is_some() {
+        return "unknown".to_string();
+    }
+    match hgvs_p {
+        None => "non_coding_transcript".to_string(),
+        Some(p) if p.contains('?') => "unknown".to_string(),
+        Some(p) if p.contains('=') => "synonymous".to_string(),
+        Some(p) if p.contains("fs") => "frameshift".to_string(),
+        Some(p) if p.contains("Ter") => "nonsense".to_string(),
+        Some(_) => "missense".to_string(),
+    }
+}
+
+/// Returns `true` for ALT alleles that cannot be turned into a `SequenceVariant`:
+/// no-calls (`.`), spanning deletions (`*`), and symbolic/breakend alleles (`<DEL>`, `]chr1:123]`, ...).
+pub fn is_skippable_alt(alt: &str) -> bool {
+    alt == "." || alt == "*" || alt.starts_with('<') || alt.contains('[') || alt.contains(']')
+}
+
+/// Splits a (possibly multi-allelic) VCF `ALT` field into its component alleles,
+/// dropping any that [`is_skippable_alt`] flags.
+pub fn split_alts(alt_field: &str) -> Vec<&str> {
+    alt_field
+        .split(',')
+        .filter(|a| !is_skippable_alt(a))
+        .collect()
+}
+
+/// Builds a minimal `g.` substitution/indel variant from raw VCF fields.
+///
+/// This intentionally mirrors VCF's left-anchored REF/ALT convention rather
+/// than normalizing; callers that need canonical HGVS should run the result
+/// through [`VariantMapper::normalize_variant`].
+pub fn vcf_to_genomic_variant(
+    reference_ac: &str,
+    pos: i32,
+    reference_bases: &str,
+    alt_bases: &str,
+) -> Result<GVariant, HgvsError> {
+    if reference_bases.is_empty() || alt_bases.is_empty() {
+        return Err(HgvsError::ValidationError(
+            "VCF REF/ALT must be non-empty".into(),
+        ));
+    }
+    let end = pos + reference_bases.len() as i32 - 1;
+    let posedit = PosEdit {
+        pos: Some(SimpleInterval {
+            start: SimplePosition {
+                base: HgvsGenomicPos(pos),
+                end: None,
+                uncertain: false,
+            },
+            end: if end != pos {
+                Some(SimplePosition {
+                    base: HgvsGenomicPos(end),
+                    end: None,
+                    uncertain: false,
+                })
+            } else {
+                None
+            },
+            uncertain: false,
+        }),
+        edit: NaEdit::RefAlt {
+            ref_: Some(reference_bases.to_string()),
+            alt: Some(alt_bases.to_string()),
+        },
+        uncertain: false,
+        predicted: false,
+    };
+    Ok(GVariant {
+        ac: reference_ac.to_string(),
+        gene: None,
+        posedit,
+    })
+}
+
+/// A symbolic structural-variant class, as carried by a VCF `<DEL>`/`<DUP>`/`<INS>` ALT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolicSvKind {
+    Del,
+    Dup,
+    Ins,
+}
+
+/// Classifies a symbolic VCF ALT token (`<DEL>`, `<DUP>`, `<INS>`, or a
+/// sub-type like `<DEL:ME>`) into a [`SymbolicSvKind`], or `None` for
+/// breakends and other symbolic alleles this crate doesn't represent.
+pub fn symbolic_sv_kind(alt: &str) -> Option<SymbolicSvKind> {
+    let inner = alt.strip_prefix('<')?.strip_suffix('>')?;
+    let tag = inner.split(':').next().unwrap_or(inner);
+    match tag {
+        "DEL" => Some(SymbolicSvKind::Del),
+        "DUP" => Some(SymbolicSvKind::Dup),
+        "INS" => Some(SymbolicSvKind::Ins),
+        _ => None,
+    }
+}
+
+/// Builds an imprecise `g.` variant for a symbolic structural ALT (`<DEL>`,
+/// `<DUP>`, `<INS>`) where no explicit ref/alt sequence is available.
+///
+/// `end` is the VCF `INFO/END` field for `DEL`/`DUP`; it's ignored for `INS`,
+/// which is represented as a zero-length insertion point at `pos` with an
+/// unknown inserted sequence. Downstream, `VariantMapper::c_to_p` resolves
+/// that `INS` case to a conservative `p.?` rather than erroring; `DEL`/`DUP`
+/// are projected from their span like any other deletion or duplication.
+pub fn vcf_symbolic_to_genomic_variant(
+    reference_ac: &str,
+    pos: i32,
+    end: i32,
+    kind: SymbolicSvKind,
+) -> GVariant {
+    let start = SimplePosition {
+        base: HgvsGenomicPos(pos),
+        end: None,
+        uncertain: false,
+    };
+    let (interval_end, edit) = match kind {
+        SymbolicSvKind::Del => (
+            Some(SimplePosition {
+                base: HgvsGenomicPos(end),
+                end: None,
+                uncertain: false,
+            }),
+            NaEdit::Del {
+                ref_: None,
+                uncertain: true,
+            },
+        ),
+        SymbolicSvKind::Dup => (
+            Some(SimplePosition {
+                base: HgvsGenomicPos(end),
+                end: None,
+                uncertain: false,
+            }),
+            NaEdit::Dup {
+                ref_: None,
+                uncertain: true,
+            },
+        ),
+        SymbolicSvKind::Ins => (
+            None,
+            NaEdit::Ins {
+                alt: None,
+                uncertain: true,
+            },
+        ),
+    };
+
+    GVariant {
+        ac: reference_ac.to_string(),
+        gene: None,
+        posedit: PosEdit {
+            pos: Some(SimpleInterval {
+                start,
+                end: interval_end,
+                uncertain: false,
+            }),
+            edit,
+            uncertain: true,
+            predicted: false,
+        },
+    }
+}
+
+/// Annotates one VCF record against every transcript overlapping its locus.
+///
+/// Splits `alt_field` into individual ALT alleles, skips no-calls and
+/// symbolic alleles, and for each remaining allele runs `g_to_c_all` followed
+/// by `c_to_p`. A failure to map onto a particular transcript produces an
+/// [`AnnotationRecord`] with `error` set rather than aborting the whole batch.
+/// `gene`/`strand` are both looked up once per transcript via a single
+/// `get_transcript` call and are populated even when `c_to_p` itself fails,
+/// since they don't depend on the protein projection succeeding.
+pub fn annotate_record(
+    hdp: &dyn DataProvider,
+    searcher: &dyn TranscriptSearch,
+    reference_ac: &str,
+    pos: i32,
+    reference_bases: &str,
+    alt_field: &str,
+) -> Result<Vec<AnnotationRecord>, HgvsError> {
+    let mapper = VariantMapper::new(hdp);
+    let mut records = Vec::new();
+
+    for alt in split_alts(alt_field) {
+        let var_g = vcf_to_genomic_variant(reference_ac, pos, reference_bases, alt)?;
+        let c_variants = mapper.g_to_c_all(&var_g, searcher)?;
+
+        for var_c in c_variants {
+            let transcript_ac = var_c.ac().to_string();
+            let transcript = hdp.get_transcript(&transcript_ac, Some(reference_ac)).ok();
+            let gene = transcript.as_ref().map(|t| t.gene().to_string());
+            let strand = transcript.as_ref().map(|t| t.strand());
+            let hgvs_c = SequenceVariant::Coding(var_c.clone()).to_string();
+            match mapper.c_to_p(&var_c, None) {
+                Ok(var_p) => {
+                    let hgvs_p = SequenceVariant::Protein(var_p).to_string();
+                    records.push(AnnotationRecord {
+                        reference_ac: reference_ac.to_string(),
+                        pos,
+                        reference_bases: reference_bases.to_string(),
+                        alt_bases: alt.to_string(),
+                        transcript_ac,
+                        gene,
+                        strand,
+                        consequence: classify_consequence(Some(&hgvs_p), None),
+                        hgvs_c: Some(hgvs_c),
+                        hgvs_p: Some(hgvs_p),
+                        error: None,
+                    })
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    records.push(AnnotationRecord {
+                        reference_ac: reference_ac.to_string(),
+                        pos,
+                        reference_bases: reference_bases.to_string(),
+                        alt_bases: alt.to_string(),
+                        transcript_ac,
+                        gene,
+                        strand,
+                        consequence: classify_consequence(None, Some(&error)),
+                        hgvs_c: Some(hgvs_c),
+                        hgvs_p: None,
+                        error: Some(error),
+                    })
+                }
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+/// A single input locus to annotate, mirroring a VCF record's already-split
+/// `CHROM POS REF ALT` fields. One `VcfRecord` can expand into several
+/// [`AnnotationRecord`]s: one per ALT allele times overlapping transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VcfRecord {
+    pub reference_ac: String,
+    pub pos: i32,
+    pub reference_bases: String,
+    pub alt_field: String,
+}
+
+/// Streams [`AnnotationRecord`]s from an iterator of [`VcfRecord`]s without
+/// materializing the whole batch.
+///
+/// Each `next()` call pulls at most one input record through
+/// [`annotate_record`], buffering only that record's resulting rows. Feed it
+/// a lazy `VcfRecord` iterator (e.g. over a `noodles_vcf` reader) and it, or
+/// [`crate::varfish::write_rows`] downstream of it, will never hold more
+/// than one locus's annotations in memory at a time.
+pub struct VariantAnnotator<'a, I> {
+    hdp: &'a dyn DataProvider,
+    searcher: &'a dyn TranscriptSearch,
+    records: I,
+    pending: std::vec::IntoIter<AnnotationRecord>,
+}
+
+impl<'a, I: Iterator<Item = VcfRecord>> VariantAnnotator<'a, I> {
+    pub fn new(hdp: &'a dyn DataProvider, searcher: &'a dyn TranscriptSearch, records: I) -> Self {
+        VariantAnnotator {
+            hdp,
+            searcher,
+            records,
+            pending: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = VcfRecord>> Iterator for VariantAnnotator<'a, I> {
+    type Item = Result<AnnotationRecord, HgvsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(rec) = self.pending.next() {
+                return Some(Ok(rec));
+            }
+            let vcf_record = self.records.next()?;
+            match annotate_record(
+                self.hdp,
+                self.searcher,
+                &vcf_record.reference_ac,
+                vcf_record.pos,
+                &vcf_record.reference_bases,
+                &vcf_record.alt_field,
+            ) {
+                Ok(records) => self.pending = records.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Reads `CHROM`/`POS`/`REF`/`ALT` out of a bgzipped VCF and annotates each
+/// record's alleles against every transcript `searcher` reports overlapping
+/// its locus, writing one JSON object per variant×transcript row to `sink`,
+/// newline-delimited ([`GenomicIntervalIndex`](crate::genomic_index::GenomicIntervalIndex)
+/// is the usual `searcher` for this, built once over a whole gene model).
+///
+/// bgzip is a valid (if non-indexed) concatenation of ordinary gzip blocks,
+/// so a sequential [`GzDecoder`] reads it end to end without needing a
+/// `.tbi`/`.csi` index. Random access is not supported, and INFO/FORMAT
+/// columns are not read; symbolic ALTs are skipped. [`crate::vcf`] reads the
+/// file through `noodles_vcf` instead, with the same site-only scope.
+///
+/// Returns the number of `AnnotationRecord` rows written. A record whose
+/// `POS`/`REF` fields fail to parse is skipped rather than aborting the rest
+/// of the file.
+pub fn annotate_bgzipped_vcf_to_jsonl<W: Write>(
+    vcf_path: impl AsRef<Path>,
+    hdp: &dyn DataProvider,
+    searcher: &dyn TranscriptSearch,
+    sink: &mut W,
+) -> Result<usize, HgvsError> {
+    let file = std::fs::File::open(vcf_path).map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+    let reader = std::io::BufReader::new(GzDecoder::new(file));
+
+    let records = reader.lines().filter_map(|line| {
+        let line = line.ok()?;
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let mut fields = line.split('\t');
+        let reference_ac = fields.next()?.to_string();
+        let pos: i32 = fields.next()?.parse().ok()?;
+        let _id = fields.next();
+        let reference_bases = fields.next()?.to_string();
+        let alt_field = fields.next()?.to_string();
+        Some(VcfRecord {
+            reference_ac,
+            pos,
+            reference_bases,
+            alt_field,
+        })
+    });
+
+    let mut count = 0;
+    for row in VariantAnnotator::new(hdp, searcher, records) {
+        let row = row?;
+        serde_json::to_writer(&mut *sink, &row)
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        sink.write_all(b"\n")
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::{GenomicPos, IntronicOffset, TranscriptPos};
+    use crate::data::{ExonData, IdentifierKind, IdentifierType, TranscriptData};
+
+    struct MockDataProvider {
+        with_protein_accession: bool,
+    }
+
+    impl DataProvider for MockDataProvider {
+        fn get_transcript(
+            &self,
+            ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn crate::data::Transcript>, HgvsError> {
+            if ac == "NM_000123.4" {
+                Ok(Box::new(TranscriptData {
+                    ac: "NM_000123.4".to_string(),
+                    gene: "ABC".to_string(),
+                    cds_start_index: Some(TranscriptPos(0)),
+                    cds_end_index: Some(TranscriptPos(19)),
+                    strand: 1,
+                    reference_accession: "NC_000001.11".to_string(),
+                    exons: vec![ExonData {
+                        transcript_start: TranscriptPos(0),
+                        transcript_end: TranscriptPos(19),
+                        reference_start: GenomicPos(0),
+                        reference_end: GenomicPos(19),
+                        alt_strand: 1,
+                        cigar: "20M".to_string(),
+                    }],
+                }))
+            } else {
+                Err(HgvsError::ValidationError("Not found".into()))
+            }
+        }
+
+        fn get_seq(
+            &self,
+            _ac: &str,
+            start: i32,
+            end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            let seq = "ACGTACGTACGTACGTACGT";
+            let s = start.max(0) as usize;
+            let e = if end == -1 { seq.len() } else { end as usize };
+            Ok(seq[s..e.min(seq.len())].to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            if self.with_protein_accession {
+                Ok(vec![(
+                    IdentifierType::ProteinAccession,
+                    "NP_000123.1".to_string(),
+                )])
+            } else {
+                Ok(vec![])
+            }
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: TranscriptPos,
+            offset: IntronicOffset,
+        ) -> Result<(String, GenomicPos), HgvsError> {
+            Ok(("NC_000001.11".to_string(), GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    struct MockSearch;
+    impl TranscriptSearch for MockSearch {
+        fn get_transcripts_for_region(
+            &self,
+            _ac: &str,
+            _s: i32,
+            _e: i32,
+        ) -> Result<Vec<String>, HgvsError> {
+            Ok(vec!["NM_000123.4".to_string()])
+        }
+    }
+
+    #[test]
+    fn annotate_record_reports_transcript_strand_on_success() {
+        let hdp = MockDataProvider {
+            with_protein_accession: true,
+        };
+        let searcher = MockSearch;
+
+        let records = annotate_record(&hdp, &searcher, "NC_000001.11", 5, "A", "T").unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec.transcript_ac, "NM_000123.4");
+        assert_eq!(rec.strand, Some(1));
+        assert!(rec.error.is_none());
+        assert!(rec.hgvs_p.is_some());
+    }
+
+    #[test]
+    fn annotate_record_reports_transcript_strand_even_when_c_to_p_fails() {
+        let hdp = MockDataProvider {
+            with_protein_accession: false,
+        };
+        let searcher = MockSearch;
+
+        let records = annotate_record(&hdp, &searcher, "NC_000001.11", 5, "A", "T").unwrap();
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec.strand, Some(1));
+        assert!(rec.error.is_some());
+        assert!(rec.hgvs_p.is_none());
+    }
+
+    #[test]
+    fn annotate_bgzipped_vcf_to_jsonl_writes_one_jsonl_row_per_variant() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write as _;
+
+        let hdp = MockDataProvider {
+            with_protein_accession: true,
+        };
+        let searcher = MockSearch;
+
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "hgvs-weaver-annotate-test-{:?}.vcf.gz",
+            std::thread::current().id()
+        ));
+        {
+            let file = std::fs::File::create(&path).unwrap();
+            let mut gz = GzEncoder::new(file, Compression::default());
+            writeln!(gz, "##fileformat=VCFv4.2").unwrap();
+            writeln!(gz, "#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO").unwrap();
+            writeln!(gz, "NC_000001.11\t5\t.\tA\tT\t.\t.\t.").unwrap();
+            gz.finish().unwrap();
+        }
+
+        let mut out = Vec::new();
+        let count = annotate_bgzipped_vcf_to_jsonl(&path, &hdp, &searcher, &mut out).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(count, 1);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let row: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(row["transcript_ac"], "NM_000123.4");
+        assert_eq!(row["pos"], 5);
+        assert!(row["hgvs_p"].as_str().is_some());
+    }
+}
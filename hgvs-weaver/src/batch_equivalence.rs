@@ -0,0 +1,273 @@
+//! Concurrent `equivalent_level` evaluation over large batches of variant
+//! pairs (e.g. cross-walking a ClinVar export against Weaver calls).
+//!
+//! A single [`VariantEquivalence`] comparison can fan out into many
+//! `DataProvider::get_seq`/`get_transcript` calls, and analogous-repeat
+//! detection in particular re-probes overlapping windows of the same
+//! sequence. [`evaluate_batch`] spreads pairs across a small worker pool,
+//! gives each worker its own [`CachingDataProvider`] so those repeat probes
+//! are served from memory, and streams results back through a
+//! [`BatchEquivalenceHandle`] modeled on a background check actor: results
+//! arrive as workers finish them, and `cancel()` asks idle workers to stop
+//! claiming new pairs without losing work already in flight.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+use crate::caching_provider::CachingDataProvider;
+use crate::coords::SequenceVariant;
+use crate::data::{DataProvider, TranscriptSearch};
+use crate::equivalence::{EquivalenceLevel, VariantEquivalence};
+use crate::error::HgvsError;
+
+/// One pair's outcome, tagged with its index into the input `pairs` slice so
+/// callers can put results streamed out of completion order back into input
+/// order.
+pub type BatchResult = (usize, Result<EquivalenceLevel, HgvsError>);
+
+/// A running (or finished) batch evaluation started by [`evaluate_batch`].
+pub struct BatchEquivalenceHandle {
+    results: Receiver<BatchResult>,
+    cancelled: Arc<AtomicBool>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl BatchEquivalenceHandle {
+    /// Asks every worker to stop claiming new pairs. Pairs already being
+    /// evaluated still run to completion and are still sent to the channel;
+    /// this only stops work that hasn't started yet.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Blocks for the next completed pair, in whatever order workers finish
+    /// them. Returns `None` once every worker has exited and no result is
+    /// left buffered.
+    pub fn recv(&self) -> Option<BatchResult> {
+        self.results.recv().ok()
+    }
+
+    /// Blocks until every worker has exited, then returns all results
+    /// re-sorted back into the order of the `pairs` passed to
+    /// [`evaluate_batch`].
+    pub fn collect_ordered(self) -> Vec<BatchResult> {
+        let mut out: Vec<BatchResult> = self.results.iter().collect();
+        for worker in self.workers {
+            let _ = worker.join();
+        }
+        out.sort_by_key(|(index, _)| *index);
+        out
+    }
+}
+
+/// Evaluates `pairs` concurrently across `worker_count` threads (clamped to
+/// at least 1) and returns a handle streaming each pair's
+/// [`EquivalenceLevel`] as it completes.
+///
+/// Each worker wraps `provider` in its own [`CachingDataProvider`], so
+/// repeated sequence windows probed while evaluating its share of the batch
+/// are fetched once rather than once per pair; workers don't share a cache
+/// with each other, since `CachingDataProvider` is interior-mutable and not
+/// `Sync`.
+pub fn evaluate_batch<D, S>(
+    provider: Arc<D>,
+    searcher: Arc<S>,
+    pairs: Vec<(SequenceVariant, SequenceVariant)>,
+    worker_count: usize,
+) -> BatchEquivalenceHandle
+where
+    D: DataProvider + Send + Sync + 'static,
+    S: TranscriptSearch + Send + Sync + 'static,
+{
+    let worker_count = worker_count.max(1);
+    let (tx, rx) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let next_index = Arc::new(AtomicUsize::new(0));
+    let pairs = Arc::new(pairs);
+
+    let mut workers = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let provider = provider.clone();
+        let searcher = searcher.clone();
+        let pairs = pairs.clone();
+        let next_index = next_index.clone();
+        let cancelled = cancelled.clone();
+        let tx = tx.clone();
+
+        workers.push(thread::spawn(move || {
+            let cache = CachingDataProvider::new(provider.as_ref());
+            let equiv = VariantEquivalence::new(&cache, searcher.as_ref());
+
+            loop {
+                if cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some((v1, v2)) = pairs.get(index) else {
+                    break;
+                };
+                let result = equiv.equivalent_level(v1, v2);
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    BatchEquivalenceHandle {
+        results: rx,
+        cancelled,
+        workers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ExonData, IdentifierKind, IdentifierType, Transcript};
+
+    struct StaticProvider {
+        seq: String,
+    }
+
+    impl DataProvider for StaticProvider {
+        fn get_transcript(
+            &self,
+            _ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            struct Mock;
+            impl Transcript for Mock {
+                fn ac(&self) -> &str {
+                    "NM_0001.1"
+                }
+                fn gene(&self) -> &str {
+                    "MOCK"
+                }
+                fn strand(&self) -> i32 {
+                    1
+                }
+                fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    None
+                }
+                fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    None
+                }
+                fn reference_accession(&self) -> &str {
+                    "NC_0001.1"
+                }
+                fn exons(&self) -> &[ExonData] {
+                    &[]
+                }
+            }
+            Ok(Box::new(Mock))
+        }
+
+        fn get_seq(
+            &self,
+            _ac: &str,
+            start: i32,
+            end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            let s = start.max(0) as usize;
+            let e = if end == -1 {
+                self.seq.len()
+            } else {
+                end as usize
+            };
+            Ok(self.seq[s..e.min(self.seq.len())].to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            Ok(vec![])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: crate::coords::TranscriptPos,
+            offset: crate::coords::IntronicOffset,
+        ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+            Ok(("NC_0001.1".to_string(), crate::coords::GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    struct NoSearch;
+    impl TranscriptSearch for NoSearch {
+        fn get_transcripts_for_region(
+            &self,
+            _chrom: &str,
+            _start: i32,
+            _end: i32,
+        ) -> Result<Vec<String>, HgvsError> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_evaluate_batch_preserves_order_after_collect() {
+        let provider = Arc::new(StaticProvider {
+            seq: "ACGTACGTACGTACGTACGT".to_string(),
+        });
+        let searcher = Arc::new(NoSearch);
+
+        let pairs: Vec<_> = (0..20)
+            .map(|i| {
+                let v1 = crate::parse_hgvs_variant("NC_000001.11:g.5A>T").unwrap();
+                let v2 = if i % 2 == 0 {
+                    crate::parse_hgvs_variant("NC_000001.11:g.5A>T").unwrap()
+                } else {
+                    crate::parse_hgvs_variant("NC_000001.11:g.6C>T").unwrap()
+                };
+                (v1, v2)
+            })
+            .collect();
+
+        let handle = evaluate_batch(provider, searcher, pairs, 4);
+        let results = handle.collect_ordered();
+
+        assert_eq!(results.len(), 20);
+        for (i, (index, result)) in results.into_iter().enumerate() {
+            assert_eq!(i, index);
+            let level = result.unwrap();
+            if i % 2 == 0 {
+                assert_eq!(level, EquivalenceLevel::Identity);
+            } else {
+                assert_eq!(level, EquivalenceLevel::Different);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cancel_stops_further_pairs_being_claimed() {
+        let provider = Arc::new(StaticProvider {
+            seq: "ACGTACGTACGTACGTACGT".to_string(),
+        });
+        let searcher = Arc::new(NoSearch);
+        let pairs: Vec<_> = (0..50)
+            .map(|_| {
+                let v1 = crate::parse_hgvs_variant("NC_000001.11:g.5A>T").unwrap();
+                let v2 = crate::parse_hgvs_variant("NC_000001.11:g.5A>T").unwrap();
+                (v1, v2)
+            })
+            .collect();
+
+        let handle = evaluate_batch(provider, searcher, pairs, 1);
+        let _ = handle.recv();
+        handle.cancel();
+        let results = handle.collect_ordered();
+        assert!(results.len() < 50);
+    }
+}
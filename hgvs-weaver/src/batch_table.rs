@@ -0,0 +1,321 @@
+//! Streaming CSV/TSV batch normalization: reads a table with a configurable
+//! HGVS column, runs each row through parse -> normalize -> project, and
+//! writes it back out augmented with the result, without ever holding more
+//! than one row in memory.
+//!
+//! This mirrors [`crate::annotate::VariantAnnotator`]'s streaming shape, but
+//! for a flat input table instead of VCF records, and follows
+//! [`crate::varfish`]'s lead of hand-rolling the delimited read/write rather
+//! than taking on a CSV crate dependency for a single column of interest.
+
+use std::io::{self, BufRead, Write};
+
+use crate::data::DataProvider;
+use crate::error::HgvsError;
+use crate::mapper::VariantMapper;
+
+/// How to read an input table and which column holds the HGVS string.
+#[derive(Debug, Clone)]
+pub struct BatchTableConfig {
+    pub delimiter: u8,
+    pub hgvs_column: usize,
+    /// When `false` (the default), the first row whose variant fails to
+    /// parse/normalize/project aborts [`process_table`]. When `true`, the
+    /// failure is recorded in that row's `error` column and the run
+    /// continues.
+    pub continue_on_error: bool,
+}
+
+impl BatchTableConfig {
+    pub fn new(delimiter: u8, hgvs_column: usize) -> Self {
+        BatchTableConfig {
+            delimiter,
+            hgvs_column,
+            continue_on_error: false,
+        }
+    }
+}
+
+/// Finds `name` in a header row, for callers that want to address the HGVS
+/// column by name rather than by index.
+pub fn locate_column(header: &[String], name: &str) -> Option<usize> {
+    header.iter().position(|h| h == name)
+}
+
+/// Splits one delimited line into fields, honoring double-quoted fields with
+/// `""`-escaped quotes (the RFC 4180 convention). This is not a general CSV
+/// dialect parser — just enough to round-trip the tables this tool reads.
+fn split_row(line: &str, delimiter: u8) -> Vec<String> {
+    let delimiter = delimiter as char;
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Inverse of [`split_row`]: quotes a field only if it needs it.
+fn join_row(fields: &[String], delimiter: u8) -> String {
+    let delimiter_char = delimiter as char;
+    fields
+        .iter()
+        .map(|f| {
+            if f.contains(delimiter_char) || f.contains('"') || f.contains('\n') {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&delimiter_char.to_string())
+}
+
+fn io_err(e: io::Error) -> HgvsError {
+    HgvsError::DataProviderError(e.to_string())
+}
+
+/// Runs one HGVS string through parse -> normalize -> project-to-genomic,
+/// never panicking: any failure along the pipeline is returned as `error`
+/// instead of propagated, so one bad variant never need abort a batch.
+///
+/// `genomic_hgvs` is populated via [`VariantMapper::to_spdi`], which
+/// resolves `g.`/`c.`/`n.` variants alike down to genomic SPDI coordinates.
+pub fn process_variant(
+    mapper: &VariantMapper,
+    hgvs: &str,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let parsed = match crate::parse_hgvs_variant(hgvs) {
+        Ok(v) => v,
+        Err(e) => return (None, None, Some(e.to_string())),
+    };
+    let normalized = match mapper.normalize_variant(parsed) {
+        Ok(v) => v,
+        Err(e) => return (None, None, Some(e.to_string())),
+    };
+    let normalized_hgvs = normalized.to_string();
+    match mapper.to_spdi(&normalized, false) {
+        Ok(spdi) => (Some(normalized_hgvs), Some(spdi), None),
+        Err(e) => (Some(normalized_hgvs), None, Some(e.to_string())),
+    }
+}
+
+/// Streams `reader` row-by-row, running each row's HGVS column through
+/// [`process_variant`] and writing `original columns .. normalized_hgvs,
+/// genomic_hgvs, error` to `sink`. Returns the number of data rows written.
+///
+/// `hdp` is wrapped once in a [`crate::caching_provider::CachingDataProvider`]
+/// shared across every row, so repeated transcript/sequence lookups within
+/// the batch are served from memory rather than re-fetched per row.
+pub fn process_table<R: BufRead, W: Write>(
+    hdp: &dyn DataProvider,
+    cfg: &BatchTableConfig,
+    mut reader: R,
+    sink: &mut W,
+) -> Result<usize, HgvsError> {
+    let cache = crate::caching_provider::CachingDataProvider::new(hdp);
+    let mapper = VariantMapper::new(&cache);
+
+    let mut header_line = String::new();
+    if reader.read_line(&mut header_line).map_err(io_err)? == 0 {
+        return Ok(0);
+    }
+    let mut header = split_row(header_line.trim_end_matches(['\n', '\r']), cfg.delimiter);
+    header.push("normalized_hgvs".to_string());
+    header.push("genomic_hgvs".to_string());
+    header.push("error".to_string());
+    writeln!(sink, "{}", join_row(&header, cfg.delimiter)).map_err(io_err)?;
+
+    let mut processed = 0usize;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let bytes_read = reader.read_line(&mut line).map_err(io_err)?;
+        if bytes_read == 0 {
+            break;
+        }
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let mut fields = split_row(trimmed, cfg.delimiter);
+        let hgvs = fields.get(cfg.hgvs_column).cloned().unwrap_or_default();
+        let (normalized, genomic, error) = process_variant(&mapper, &hgvs);
+
+        if let Some(message) = &error {
+            if !cfg.continue_on_error {
+                return Err(HgvsError::ValidationError(format!(
+                    "row {}: {message}",
+                    processed + 1
+                )));
+            }
+        }
+
+        fields.push(normalized.unwrap_or_default());
+        fields.push(genomic.unwrap_or_default());
+        fields.push(error.unwrap_or_default());
+        writeln!(sink, "{}", join_row(&fields, cfg.delimiter)).map_err(io_err)?;
+        processed += 1;
+    }
+
+    Ok(processed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ExonData, IdentifierKind, IdentifierType, Transcript};
+
+    struct StaticProvider;
+
+    impl DataProvider for StaticProvider {
+        fn get_transcript(
+            &self,
+            _ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            struct Mock;
+            impl Transcript for Mock {
+                fn ac(&self) -> &str {
+                    "NM_0001.1"
+                }
+                fn gene(&self) -> &str {
+                    "MOCK"
+                }
+                fn strand(&self) -> i32 {
+                    1
+                }
+                fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    None
+                }
+                fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    None
+                }
+                fn reference_accession(&self) -> &str {
+                    "NC_0001.1"
+                }
+                fn exons(&self) -> &[ExonData] {
+                    &[]
+                }
+            }
+            Ok(Box::new(Mock))
+        }
+
+        fn get_seq(
+            &self,
+            _ac: &str,
+            _start: i32,
+            _end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            Ok("ACGTACGTACGTACGTACGT".to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            Ok(vec![])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: crate::coords::TranscriptPos,
+            offset: crate::coords::IntronicOffset,
+        ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+            Ok(("NC_0001.1".to_string(), crate::coords::GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    #[test]
+    fn test_split_row_handles_quoted_delimiter() {
+        let fields = split_row(r#"a,"b,c",d"#, b',');
+        assert_eq!(fields, vec!["a", "b,c", "d"]);
+    }
+
+    #[test]
+    fn test_locate_column_finds_header_by_name() {
+        let header = vec!["id".to_string(), "hgvs".to_string()];
+        assert_eq!(locate_column(&header, "hgvs"), Some(1));
+        assert_eq!(locate_column(&header, "missing"), None);
+    }
+
+    #[test]
+    fn test_process_table_streams_valid_rows() {
+        let provider = StaticProvider;
+        let cfg = BatchTableConfig::new(b',', 1);
+        let input = "id,hgvs\n1,NC_0001.1:g.5A>T\n";
+        let mut output = Vec::new();
+        let rows =
+            process_table(&provider, &cfg, io::Cursor::new(input.as_bytes()), &mut output)
+                .unwrap();
+        assert_eq!(rows, 1);
+        let text = String::from_utf8(output).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "id,hgvs,normalized_hgvs,genomic_hgvs,error"
+        );
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("1,NC_0001.1:g.5A>T,"));
+        assert!(!row.ends_with(','), "error column should be empty: {row}");
+    }
+
+    #[test]
+    fn test_process_table_aborts_on_bad_row_by_default() {
+        let provider = StaticProvider;
+        let cfg = BatchTableConfig::new(b',', 1);
+        let input = "id,hgvs\n1,not-a-variant\n";
+        let mut output = Vec::new();
+        assert!(process_table(&provider, &cfg, io::Cursor::new(input.as_bytes()), &mut output)
+            .is_err());
+    }
+
+    #[test]
+    fn test_process_table_continue_on_error_keeps_going() {
+        let provider = StaticProvider;
+        let mut cfg = BatchTableConfig::new(b',', 1);
+        cfg.continue_on_error = true;
+        let input = "id,hgvs\n1,not-a-variant\n2,NC_0001.1:g.5A>T\n";
+        let mut output = Vec::new();
+        let rows =
+            process_table(&provider, &cfg, io::Cursor::new(input.as_bytes()), &mut output)
+                .unwrap();
+        assert_eq!(rows, 2);
+        let text = String::from_utf8(output).unwrap();
+        let mut lines = text.lines();
+        lines.next();
+        let bad_row = lines.next().unwrap();
+        assert!(bad_row.starts_with("1,not-a-variant,,,"));
+        assert!(!bad_row.ends_with(',')); // error column is non-empty
+        assert!(lines.next().unwrap().starts_with("2,NC_0001.1:g.5A>T,"));
+    }
+}
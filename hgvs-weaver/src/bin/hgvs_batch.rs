@@ -0,0 +1,126 @@
+//! Thin CLI wrapper around [`hgvs_weaver::batch_table`]: reads a CSV/TSV
+//! table from a file (or stdin), normalizes and projects its HGVS column,
+//! and writes the augmented table to a file (or stdout). All the actual
+//! work lives in the library; this binary only parses flags and wires up
+//! I/O.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use hgvs_weaver::batch_table::{locate_column, process_table, BatchTableConfig};
+use hgvs_weaver::flatfile_provider::FlatFileDataProvider;
+
+struct Args {
+    bundle: String,
+    input: Option<String>,
+    output: Option<String>,
+    column: String,
+    delimiter: u8,
+    continue_on_error: bool,
+}
+
+fn usage() -> &'static str {
+    "usage: hgvs_batch --bundle <flatfile.json> --column <name|index> \
+     [--input <path>] [--output <path>] [--delimiter ,|\\t] [--continue-on-error]"
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut bundle = None;
+    let mut input = None;
+    let mut output = None;
+    let mut column = None;
+    let mut delimiter = b',';
+    let mut continue_on_error = false;
+
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or_else(|| format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--bundle" => bundle = Some(value()?),
+            "--input" => input = Some(value()?),
+            "--output" => output = Some(value()?),
+            "--column" => column = Some(value()?),
+            "--delimiter" => {
+                delimiter = match value()?.as_str() {
+                    "\\t" | "tab" => b'\t',
+                    s if s.len() == 1 => s.as_bytes()[0],
+                    other => return Err(format!("unsupported delimiter: {other}")),
+                }
+            }
+            "--continue-on-error" => continue_on_error = true,
+            other => return Err(format!("unrecognized argument: {other}")),
+        }
+    }
+
+    Ok(Args {
+        bundle: bundle.ok_or("--bundle is required")?,
+        input,
+        output,
+        column: column.ok_or("--column is required")?,
+        delimiter,
+        continue_on_error,
+    })
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args().map_err(|e| format!("{e}\n{}", usage()))?;
+
+    let provider = FlatFileDataProvider::load(&args.bundle).map_err(|e| e.to_string())?;
+
+    let mut reader: Box<dyn BufRead> = match &args.input {
+        Some(path) => Box::new(BufReader::new(
+            File::open(path).map_err(|e| format!("opening {path}: {e}"))?,
+        )),
+        None => Box::new(BufReader::new(io::stdin())),
+    };
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|e| format!("creating {path}: {e}"))?,
+        )),
+        None => Box::new(io::stdout()),
+    };
+
+    // `process_table` re-reads the header itself, so resolving a column by
+    // name means peeking that first line and re-stitching it back onto the
+    // stream rather than consuming it here.
+    let hgvs_column = if let Ok(index) = args.column.parse::<usize>() {
+        index
+    } else {
+        let mut header_line = String::new();
+        reader
+            .read_line(&mut header_line)
+            .map_err(|e| e.to_string())?;
+        let header: Vec<String> = header_line
+            .trim_end_matches(['\n', '\r'])
+            .split(args.delimiter as char)
+            .map(str::to_string)
+            .collect();
+        let index = locate_column(&header, &args.column)
+            .ok_or_else(|| format!("column {:?} not found in header", args.column))?;
+        reader = Box::new(BufReader::new(
+            io::Cursor::new(header_line.into_bytes()).chain(reader),
+        ));
+        index
+    };
+
+    let cfg = BatchTableConfig {
+        delimiter: args.delimiter,
+        hgvs_column,
+        continue_on_error: args.continue_on_error,
+    };
+    let rows = process_table(&provider, &cfg, reader, &mut writer).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())?;
+    eprintln!("hgvs_batch: wrote {rows} row(s)");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("hgvs_batch: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! A memoizing [`DataProvider`] wrapper for batch equivalence workloads.
+//!
+//! `n_vs_n_equivalent`/`c_vs_n_equivalent`/`try_normalize_to_dup` and friends
+//! in [`crate::equivalence`] and [`crate::mapper`] repeatedly call
+//! `get_transcript` and `get_seq` for the same accessions and overlapping
+//! coordinate ranges when one variant is compared against many others.
+//! [`CachingDataProvider`] sits in front of a real provider and serves
+//! repeat calls from memory instead of re-hitting `self.inner`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::rc::Rc;
+
+use crate::data::{DataProvider, IdentifierKind, IdentifierType, Transcript};
+use crate::error::HgvsError;
+
+/// A minimal FxHash-style hasher: fast and collision-tolerant for short
+/// accession-string keys, at the cost of no DoS resistance — fine here since
+/// keys come from trusted transcript/genomic accessions, not untrusted input.
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut buf = [0u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            let word = u64::from_ne_bytes(buf);
+            self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
+type FxHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A fetched sequence window, `[start, start + seq.len())`.
+///
+/// `is_full` marks a window fetched with `end == -1` (the "whole sequence"
+/// sentinel `DataProvider::get_seq` callers use for CDS/protein lookups), so
+/// a later `end == -1` request against the same key can be served from cache
+/// even though `-1` itself can't be compared against `start + seq.len()`.
+struct SeqSpan {
+    start: i32,
+    seq: String,
+    is_full: bool,
+}
+
+impl SeqSpan {
+    fn end(&self) -> i32 {
+        self.start + self.seq.len() as i32
+    }
+
+    fn covers(&self, start: i32, end: i32) -> bool {
+        if end == -1 {
+            self.is_full
+        } else {
+            start >= self.start && end <= self.end()
+        }
+    }
+
+    fn slice(&self, start: i32, end: i32) -> String {
+        let s = (start - self.start).max(0) as usize;
+        let e = if end == -1 {
+            self.seq.len()
+        } else {
+            (end - self.start).max(0) as usize
+        };
+        self.seq[s..e.min(self.seq.len())].to_string()
+    }
+}
+
+/// A `Transcript` that cheaply clones an already-fetched transcript out of
+/// the cache: each `get_transcript` cache hit returns a fresh `Box` wrapping
+/// the same `Rc<dyn Transcript>`, rather than re-fetching from `inner`.
+struct CachedTranscript(Rc<dyn Transcript>);
+
+impl Transcript for CachedTranscript {
+    fn ac(&self) -> &str {
+        self.0.ac()
+    }
+    fn gene(&self) -> &str {
+        self.0.gene()
+    }
+    fn strand(&self) -> i32 {
+        self.0.strand()
+    }
+    fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_start_index()
+    }
+    fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_end_index()
+    }
+    fn reference_accession(&self) -> &str {
+        self.0.reference_accession()
+    }
+    fn exons(&self) -> &[crate::data::ExonData] {
+        self.0.exons()
+    }
+}
+
+/// Memoizes [`DataProvider::get_transcript`] by accession and
+/// [`DataProvider::get_seq`] by `(accession, kind)`, falling back to `inner`
+/// on a miss. Interior-mutable so it can sit behind `&self` — the same
+/// access pattern [`crate::equivalence::VariantEquivalence`] already uses
+/// for its `&'a dyn DataProvider`.
+pub struct CachingDataProvider<'a, D: DataProvider + ?Sized> {
+    inner: &'a D,
+    transcripts: RefCell<FxHashMap<String, Rc<dyn Transcript>>>,
+    sequences: RefCell<FxHashMap<(String, IdentifierType), SeqSpan>>,
+}
+
+impl<'a, D: DataProvider + ?Sized> CachingDataProvider<'a, D> {
+    pub fn new(inner: &'a D) -> Self {
+        CachingDataProvider {
+            inner,
+            transcripts: RefCell::new(FxHashMap::default()),
+            sequences: RefCell::new(FxHashMap::default()),
+        }
+    }
+}
+
+impl<'a, D: DataProvider + ?Sized> DataProvider for CachingDataProvider<'a, D> {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        ref_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        if let Some(rc) = self.transcripts.borrow().get(ac) {
+            return Ok(Box::new(CachedTranscript(rc.clone())));
+        }
+        let boxed = self.inner.get_transcript(ac, ref_ac)?;
+        let rc: Rc<dyn Transcript> = Rc::from(boxed);
+        self.transcripts.borrow_mut().insert(ac.to_string(), rc.clone());
+        Ok(Box::new(CachedTranscript(rc)))
+    }
+
+    fn get_seq(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let key = (ac.to_string(), kind);
+        if let Some(span) = self.sequences.borrow().get(&key) {
+            if span.covers(start, end) {
+                return Ok(span.slice(start, end));
+            }
+        }
+
+        // Miss: widen the fetch to cover the union of what's already cached
+        // and what's newly requested, so a later request that falls between
+        // the two doesn't also miss.
+        let (fetch_start, fetch_end) = match self.sequences.borrow().get(&key) {
+            Some(span) if end != -1 => (start.min(span.start), end.max(span.end())),
+            _ => (start, end),
+        };
+        let seq = self.inner.get_seq(ac, fetch_start, fetch_end, kind)?;
+        let span = SeqSpan {
+            start: fetch_start,
+            seq,
+            is_full: fetch_end == -1,
+        };
+        let result = span.slice(start, end);
+        self.sequences.borrow_mut().insert(key, span);
+        Ok(result)
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        from: IdentifierKind,
+        to: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        self.inner.get_symbol_accessions(symbol, from, to)
+    }
+
+    fn get_identifier_type(&self, id: &str) -> Result<IdentifierType, HgvsError> {
+        self.inner.get_identifier_type(id)
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: crate::coords::TranscriptPos,
+        offset: crate::coords::IntronicOffset,
+    ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+        self.inner.c_to_g(transcript_ac, pos, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ExonData;
+    use std::cell::Cell;
+
+    struct CountingProvider {
+        get_seq_calls: Cell<u32>,
+        get_transcript_calls: Cell<u32>,
+    }
+
+    impl DataProvider for CountingProvider {
+        fn get_transcript(
+            &self,
+            ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            self.get_transcript_calls.set(self.get_transcript_calls.get() + 1);
+            struct Mock;
+            impl Transcript for Mock {
+                fn ac(&self) -> &str {
+                    "NM_0001.1"
+                }
+                fn gene(&self) -> &str {
+                    "MOCK"
+                }
+                fn strand(&self) -> i32 {
+                    1
+                }
+                fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    Some(crate::coords::TranscriptPos(0))
+                }
+                fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    Some(crate::coords::TranscriptPos(10))
+                }
+                fn reference_accession(&self) -> &str {
+                    "NC_0001.1"
+                }
+                fn exons(&self) -> &[ExonData] {
+                    &[]
+                }
+            }
+            let _ = ac;
+            Ok(Box::new(Mock))
+        }
+
+        fn get_seq(
+            &self,
+            _ac: &str,
+            start: i32,
+            end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            self.get_seq_calls.set(self.get_seq_calls.get() + 1);
+            let full = "ACGTACGTACGTACGTACGT";
+            let s = start as usize;
+            let e = if end == -1 { full.len() } else { end as usize };
+            Ok(full[s..e.min(full.len())].to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            Ok(vec![])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: crate::coords::TranscriptPos,
+            offset: crate::coords::IntronicOffset,
+        ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+            Ok(("NC_0001.1".to_string(), crate::coords::GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    #[test]
+    fn test_get_transcript_is_memoized_by_accession() {
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+        let cache = CachingDataProvider::new(&inner);
+
+        for _ in 0..3 {
+            let t = cache.get_transcript("NM_0001.1", None).unwrap();
+            assert_eq!(t.gene(), "MOCK");
+        }
+        assert_eq!(inner.get_transcript_calls.get(), 1);
+    }
+
+    #[test]
+    fn test_get_seq_slices_repeat_subrange_requests_from_cache() {
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+        let cache = CachingDataProvider::new(&inner);
+
+        let full = cache
+            .get_seq("NM_0001.1", 0, -1, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(full, "ACGTACGTACGTACGTACGT");
+        assert_eq!(inner.get_seq_calls.get(), 1);
+
+        // A subrange of the already-cached whole sequence should not refetch.
+        let sub = cache
+            .get_seq("NM_0001.1", 4, 8, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(sub, "ACGT");
+        assert_eq!(inner.get_seq_calls.get(), 1);
+
+        // A second whole-sequence request is also served from the cached span.
+        let full_again = cache
+            .get_seq("NM_0001.1", 0, -1, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(full_again, "ACGTACGTACGTACGTACGT");
+        assert_eq!(inner.get_seq_calls.get(), 1);
+    }
+
+    #[test]
+    fn test_get_seq_widens_cached_span_on_partial_miss() {
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+        let cache = CachingDataProvider::new(&inner);
+
+        let first = cache
+            .get_seq("NM_0001.1", 0, 4, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(first, "ACGT");
+        assert_eq!(inner.get_seq_calls.get(), 1);
+
+        // Overlaps but extends past the cached [0, 4) span: one more fetch,
+        // widened to cover both.
+        let second = cache
+            .get_seq("NM_0001.1", 2, 8, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(second, "GTACGT");
+        assert_eq!(inner.get_seq_calls.get(), 2);
+
+        // Now fully inside the widened [0, 8) span: no further fetch.
+        let third = cache
+            .get_seq("NM_0001.1", 1, 3, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(third, "CG");
+        assert_eq!(inner.get_seq_calls.get(), 2);
+    }
+}
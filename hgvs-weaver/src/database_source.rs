@@ -0,0 +1,252 @@
+//! Source-scoped accession resolution for symbols that resolve differently
+//! in RefSeq and Ensembl.
+//!
+//! RefSeq and Ensembl disagree on exon boundaries and CDS starts for the
+//! same gene, so a symbol lookup that doesn't pin down which namespace it's
+//! scoped to can silently hand back a transcript from the wrong nomenclature
+//! system. [`DataProvider::get_symbol_accessions`] itself stays source-
+//! agnostic (it's implemented against whatever backend the provider wraps),
+//! so this module layers source filtering on top of it rather than changing
+//! its signature.
+use crate::data::{DataProvider, IdentifierKind, IdentifierType};
+use crate::error::HgvsError;
+
+/// The nomenclature namespace an accession belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseSource {
+    RefSeq,
+    Ensembl,
+}
+
+impl DatabaseSource {
+    /// Infers the source from an accession's prefix convention, e.g.
+    /// `NM_000123.4` is RefSeq and `ENST00000357654` is Ensembl.
+    ///
+    /// Returns `None` for accessions that don't match either convention
+    /// (local identifiers, test fixtures, etc.).
+    pub fn for_accession(ac: &str) -> Option<Self> {
+        if ac.starts_with("ENST")
+            || ac.starts_with("ENSP")
+            || ac.starts_with("ENSG")
+            || ac.starts_with("ENSR")
+        {
+            Some(Self::Ensembl)
+        } else if ac.starts_with("NM_")
+            || ac.starts_with("NP_")
+            || ac.starts_with("NC_")
+            || ac.starts_with("NR_")
+            || ac.starts_with("NG_")
+        {
+            Some(Self::RefSeq)
+        } else {
+            None
+        }
+    }
+}
+
+/// Infers an accession's [`IdentifierType`] from its naming convention,
+/// recognizing both RefSeq (`NM_`/`NR_`/`NP_`/`NC_`/`NG_`) and Ensembl
+/// (`ENST`/`ENSP`/`ENSG`) prefixes, version suffix or not (e.g.
+/// `ENST00000357654.3`). Returns `None` for anything else -- including bare
+/// gene symbols, which only [`DataProvider::get_symbol_accessions`] can
+/// resolve -- so a provider can fall back to this before giving up with
+/// [`IdentifierType::Unknown`].
+pub fn identifier_type_for_accession(ac: &str) -> Option<IdentifierType> {
+    if ac.starts_with("NM_") || ac.starts_with("NR_") || ac.starts_with("ENST") {
+        Some(IdentifierType::TranscriptAccession)
+    } else if ac.starts_with("NP_") || ac.starts_with("ENSP") {
+        Some(IdentifierType::ProteinAccession)
+    } else if ac.starts_with("NC_") || ac.starts_with("NG_") {
+        Some(IdentifierType::GenomicAccession)
+    } else if ac.starts_with("ENSG") {
+        Some(IdentifierType::GeneSymbol)
+    } else {
+        None
+    }
+}
+
+/// Resolves `symbol` to `to`-kind accessions scoped to `source`, filtering
+/// out any accession [`DatabaseSource::for_accession`] doesn't attribute to
+/// `source`.
+pub fn get_symbol_accessions_for_source(
+    hdp: &dyn DataProvider,
+    symbol: &str,
+    from: IdentifierKind,
+    to: IdentifierKind,
+    source: DatabaseSource,
+) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+    let accessions = hdp.get_symbol_accessions(symbol, from, to)?;
+    Ok(accessions
+        .into_iter()
+        .filter(|(_, ac)| DatabaseSource::for_accession(ac) == Some(source))
+        .collect())
+}
+
+/// Resolves `symbol` to a single `source`-scoped transcript accession.
+///
+/// Errors if the symbol has no accession in `source`, or if it resolves to
+/// more than one (callers that want to pick among several ambiguous
+/// transcripts should use [`get_symbol_accessions_for_source`] directly).
+pub fn preferred_transcript_accession(
+    hdp: &dyn DataProvider,
+    symbol: &str,
+    source: DatabaseSource,
+) -> Result<String, HgvsError> {
+    let mut matches = get_symbol_accessions_for_source(
+        hdp,
+        symbol,
+        IdentifierKind::Gene,
+        IdentifierKind::Transcript,
+        source,
+    )?;
+    match matches.len() {
+        0 => Err(HgvsError::ValidationError(format!(
+            "No {:?} transcript accession found for symbol {}",
+            source, symbol
+        ))),
+        1 => Ok(matches.remove(0).1),
+        _ => Err(HgvsError::ValidationError(format!(
+            "Symbol {} resolves to multiple {:?} transcript accessions",
+            symbol, source
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{IdentifierKind, IdentifierType, Transcript};
+    use crate::error::HgvsError;
+
+    #[test]
+    fn test_for_accession_distinguishes_refseq_and_ensembl() {
+        assert_eq!(
+            DatabaseSource::for_accession("NM_000123.4"),
+            Some(DatabaseSource::RefSeq)
+        );
+        assert_eq!(
+            DatabaseSource::for_accession("ENST00000357654"),
+            Some(DatabaseSource::Ensembl)
+        );
+        assert_eq!(DatabaseSource::for_accession("BRCA1"), None);
+    }
+
+    #[test]
+    fn test_identifier_type_for_accession_recognizes_ensembl_and_refseq() {
+        assert_eq!(
+            identifier_type_for_accession("ENST00000357654.9"),
+            Some(IdentifierType::TranscriptAccession)
+        );
+        assert_eq!(
+            identifier_type_for_accession("ENSP00000349216"),
+            Some(IdentifierType::ProteinAccession)
+        );
+        assert_eq!(
+            identifier_type_for_accession("ENSG00000012048"),
+            Some(IdentifierType::GeneSymbol)
+        );
+        assert_eq!(
+            identifier_type_for_accession("NM_007294.4"),
+            Some(IdentifierType::TranscriptAccession)
+        );
+        assert_eq!(
+            identifier_type_for_accession("NP_009225.1"),
+            Some(IdentifierType::ProteinAccession)
+        );
+        assert_eq!(identifier_type_for_accession("BRCA1"), None);
+    }
+
+    struct DualSourceProvider;
+    impl DataProvider for DualSourceProvider {
+        fn get_transcript(
+            &self,
+            _ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            Err(HgvsError::ValidationError("not used".into()))
+        }
+        fn get_seq(
+            &self,
+            _ac: &str,
+            _start: i32,
+            _end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            Ok(String::new())
+        }
+        fn get_symbol_accessions(
+            &self,
+            symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            if symbol == "BRCA1" {
+                Ok(vec![
+                    (IdentifierType::TranscriptAccession, "NM_007294.4".to_string()),
+                    (
+                        IdentifierType::TranscriptAccession,
+                        "ENST00000357654".to_string(),
+                    ),
+                ])
+            } else {
+                Ok(vec![])
+            }
+        }
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::TranscriptAccession)
+        }
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: crate::coords::TranscriptPos,
+            offset: crate::coords::IntronicOffset,
+        ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+            Ok((
+                "NC_000017.11".to_string(),
+                crate::coords::GenomicPos(pos.0 + offset.0),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_get_symbol_accessions_for_source_filters_by_namespace() -> Result<(), HgvsError> {
+        let hdp = DualSourceProvider;
+        let refseq = get_symbol_accessions_for_source(
+            &hdp,
+            "BRCA1",
+            IdentifierKind::Gene,
+            IdentifierKind::Transcript,
+            DatabaseSource::RefSeq,
+        )?;
+        assert_eq!(refseq, vec![(IdentifierType::TranscriptAccession, "NM_007294.4".to_string())]);
+
+        let ensembl = get_symbol_accessions_for_source(
+            &hdp,
+            "BRCA1",
+            IdentifierKind::Gene,
+            IdentifierKind::Transcript,
+            DatabaseSource::Ensembl,
+        )?;
+        assert_eq!(
+            ensembl,
+            vec![(IdentifierType::TranscriptAccession, "ENST00000357654".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_preferred_transcript_accession_picks_single_match() -> Result<(), HgvsError> {
+        let hdp = DualSourceProvider;
+        assert_eq!(
+            preferred_transcript_accession(&hdp, "BRCA1", DatabaseSource::RefSeq)?,
+            "NM_007294.4"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_preferred_transcript_accession_errors_when_no_match() {
+        let hdp = DualSourceProvider;
+        assert!(preferred_transcript_accession(&hdp, "UNKNOWN", DatabaseSource::RefSeq).is_err());
+    }
+}
@@ -0,0 +1,495 @@
+//! Persistent, content-addressed on-disk cache in front of a [`DataProvider`].
+//!
+//! [`crate::caching_provider::CachingDataProvider`] memoizes transcript and
+//! sequence lookups for the lifetime of one process; this module extends
+//! that idea across process runs by spilling the same lookups to disk.
+//! Every `get_transcript`/`get_seq` call is keyed by an md-5 digest of its
+//! arguments (hex-encoded with `base16ct`, the same style SeqRepo uses for
+//! its content-addressed digests) and stored as a gzip-compressed CBOR blob
+//! under `directory`, sharded into one level of subdirectories named by
+//! the key's first two hex characters (at most 256 of them). A hit is served
+//! straight from disk without touching `inner`; a miss fetches once, writes
+//! the blob, and returns it. [`DiskCachingProvider`] doesn't itself keep an
+//! in-memory index — stack a [`crate::caching_provider::CachingDataProvider`]
+//! in front of it if a batch also wants same-process memoization.
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+
+use crate::data::{DataProvider, IdentifierKind, IdentifierType, Transcript, TranscriptData};
+use crate::error::HgvsError;
+
+/// Where the cache lives on disk and how long an entry stays valid.
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    pub directory: PathBuf,
+    /// Soft cap on total blob size; [`DiskCachingProvider::enforce_size_budget`]
+    /// deletes the least-recently-written blobs until under this, but a plain
+    /// `get`/`put` never evicts on its own mid-batch.
+    pub max_size_bytes: Option<u64>,
+    /// Entries older than this are treated as a miss and re-fetched.
+    pub ttl: Option<Duration>,
+}
+
+impl DiskCacheConfig {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        DiskCacheConfig {
+            directory: directory.into(),
+            max_size_bytes: None,
+            ttl: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    stored_at_unix_secs: u64,
+    value: T,
+}
+
+/// Wraps an already-fetched [`TranscriptData`] so a disk-cache hit can hand
+/// back a `Box<dyn Transcript>` without re-fetching from `inner`.
+struct CachedTranscript(TranscriptData);
+
+impl Transcript for CachedTranscript {
+    fn ac(&self) -> &str {
+        self.0.ac()
+    }
+    fn gene(&self) -> &str {
+        self.0.gene()
+    }
+    fn strand(&self) -> i32 {
+        self.0.strand()
+    }
+    fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_start_index()
+    }
+    fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_end_index()
+    }
+    fn reference_accession(&self) -> &str {
+        self.0.reference_accession()
+    }
+    fn exons(&self) -> &[crate::data::ExonData] {
+        self.0.exons()
+    }
+}
+
+fn snapshot_transcript(t: &dyn Transcript) -> TranscriptData {
+    TranscriptData {
+        ac: t.ac().to_string(),
+        gene: t.gene().to_string(),
+        strand: t.strand(),
+        cds_start_index: t.cds_start_index(),
+        cds_end_index: t.cds_end_index(),
+        reference_accession: t.reference_accession().to_string(),
+        exons: t.exons().to_vec(),
+    }
+}
+
+fn io_err(e: impl ToString) -> HgvsError {
+    HgvsError::DataProviderError(e.to_string())
+}
+
+fn digest_hex(parts: &[&str]) -> String {
+    let mut hasher = Md5::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            hasher.update([0x1f]);
+        }
+        hasher.update(part.as_bytes());
+    }
+    let digest = hasher.finalize();
+    let mut hex = [0u8; 32];
+    base16ct::lower::encode_str(&digest, &mut hex)
+        .expect("32-byte buffer always fits a 16-byte md5 digest")
+        .to_string()
+}
+
+/// A [`DataProvider`] backed by a local, persistent, content-addressed cache
+/// in front of `inner`. See the module docs for the on-disk layout.
+pub struct DiskCachingProvider<'a, D: DataProvider + ?Sized> {
+    inner: &'a D,
+    config: DiskCacheConfig,
+}
+
+impl<'a, D: DataProvider + ?Sized> DiskCachingProvider<'a, D> {
+    pub fn new(inner: &'a D, config: DiskCacheConfig) -> Result<Self, HgvsError> {
+        fs::create_dir_all(&config.directory).map_err(io_err)?;
+        Ok(DiskCachingProvider { inner, config })
+    }
+
+    fn blob_path(&self, key: &str) -> PathBuf {
+        self.config.directory.join(&key[..2]).join(format!("{key}.cbor.gz"))
+    }
+
+    fn read_entry<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Option<T> {
+        let path = self.blob_path(key);
+        let bytes = fs::read(&path).ok()?;
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .ok()?;
+        let entry: CacheEntry<T> = serde_cbor::from_slice(&decompressed).ok()?;
+
+        if let Some(ttl) = self.config.ttl {
+            let age = SystemTime::now()
+                .duration_since(UNIX_EPOCH + Duration::from_secs(entry.stored_at_unix_secs))
+                .ok()?;
+            if age > ttl {
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        }
+        Some(entry.value)
+    }
+
+    fn write_entry<T: Serialize>(&self, key: &str, value: &T) -> Result<(), HgvsError> {
+        #[derive(Serialize)]
+        struct EntryRef<'a, T> {
+            stored_at_unix_secs: u64,
+            value: &'a T,
+        }
+
+        let path = self.blob_path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_err)?;
+        }
+        let stored_at_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(io_err)?
+            .as_secs();
+        let entry = EntryRef {
+            stored_at_unix_secs,
+            value,
+        };
+        let cbor = serde_cbor::to_vec(&entry).map_err(io_err)?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&cbor).map_err(io_err)?;
+        let gzipped = encoder.finish().map_err(io_err)?;
+        fs::write(&path, gzipped).map_err(io_err)
+    }
+
+    /// Pre-fetches every transcript [`DataProvider::get_symbol_accessions`]
+    /// returns for `gene`, populating the on-disk cache so a later batch run
+    /// against the same gene can proceed entirely offline. Returns the
+    /// number of transcripts warmed.
+    pub fn warm_up_gene(&self, gene: &str) -> Result<usize, HgvsError> {
+        let accessions =
+            self.inner
+                .get_symbol_accessions(gene, IdentifierKind::Gene, IdentifierKind::Transcript)?;
+        let mut warmed = 0;
+        for (_, ac) in accessions {
+            self.get_transcript(&ac, None)?;
+            warmed += 1;
+        }
+        Ok(warmed)
+    }
+
+    /// Deletes the least-recently-written blobs (by file mtime) until the
+    /// cache directory's total size is back under `max_size_bytes`. A no-op
+    /// if no budget was configured.
+    pub fn enforce_size_budget(&self) -> Result<(), HgvsError> {
+        let Some(budget) = self.config.max_size_bytes else {
+            return Ok(());
+        };
+
+        let mut blobs = self.collect_blob_metadata()?;
+        let mut total: u64 = blobs.iter().map(|(_, _, size)| size).sum();
+        if total <= budget {
+            return Ok(());
+        }
+
+        blobs.sort_by_key(|(_, modified, _)| *modified);
+        for (path, _, size) in blobs {
+            if total <= budget {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_blob_metadata(&self) -> Result<Vec<(PathBuf, SystemTime, u64)>, HgvsError> {
+        let mut blobs = Vec::new();
+        collect_blobs_recursive(&self.config.directory, &mut blobs)?;
+        Ok(blobs)
+    }
+}
+
+fn collect_blobs_recursive(
+    dir: &Path,
+    out: &mut Vec<(PathBuf, SystemTime, u64)>,
+) -> Result<(), HgvsError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+    for entry in entries {
+        let entry = entry.map_err(io_err)?;
+        let path = entry.path();
+        let metadata = entry.metadata().map_err(io_err)?;
+        if metadata.is_dir() {
+            collect_blobs_recursive(&path, out)?;
+        } else {
+            let modified = metadata.modified().map_err(io_err)?;
+            out.push((path, modified, metadata.len()));
+        }
+    }
+    Ok(())
+}
+
+impl<'a, D: DataProvider + ?Sized> DataProvider for DiskCachingProvider<'a, D> {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        reference_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        let key = digest_hex(&["transcript", ac, reference_ac.unwrap_or("")]);
+        if let Some(data) = self.read_entry::<TranscriptData>(&key) {
+            return Ok(Box::new(CachedTranscript(data)));
+        }
+        let transcript = self.inner.get_transcript(ac, reference_ac)?;
+        let data = snapshot_transcript(transcript.as_ref());
+        self.write_entry(&key, &data)?;
+        Ok(Box::new(CachedTranscript(data)))
+    }
+
+    fn get_seq(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let key = digest_hex(&[
+            "seq",
+            ac,
+            &start.to_string(),
+            &end.to_string(),
+            &format!("{kind:?}"),
+        ]);
+        if let Some(seq) = self.read_entry::<String>(&key) {
+            return Ok(seq);
+        }
+        let seq = self.inner.get_seq(ac, start, end, kind)?;
+        self.write_entry(&key, &seq)?;
+        Ok(seq)
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        from: IdentifierKind,
+        to: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        self.inner.get_symbol_accessions(symbol, from, to)
+    }
+
+    fn get_identifier_type(&self, identifier: &str) -> Result<IdentifierType, HgvsError> {
+        self.inner.get_identifier_type(identifier)
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: crate::coords::TranscriptPos,
+        offset: crate::coords::IntronicOffset,
+    ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+        self.inner.c_to_g(transcript_ac, pos, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ExonData;
+    use std::cell::Cell;
+
+    struct CountingProvider {
+        get_seq_calls: Cell<u32>,
+        get_transcript_calls: Cell<u32>,
+    }
+
+    impl DataProvider for CountingProvider {
+        fn get_transcript(
+            &self,
+            _ac: &str,
+            _ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            self.get_transcript_calls.set(self.get_transcript_calls.get() + 1);
+            struct Mock;
+            impl Transcript for Mock {
+                fn ac(&self) -> &str {
+                    "NM_0001.1"
+                }
+                fn gene(&self) -> &str {
+                    "MOCK"
+                }
+                fn strand(&self) -> i32 {
+                    1
+                }
+                fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    Some(crate::coords::TranscriptPos(0))
+                }
+                fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+                    Some(crate::coords::TranscriptPos(10))
+                }
+                fn reference_accession(&self) -> &str {
+                    "NC_0001.1"
+                }
+                fn exons(&self) -> &[ExonData] {
+                    &[]
+                }
+            }
+            Ok(Box::new(Mock))
+        }
+
+        fn get_seq(
+            &self,
+            _ac: &str,
+            start: i32,
+            end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            self.get_seq_calls.set(self.get_seq_calls.get() + 1);
+            let full = "ACGTACGTACGTACGTACGT";
+            let s = start as usize;
+            let e = if end == -1 { full.len() } else { end as usize };
+            Ok(full[s..e.min(full.len())].to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            Ok(vec![(IdentifierType::TranscriptAccession, "NM_0001.1".to_string())])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: crate::coords::TranscriptPos,
+            offset: crate::coords::IntronicOffset,
+        ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+            Ok(("NC_0001.1".to_string(), crate::coords::GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("hgvs-weaver-disk-cache-test-{name}-{:?}", std::thread::current().id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_get_transcript_is_served_from_disk_on_second_run() {
+        let dir = temp_dir("transcript");
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+
+        {
+            let cache = DiskCachingProvider::new(&inner, DiskCacheConfig::new(&dir)).unwrap();
+            let t = cache.get_transcript("NM_0001.1", None).unwrap();
+            assert_eq!(t.gene(), "MOCK");
+        }
+        assert_eq!(inner.get_transcript_calls.get(), 1);
+
+        // A fresh provider instance pointed at the same directory should hit
+        // the on-disk blob instead of calling inner again.
+        {
+            let cache = DiskCachingProvider::new(&inner, DiskCacheConfig::new(&dir)).unwrap();
+            let t = cache.get_transcript("NM_0001.1", None).unwrap();
+            assert_eq!(t.gene(), "MOCK");
+        }
+        assert_eq!(inner.get_transcript_calls.get(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_get_seq_is_served_from_disk_on_second_run() {
+        let dir = temp_dir("seq");
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+
+        {
+            let cache = DiskCachingProvider::new(&inner, DiskCacheConfig::new(&dir)).unwrap();
+            let seq = cache
+                .get_seq("NM_0001.1", 0, 4, IdentifierType::TranscriptAccession)
+                .unwrap();
+            assert_eq!(seq, "ACGT");
+        }
+        assert_eq!(inner.get_seq_calls.get(), 1);
+
+        {
+            let cache = DiskCachingProvider::new(&inner, DiskCacheConfig::new(&dir)).unwrap();
+            let seq = cache
+                .get_seq("NM_0001.1", 0, 4, IdentifierType::TranscriptAccession)
+                .unwrap();
+            assert_eq!(seq, "ACGT");
+        }
+        assert_eq!(inner.get_seq_calls.get(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_expired_ttl_entry_is_refetched() {
+        let dir = temp_dir("ttl");
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+        let mut config = DiskCacheConfig::new(&dir);
+        config.ttl = Some(Duration::from_secs(0));
+        let cache = DiskCachingProvider::new(&inner, config).unwrap();
+
+        cache
+            .get_seq("NM_0001.1", 0, 4, IdentifierType::TranscriptAccession)
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+        cache
+            .get_seq("NM_0001.1", 0, 4, IdentifierType::TranscriptAccession)
+            .unwrap();
+
+        assert_eq!(inner.get_seq_calls.get(), 2);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_warm_up_gene_fetches_every_transcript_for_symbol() {
+        let dir = temp_dir("warmup");
+        let inner = CountingProvider {
+            get_seq_calls: Cell::new(0),
+            get_transcript_calls: Cell::new(0),
+        };
+        let cache = DiskCachingProvider::new(&inner, DiskCacheConfig::new(&dir)).unwrap();
+
+        let warmed = cache.warm_up_gene("MOCK").unwrap();
+        assert_eq!(warmed, 1);
+        assert_eq!(inner.get_transcript_calls.get(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
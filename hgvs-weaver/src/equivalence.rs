@@ -1,10 +1,13 @@
-use crate::analogous_edit::{project_aa_variant, project_na_variant, SparseReference};
+use crate::analogous_edit::{
+    project_aa_variant, project_na_variant, ProjectedSequence, ResidueToken, SparseReference,
+};
 use crate::data::{DataProvider, IdentifierKind, TranscriptSearch};
 use crate::error::HgvsError;
 use crate::mapper::VariantMapper;
+use crate::sequence_cache::SequenceCache;
 use crate::structs::{
-    BaseOffsetInterval, BaseOffsetPosition, GVariant, GenomicPos, IntervalSpdi, IntronicOffset,
-    NaEdit, PVariant, SequenceVariant, SimpleInterval, SimplePosition, TranscriptPos, Variant,
+    GVariant, GenomicPos, IntervalSpdi, IntronicOffset, NaEdit, PVariant, SequenceVariant,
+    TranscriptPos, Variant,
 };
 use crate::utils::decompose_aa;
 
@@ -14,24 +17,170 @@ pub enum EquivalenceLevel {
     Identity,
     /// Biologically identical but different notation (e.g., ins vs dup).
     Analogous,
+    /// An alignment-based comparison found the two projections consistent
+    /// -- no conflicting residue anywhere -- but every agreeing position
+    /// was padding (`Unknown`/`Any`/`Wildcard`) on at least one side, so
+    /// nothing actually cross-checked the two descriptions against each
+    /// other. See [`VariantEquivalence::equivalent_report`]'s protein arm.
+    Weak,
     /// Definitively different edits/outcomes.
     Different,
     /// Missing data or unsupported variant type for comparison.
     Unknown,
+    /// Two cis-allele descriptions name the same set of component variants,
+    /// but in a different order or representation -- see
+    /// [`VariantEquivalence::allele_equivalent_level`].
+    AlleleReordered,
 }
 
 impl EquivalenceLevel {
     pub fn is_equivalent(&self) -> bool {
-        matches!(self, Self::Identity | Self::Analogous)
+        matches!(
+            self,
+            Self::Identity | Self::Analogous | Self::Weak | Self::AlleleReordered
+        )
     }
 }
 
+/// Which branch of [`VariantEquivalence::equivalent_report`]'s dispatch produced a
+/// report: a same-type projection arm, or the cross-type fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonArm {
+    Protein,
+    Coding,
+    NonCoding,
+    Rna,
+    /// Two different `SequenceVariant` kinds (e.g. `c.` vs `p.`), handled by
+    /// [`VariantEquivalence::are_equivalent_single`] rather than projection.
+    CrossType,
+}
+
+fn comparison_arm_for(var1: &SequenceVariant, var2: &SequenceVariant) -> ComparisonArm {
+    match (var1, var2) {
+        (SequenceVariant::Protein(_), SequenceVariant::Protein(_)) => ComparisonArm::Protein,
+        (SequenceVariant::Coding(_), SequenceVariant::Coding(_)) => ComparisonArm::Coding,
+        (SequenceVariant::NonCoding(_), SequenceVariant::NonCoding(_)) => ComparisonArm::NonCoding,
+        (SequenceVariant::Rna(_), SequenceVariant::Rna(_)) => ComparisonArm::Rna,
+        _ => ComparisonArm::CrossType,
+    }
+}
+
+/// Explains *why* [`VariantEquivalence::equivalent_report`] reached its verdict:
+/// which arm compared the pair, the merged projection window it compared them
+/// over, the rendered outcome on each side, and (when they diverge) the first
+/// position where the two projections disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquivalenceReport {
+    pub level: EquivalenceLevel,
+    pub arm: ComparisonArm,
+    /// `(min_pos, max_pos)` of the merged projection window, or `None` when
+    /// the verdict was reached without projecting (identity pre-check,
+    /// merge conflict, or the cross-type fallback).
+    pub window: Option<(i32, i32)>,
+    /// Human-readable rendering of each side's projected outcome over `window`.
+    pub projected1: Option<String>,
+    pub projected2: Option<String>,
+    /// Absolute position of the first token where the two projections
+    /// disagree, when `level` is not equivalent.
+    pub first_divergence: Option<i32>,
+    /// Set instead of projecting when merging the two variants' reference
+    /// data into one [`SparseReference`] hit a consistency conflict (e.g. the
+    /// same position implied a different reference base from each side).
+    pub merge_conflict: Option<String>,
+}
+
+fn render_projection(seq: &ProjectedSequence) -> String {
+    seq.0.iter().map(ResidueToken::unwrap_known).collect()
+}
+
+fn first_divergent_position(
+    min_pos: i32,
+    a: &ProjectedSequence,
+    b: &ProjectedSequence,
+) -> Option<i32> {
+    for (i, (ta, tb)) in a.0.iter().zip(b.0.iter()).enumerate() {
+        if ta.normalized_symbol() != tb.normalized_symbol() {
+            return Some(min_pos + i as i32);
+        }
+    }
+    if a.0.len() != b.0.len() {
+        return Some(min_pos + a.0.len().min(b.0.len()) as i32);
+    }
+    None
+}
+
 // Migrated to analogous_edit.rs
 
+/// Converts an `r.` edit's lowercase RNA-alphabet literals (`a/c/g/u`) to the
+/// uppercase DNA alphabet (`A/C/G/T`) the projection machinery and the
+/// merged sparse reference use, so `r.76a>u` projects the same way `c.76A>T`
+/// would.
+fn rna_edit_to_dna(edit: &NaEdit) -> NaEdit {
+    fn to_dna(s: &str) -> String {
+        s.to_uppercase().replace('U', "T")
+    }
+    match edit {
+        NaEdit::RefAlt { ref_, alt } => NaEdit::RefAlt {
+            ref_: ref_.as_deref().map(to_dna),
+            alt: alt.as_deref().map(to_dna),
+        },
+        NaEdit::Del { ref_, uncertain } => NaEdit::Del {
+            ref_: ref_.as_deref().map(to_dna),
+            uncertain: *uncertain,
+        },
+        NaEdit::Ins { alt, uncertain } => NaEdit::Ins {
+            alt: alt.as_deref().map(to_dna),
+            uncertain: *uncertain,
+        },
+        NaEdit::Dup { ref_, uncertain } => NaEdit::Dup {
+            ref_: ref_.as_deref().map(to_dna),
+            uncertain: *uncertain,
+        },
+        _ => edit.clone(),
+    }
+}
+
+/// Rewrites an `r.` variant into its `n.`-equivalent on the same transcript.
+///
+/// `RVariant` and `NVariant` share the same position type
+/// (`BaseOffsetInterval`) and numbering, so this is a pure relabeling (after
+/// normalizing the edit's RNA alphabet to DNA via [`rna_edit_to_dna`]) rather
+/// than a coordinate projection — it lets the cross-type identity matrix and
+/// `are_equivalent_single` reuse the existing `n.` arms instead of
+/// duplicating them for `r.`.
+fn rna_as_noncoding(vr: &crate::structs::RVariant) -> crate::structs::NVariant {
+    crate::structs::NVariant {
+        ac: vr.ac.clone(),
+        gene: vr.gene.clone(),
+        posedit: crate::structs::PosEdit {
+            pos: vr.posedit.pos.clone(),
+            edit: rna_edit_to_dna(&vr.posedit.edit),
+            uncertain: vr.posedit.uncertain,
+            predicted: vr.posedit.predicted,
+        },
+    }
+}
+
 pub struct VariantEquivalence<'a> {
     pub hdp: &'a dyn DataProvider,
     pub searcher: &'a dyn TranscriptSearch,
     pub mapper: VariantMapper<'a>,
+    /// Optional cache of `(ac, start, end, kind) -> sequence` fetches,
+    /// consulted by [`Self::get_ref_for_variant`] and [`Self::fill_na_edit`]
+    /// before calling `self.hdp.get_seq` directly. See [`SequenceCache`].
+    pub sequence_cache: Option<SequenceCache>,
+    /// Memoizes [`Self::is_cross_type_identity`] by the canonical string form
+    /// of each side, keyed independently of call order. Several arms of the
+    /// matrix delegate to another arm on a rewritten variant (e.g. `r.` is
+    /// rewritten to its `n.`-equivalent before delegating), so a pair that
+    /// was already resolved via one path is served from cache when a later
+    /// comparison reaches the same pair via a different path.
+    identity_cache: std::cell::RefCell<std::collections::HashMap<(String, String), bool>>,
+    /// Governs how a cross-checked `c.` to `p.` projection (see
+    /// [`Self::is_cross_type_identity`]) reacts to a stated reference that
+    /// doesn't match the transcript. Defaults to
+    /// [`crate::altseq::RefMismatchPolicy::Strict`].
+    pub ref_mismatch_policy: crate::altseq::RefMismatchPolicy,
 }
 
 impl<'a> VariantEquivalence<'a> {
@@ -40,6 +189,47 @@ impl<'a> VariantEquivalence<'a> {
             hdp,
             searcher,
             mapper: VariantMapper::new(hdp),
+            sequence_cache: None,
+            identity_cache: std::cell::RefCell::new(std::collections::HashMap::new()),
+            ref_mismatch_policy: crate::altseq::RefMismatchPolicy::Strict,
+        }
+    }
+
+    /// Attaches a [`SequenceCache`] so repeated `get_seq` windows are served
+    /// from memory (or a loaded CBOR snapshot) instead of `self.hdp`.
+    pub fn with_sequence_cache(mut self, cache: SequenceCache) -> Self {
+        self.sequence_cache = Some(cache);
+        self
+    }
+
+    /// Sets [`Self::ref_mismatch_policy`] to tolerate a stated reference
+    /// that doesn't match the transcript instead of failing the comparison.
+    pub fn with_ref_mismatch_policy(mut self, policy: crate::altseq::RefMismatchPolicy) -> Self {
+        self.ref_mismatch_policy = policy;
+        self
+    }
+
+    /// Builds a `VariantEquivalence` backed by a [`CachingDataProvider`],
+    /// so repeated `get_transcript`/`get_seq` calls across a batch of
+    /// comparisons against the same accessions are served from memory
+    /// instead of re-hitting the wrapped provider.
+    pub fn with_cache(
+        cache: &'a crate::caching_provider::CachingDataProvider<'a, dyn DataProvider + 'a>,
+        searcher: &'a dyn TranscriptSearch,
+    ) -> Self {
+        Self::new(cache, searcher)
+    }
+
+    fn get_seq_cached(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        kind: crate::data::IdentifierType,
+    ) -> Result<String, HgvsError> {
+        match &self.sequence_cache {
+            Some(cache) => cache.get_or_fetch(ac, start, end, kind, || self.hdp.get_seq(ac, start, end, kind)),
+            None => self.hdp.get_seq(ac, start, end, kind),
         }
     }
 
@@ -76,17 +266,217 @@ impl<'a> VariantEquivalence<'a> {
         var1: &SequenceVariant,
         var2: &SequenceVariant,
     ) -> Result<EquivalenceLevel, HgvsError> {
+        Ok(self.build_report(var1, var2)?.level)
+    }
+
+    /// Compares two cis-allele descriptions -- each an ordered list of
+    /// component variants on the same sequence, as produced by
+    /// [`crate::parser::parse_allele`] -- as unordered sets.
+    ///
+    /// Finds a one-to-one pairing between `alleles1` and `alleles2` where
+    /// every pair is equivalent via [`Self::equivalent_level`] (greedily,
+    /// since allele component counts are small); if no such pairing exists,
+    /// or the sets differ in size, returns [`EquivalenceLevel::Different`].
+    /// If every paired component is [`EquivalenceLevel::Identity`] *and* the
+    /// pairing keeps each component at its original index, returns
+    /// `Identity`; otherwise returns [`EquivalenceLevel::AlleleReordered`],
+    /// since the alleles name the same change set but in a different order
+    /// or per-component representation.
+    pub fn allele_equivalent_level(
+        &self,
+        alleles1: &[SequenceVariant],
+        alleles2: &[SequenceVariant],
+    ) -> Result<EquivalenceLevel, HgvsError> {
+        if alleles1.len() != alleles2.len() {
+            return Ok(EquivalenceLevel::Different);
+        }
+
+        let mut used = vec![false; alleles2.len()];
+        let mut pairing = vec![0usize; alleles1.len()];
+        let mut all_identity_in_place = true;
+
+        for (i, v1) in alleles1.iter().enumerate() {
+            let mut matched = None;
+            for (j, v2) in alleles2.iter().enumerate() {
+                if used[j] {
+                    continue;
+                }
+                let lvl = self.equivalent_level(v1, v2)?;
+                if lvl.is_equivalent() {
+                    matched = Some((j, lvl));
+                    break;
+                }
+            }
+            let Some((j, lvl)) = matched else {
+                return Ok(EquivalenceLevel::Different);
+            };
+            used[j] = true;
+            pairing[i] = j;
+            if j != i || lvl != EquivalenceLevel::Identity {
+                all_identity_in_place = false;
+            }
+        }
+
+        if all_identity_in_place {
+            Ok(EquivalenceLevel::Identity)
+        } else {
+            Ok(EquivalenceLevel::AlleleReordered)
+        }
+    }
+
+    /// Partitions `variants` into equivalence classes, returning each class
+    /// as a list of indices into `variants`.
+    ///
+    /// Comparing every pair with `are_equivalent_single` is O(n^2), and each
+    /// call can itself involve projection/translation. Most of that is
+    /// avoidable: nucleotide variants (`g.`/`c.`/`n.`/`r.`) describing the
+    /// same change converge to an identical string via
+    /// [`Self::canonical_genomic_key`], so they can be grouped with a hash
+    /// map in a single pass and never need a pairwise check against each
+    /// other. Only variants without a canonical key -- protein, or a
+    /// nucleotide variant whose projection to `g.` failed -- fall back to
+    /// `are_equivalent_single` against one representative of each
+    /// already-formed class.
+    pub fn cluster_equivalent(
+        &self,
+        variants: &[SequenceVariant],
+    ) -> Result<Vec<Vec<usize>>, HgvsError> {
+        let mut keyed: std::collections::HashMap<String, Vec<usize>> =
+            std::collections::HashMap::new();
+        let mut unkeyed = Vec::new();
+
+        for (i, var) in variants.iter().enumerate() {
+            match self.canonical_genomic_key(var) {
+                Some(key) => keyed.entry(key).or_default().push(i),
+                None => unkeyed.push(i),
+            }
+        }
+
+        let mut classes: Vec<Vec<usize>> = keyed.into_values().collect();
+
+        for idx in unkeyed {
+            let mut placed = false;
+            for class in classes.iter_mut() {
+                if self.are_equivalent_single(&variants[idx], &variants[class[0]])? {
+                    class.push(idx);
+                    placed = true;
+                    break;
+                }
+            }
+            if !placed {
+                classes.push(vec![idx]);
+            }
+        }
+
+        Ok(classes)
+    }
+
+    /// Computes a canonical genomic-projection string for nucleotide
+    /// variants, reusing the same normalize -> fill-implicit-sequence ->
+    /// ins-to-dup -> format pipeline [`Self::n_vs_n_equivalent`] uses to
+    /// compare a pair. Returns `None` for protein variants (no single `g.`
+    /// projection exists -- several codings can encode the same change) and
+    /// for mitochondrial variants (not wired through the `g.`/`c.`/`n.`
+    /// pipeline above), as well as on any mapping failure; all of these are
+    /// left for [`Self::cluster_equivalent`]'s pairwise fallback.
+    fn canonical_genomic_key(&self, var: &SequenceVariant) -> Option<String> {
+        let vg = match var {
+            SequenceVariant::Genomic(vg) => vg.clone(),
+            SequenceVariant::Coding(vc) => {
+                let tx = self.hdp.get_transcript(&vc.ac, None).ok()?;
+                self.mapper
+                    .c_to_g(vc, Some(tx.reference_accession()))
+                    .ok()?
+            }
+            SequenceVariant::NonCoding(vn) => {
+                let tx = self.hdp.get_transcript(&vn.ac, None).ok()?;
+                self.mapper
+                    .n_to_g(vn, Some(tx.reference_accession()))
+                    .ok()?
+            }
+            SequenceVariant::Rna(vr) => {
+                let vn = rna_as_noncoding(vr);
+                let tx = self.hdp.get_transcript(&vn.ac, None).ok()?;
+                self.mapper
+                    .n_to_g(&vn, Some(tx.reference_accession()))
+                    .ok()?
+            }
+            SequenceVariant::Protein(_) | SequenceVariant::Mitochondrial(_) => return None,
+        };
+
+        let nv = self
+            .mapper
+            .normalize_variant(SequenceVariant::Genomic(vg))
+            .ok()?;
+        let nv_filled = self.fill_implicit_sequence(&nv).ok()?;
+        let nv_dup = self.normalize_ins_to_dup(&nv_filled).ok()?;
+        Some(self.normalize_format(&nv_dup.to_string()))
+    }
+
+    /// Like [`Self::equivalent_level`], but returns an [`EquivalenceReport`]
+    /// explaining the verdict instead of a bare [`EquivalenceLevel`] — which
+    /// comparison arm ran, the merged projection window, both rendered
+    /// outcomes, and (for `Different`) the first position they disagree on
+    /// or the reference-merge conflict that pre-empted projection entirely.
+    pub fn equivalent_report(
+        &self,
+        var1: &SequenceVariant,
+        var2: &SequenceVariant,
+    ) -> Result<EquivalenceReport, HgvsError> {
+        let vars1 = self.expand_if_gene_symbol(var1)?;
+        let vars2 = self.expand_if_gene_symbol(var2)?;
+
+        let mut last_report = None;
+        for v1 in &vars1 {
+            for v2 in &vars2 {
+                let report = self.build_report(v1, v2)?;
+                if report.level.is_equivalent() {
+                    return Ok(report);
+                }
+                last_report = Some(report);
+            }
+        }
+        Ok(last_report.unwrap_or(EquivalenceReport {
+            level: EquivalenceLevel::Unknown,
+            arm: ComparisonArm::CrossType,
+            window: None,
+            projected1: None,
+            projected2: None,
+            first_divergence: None,
+            merge_conflict: None,
+        }))
+    }
+
+    fn build_report(
+        &self,
+        var1: &SequenceVariant,
+        var2: &SequenceVariant,
+    ) -> Result<EquivalenceReport, HgvsError> {
+        let arm = comparison_arm_for(var1, var2);
+        let no_projection = |level: EquivalenceLevel, merge_conflict: Option<String>| {
+            EquivalenceReport {
+                level,
+                arm,
+                window: None,
+                projected1: None,
+                projected2: None,
+                first_divergence: None,
+                merge_conflict,
+            }
+        };
+
         // 1. Strict Check (after normalization)
         if self.normalize_format(&var1.to_string()) == self.normalize_format(&var2.to_string()) {
-            return Ok(EquivalenceLevel::Identity);
+            return Ok(no_projection(EquivalenceLevel::Identity, None));
         }
 
         // 2. Build and Merge Sparse References
         let s1 = self.get_ref_for_variant(var1);
         let s2 = self.get_ref_for_variant(var2);
         let mut merged = s1;
-        if let Err(_) = merged.merge(&s2) {
-            return Ok(EquivalenceLevel::Different); // Inconsistent references
+        if let Err(e) = merged.merge(&s2) {
+            // Inconsistent references: surface *why* instead of a bare Different.
+            return Ok(no_projection(EquivalenceLevel::Different, Some(e.to_string())));
         }
 
         // 3. Project and Compare Outcomes
@@ -118,10 +508,12 @@ impl<'a> VariantEquivalence<'a> {
                     }
                     _ => {
                         // Fallback to cross-type comparison logic which handles non-projected cases
-                        if self.are_equivalent_single(var1, var2)? {
-                            return Ok(EquivalenceLevel::Analogous);
-                        }
-                        return Ok(EquivalenceLevel::Different);
+                        let level = if self.are_equivalent_single(var1, var2)? {
+                            EquivalenceLevel::Analogous
+                        } else {
+                            EquivalenceLevel::Different
+                        };
+                        return Ok(no_projection(level, None));
                     }
                 };
 
@@ -133,11 +525,39 @@ impl<'a> VariantEquivalence<'a> {
                 let res2 = project_aa_variant(edit2, start2, end2, min_pos, max_pos, &merged)
                     .trim_at_stop();
 
-                let is_analogous = res1.is_analogous_to(&res2);
+                // Align rather than compare position-by-position: the two
+                // projections were built over the same shared window, but
+                // an alignment (allowing `Unknown`/`Any`/`Wildcard` to match
+                // anything) still recognizes an insertion and a duplication
+                // of the same residues as equivalent even when the edit each
+                // one seeds lands at a different offset within that window.
+                let alignment = res1.is_equivalent_to(&res2);
+                let is_analogous = alignment.equivalent;
+                let first_divergence = if is_analogous {
+                    None
+                } else {
+                    first_divergent_position(min_pos, &res1, &res2)
+                };
 
-                if is_analogous {
-                    return Ok(EquivalenceLevel::Analogous);
-                }
+                return Ok(EquivalenceReport {
+                    level: if !alignment.equivalent {
+                        EquivalenceLevel::Different
+                    } else if alignment.confirmed {
+                        EquivalenceLevel::Analogous
+                    } else {
+                        // Consistent, but every agreeing position was
+                        // `Unknown`/`Any`/`Wildcard` padding on at least one
+                        // side -- nothing actually cross-checked the two
+                        // descriptions against each other.
+                        EquivalenceLevel::Weak
+                    },
+                    arm,
+                    window: Some((min_pos, max_pos)),
+                    projected1: Some(render_projection(&res1)),
+                    projected2: Some(render_projection(&res2)),
+                    first_divergence,
+                    merge_conflict: None,
+                });
             }
             (SequenceVariant::Coding(c1), SequenceVariant::Coding(c2)) => {
                 if let (Some(pos1), Some(pos2)) = (&c1.posedit.pos, &c2.posedit.pos) {
@@ -202,9 +622,7 @@ impl<'a> VariantEquivalence<'a> {
                     let res2 =
                         project_na_variant(&edit2, start2, end2 - 1, min_pos, max_pos - 1, &merged);
 
-                    if res1.is_analogous_to(&res2) {
-                        return Ok(EquivalenceLevel::Analogous);
-                    }
+                    return Ok(self.na_arm_report(arm, min_pos, max_pos, res1, res2));
                 }
             }
             (SequenceVariant::NonCoding(n1), SequenceVariant::NonCoding(n2)) => {
@@ -270,30 +688,160 @@ impl<'a> VariantEquivalence<'a> {
                     let res2 =
                         project_na_variant(&edit2, start2, end2 - 1, min_pos, max_pos - 1, &merged);
 
-                    if res1.is_analogous_to(&res2) {
-                        return Ok(EquivalenceLevel::Analogous);
+                    return Ok(self.na_arm_report(arm, min_pos, max_pos, res1, res2));
+                }
+            }
+            (SequenceVariant::Rna(r1), SequenceVariant::Rna(r2)) => {
+                if let (Some(pos1), Some(pos2)) = (&r1.posedit.pos, &r2.posedit.pos) {
+                    let mut i1 = pos1.spdi_interval(&r1.ac, self.hdp)?;
+                    let mut i2 = pos2.spdi_interval(&r2.ac, self.hdp)?;
+
+                    // `r.` literals use lowercase RNA bases (u instead of t);
+                    // normalize to the uppercase DNA alphabet the rest of the
+                    // projection machinery (and the merged reference) uses
+                    // before reverse-complementing or projecting.
+                    let dna_edit1 = rna_edit_to_dna(&r1.posedit.edit);
+                    let dna_edit2 = rna_edit_to_dna(&r2.posedit.edit);
+
+                    let t1 = self.hdp.get_transcript(&r1.ac, None)?;
+                    let edit1 = if t1.strand() == -1 {
+                        dna_edit1.reverse_complement()
+                    } else {
+                        dna_edit1
+                    };
+
+                    let t2 = self.hdp.get_transcript(&r2.ac, None)?;
+                    let edit2 = if t2.strand() == -1 {
+                        dna_edit2.reverse_complement()
+                    } else {
+                        dna_edit2
+                    };
+
+                    if matches!(r1.posedit.edit, NaEdit::Ins { .. }) {
+                        if let Some(e) = &pos1.end {
+                            let g1 = self.hdp.c_to_g(
+                                &r1.ac,
+                                pos1.start.base.to_index(),
+                                pos1.start.offset.unwrap_or(IntronicOffset(0)),
+                            )?;
+                            let g2 = self.hdp.c_to_g(
+                                &r1.ac,
+                                e.base.to_index(),
+                                e.offset.unwrap_or(IntronicOffset(0)),
+                            )?;
+                            let p = g1.1 .0.min(g2.1 .0);
+                            i1 = (p, p + 1, g1.0);
+                        }
                     }
+                    if matches!(r2.posedit.edit, NaEdit::Ins { .. }) {
+                        if let Some(e) = &pos2.end {
+                            let g1 = self.hdp.c_to_g(
+                                &r2.ac,
+                                pos2.start.base.to_index(),
+                                pos2.start.offset.unwrap_or(IntronicOffset(0)),
+                            )?;
+                            let g2 = self.hdp.c_to_g(
+                                &r2.ac,
+                                e.base.to_index(),
+                                e.offset.unwrap_or(IntronicOffset(0)),
+                            )?;
+                            let p = g1.1 .0.min(g2.1 .0);
+                            i2 = (p, p + 1, g1.0);
+                        }
+                    }
+
+                    let (start1, end1, _) = i1;
+                    let (start2, end2, _) = i2;
+
+                    let min_pos = start1.min(start2).saturating_sub(2);
+                    let max_pos = end1.max(end2) + 2;
+
+                    let res1 =
+                        project_na_variant(&edit1, start1, end1 - 1, min_pos, max_pos - 1, &merged);
+                    let res2 =
+                        project_na_variant(&edit2, start2, end2 - 1, min_pos, max_pos - 1, &merged);
+
+                    return Ok(self.na_arm_report(arm, min_pos, max_pos, res1, res2));
                 }
             }
             _ => {
                 // Fallback to existing logic for cross-type comparison
                 if self.are_equivalent_single(var1, var2)? {
-                    if self.is_cross_type_identity(var1, var2) {
-                        return Ok(EquivalenceLevel::Identity);
-                    }
-                    return Ok(EquivalenceLevel::Analogous);
+                    let level = if self.is_cross_type_identity(var1, var2) {
+                        EquivalenceLevel::Identity
+                    } else {
+                        EquivalenceLevel::Analogous
+                    };
+                    return Ok(no_projection(level, None));
                 }
             }
         }
 
-        Ok(EquivalenceLevel::Different)
+        Ok(no_projection(EquivalenceLevel::Different, None))
+    }
+
+    /// Builds the shared `Analogous`/`Different` report tail for the
+    /// nucleic-acid projection arms (`Coding`, `NonCoding`, `Rna`), which all
+    /// compare via [`project_na_variant`] over the same merged window.
+    fn na_arm_report(
+        &self,
+        arm: ComparisonArm,
+        min_pos: i32,
+        max_pos: i32,
+        res1: ProjectedSequence,
+        res2: ProjectedSequence,
+    ) -> EquivalenceReport {
+        let is_analogous = res1.is_analogous_to(&res2);
+        let first_divergence = if is_analogous {
+            None
+        } else {
+            first_divergent_position(min_pos, &res1, &res2)
+        };
+        EquivalenceReport {
+            level: if is_analogous {
+                EquivalenceLevel::Analogous
+            } else {
+                EquivalenceLevel::Different
+            },
+            arm,
+            window: Some((min_pos, max_pos)),
+            projected1: Some(render_projection(&res1)),
+            projected2: Some(render_projection(&res2)),
+            first_divergence,
+            merge_conflict: None,
+        }
     }
 
+    /// Whether `var1` and `var2` describe the same underlying change across
+    /// a type boundary closely enough to report `Identity` rather than just
+    /// `Analogous` (the caller has already confirmed `are_equivalent_single`).
+    ///
+    /// Memoized in [`Self::identity_cache`] keyed by the pair's canonical
+    /// string form (order-independent), since several arms delegate to
+    /// another arm on a rewritten variant and can otherwise recompute the
+    /// same underlying comparison multiple times within one batch.
     fn is_cross_type_identity(&self, var1: &SequenceVariant, var2: &SequenceVariant) -> bool {
+        let (a, b) = (var1.to_string(), var2.to_string());
+        let key = if a <= b { (a, b) } else { (b, a) };
+        if let Some(cached) = self.identity_cache.borrow().get(&key) {
+            return *cached;
+        }
+        let result = self.is_cross_type_identity_uncached(var1, var2);
+        self.identity_cache.borrow_mut().insert(key, result);
+        result
+    }
+
+    fn is_cross_type_identity_uncached(
+        &self,
+        var1: &SequenceVariant,
+        var2: &SequenceVariant,
+    ) -> bool {
         match (var1, var2) {
             (SequenceVariant::Coding(vc), SequenceVariant::Protein(vp))
             | (SequenceVariant::Protein(vp), SequenceVariant::Coding(vc)) => {
-                if let Ok(vp_generated) = self.mapper.c_to_p(vc, Some(&vp.ac)) {
+                if let Ok(vp_generated) =
+                    self.mapper.c_to_p_with_ref_policy(vc, Some(&vp.ac), self.ref_mismatch_policy)
+                {
                     vp_generated.to_string() == vp.to_string()
                 } else {
                     false
@@ -325,6 +873,21 @@ impl<'a> VariantEquivalence<'a> {
                     false
                 }
             }
+            (SequenceVariant::Genomic(vg), SequenceVariant::Protein(vp))
+            | (SequenceVariant::Protein(vp), SequenceVariant::Genomic(vg)) => {
+                if let Ok(c_variants) = self.mapper.g_to_c_all(vg, self.searcher) {
+                    for vc in c_variants {
+                        if let Ok(vp_generated) =
+                            self.mapper.c_to_p_with_ref_policy(&vc, Some(&vp.ac), self.ref_mismatch_policy)
+                        {
+                            if vp_generated.to_string() == vp.to_string() {
+                                return true;
+                            }
+                        }
+                    }
+                }
+                false
+            }
             (SequenceVariant::NonCoding(vn), SequenceVariant::Protein(vp))
             | (SequenceVariant::Protein(vp), SequenceVariant::NonCoding(vn)) => {
                 if let Ok(tx) = self.hdp.get_transcript(&vn.ac, None) {
@@ -333,7 +896,11 @@ impl<'a> VariantEquivalence<'a> {
                         if let Ok(c_variants) = self.mapper.g_to_c_all(&vg_generated, self.searcher)
                         {
                             for vc in c_variants {
-                                if let Ok(vp_generated) = self.mapper.c_to_p(&vc, Some(&vp.ac)) {
+                                if let Ok(vp_generated) = self.mapper.c_to_p_with_ref_policy(
+                                    &vc,
+                                    Some(&vp.ac),
+                                    self.ref_mismatch_policy,
+                                ) {
                                     if vp_generated.to_string() == vp.to_string() {
                                         return true;
                                     }
@@ -344,6 +911,19 @@ impl<'a> VariantEquivalence<'a> {
                 }
                 false
             }
+            // RNA is numbered identically to NonCoding, so rewrite and
+            // delegate rather than duplicating every arm above for `r.`.
+            (SequenceVariant::NonCoding(vn), SequenceVariant::Rna(vr))
+            | (SequenceVariant::Rna(vr), SequenceVariant::NonCoding(vn)) => {
+                let vr_as_n = rna_as_noncoding(vr);
+                vn.ac == vr_as_n.ac
+                    && self.normalize_format(&SequenceVariant::NonCoding(vn.clone()).to_string())
+                        == self
+                            .normalize_format(&SequenceVariant::NonCoding(vr_as_n).to_string())
+            }
+            (SequenceVariant::Rna(vr), other) | (other, SequenceVariant::Rna(vr)) => {
+                self.is_cross_type_identity(&SequenceVariant::NonCoding(rna_as_noncoding(vr)), other)
+            }
             _ => false,
         }
     }
@@ -366,10 +946,12 @@ impl<'a> VariantEquivalence<'a> {
         let mut s = SparseReference::new();
         match var {
             SequenceVariant::Protein(vp) => {
-                if let Ok(seq) =
-                    self.hdp
-                        .get_seq(&vp.ac, 0, -1, crate::data::IdentifierType::ProteinAccession)
-                {
+                if let Ok(seq) = self.get_seq_cached(
+                    &vp.ac,
+                    0,
+                    -1,
+                    crate::data::IdentifierType::ProteinAccession,
+                ) {
                     if let Ok(aas) = decompose_aa(&seq) {
                         for (i, aa) in aas.iter().enumerate() {
                             let _ = s.set(i as i32, aa.to_string());
@@ -380,7 +962,7 @@ impl<'a> VariantEquivalence<'a> {
             SequenceVariant::Coding(vc) => {
                 if let Some(pos) = &vc.posedit.pos {
                     if let Ok((start, end, spdi_ac)) = pos.spdi_interval(&vc.ac, self.hdp) {
-                        if let Ok(seq) = self.hdp.get_seq(
+                        if let Ok(seq) = self.get_seq_cached(
                             &spdi_ac,
                             start,
                             end,
@@ -509,9 +1091,12 @@ impl<'a> VariantEquivalence<'a> {
                 ref_: None,
                 uncertain,
             } => {
-                let seq =
-                    self.hdp
-                        .get_seq(ac, start as i32, end as i32, kind.into_identifier_type())?;
+                let seq = self.get_seq_cached(
+                    ac,
+                    start as i32,
+                    end as i32,
+                    kind.into_identifier_type(),
+                )?;
                 Ok(crate::edits::NaEdit::Del {
                     ref_: Some(seq),
                     uncertain,
@@ -521,9 +1106,12 @@ impl<'a> VariantEquivalence<'a> {
                 ref_: None,
                 uncertain,
             } => {
-                let seq =
-                    self.hdp
-                        .get_seq(ac, start as i32, end as i32, kind.into_identifier_type())?;
+                let seq = self.get_seq_cached(
+                    ac,
+                    start as i32,
+                    end as i32,
+                    kind.into_identifier_type(),
+                )?;
                 Ok(crate::edits::NaEdit::Dup {
                     ref_: Some(seq),
                     uncertain,
@@ -664,6 +1252,33 @@ impl<'a> VariantEquivalence<'a> {
                 self.n_vs_p_equivalent(v2, v1)
             }
 
+            // RNA vs everything else: rewrite to the `n.`-equivalent and
+            // reuse the matching NonCoding arm.
+            (SequenceVariant::NonCoding(v1), SequenceVariant::Rna(v2)) => {
+                self.n_vs_n_equivalent_n(v1, &rna_as_noncoding(v2))
+            }
+            (SequenceVariant::Rna(v1), SequenceVariant::NonCoding(v2)) => {
+                self.n_vs_n_equivalent_n(&rna_as_noncoding(v1), v2)
+            }
+            (SequenceVariant::Genomic(v1), SequenceVariant::Rna(v2)) => {
+                self.g_vs_n_equivalent(v1, &rna_as_noncoding(v2))
+            }
+            (SequenceVariant::Rna(v1), SequenceVariant::Genomic(v2)) => {
+                self.g_vs_n_equivalent(v2, &rna_as_noncoding(v1))
+            }
+            (SequenceVariant::Coding(v1), SequenceVariant::Rna(v2)) => {
+                self.c_vs_n_equivalent(v1, &rna_as_noncoding(v2))
+            }
+            (SequenceVariant::Rna(v1), SequenceVariant::Coding(v2)) => {
+                self.c_vs_n_equivalent(v2, &rna_as_noncoding(v1))
+            }
+            (SequenceVariant::Protein(v1), SequenceVariant::Rna(v2)) => {
+                self.n_vs_p_equivalent(&rna_as_noncoding(v2), v1)
+            }
+            (SequenceVariant::Rna(v1), SequenceVariant::Protein(v2)) => {
+                self.n_vs_p_equivalent(&rna_as_noncoding(v1), v2)
+            }
+
             // Protein vs Protein
             (SequenceVariant::Protein(v1), SequenceVariant::Protein(v2)) => {
                 self.p_vs_p_equivalent(v1, v2)
@@ -737,188 +1352,15 @@ impl<'a> VariantEquivalence<'a> {
         Ok(s1 == s2)
     }
 
+    /// Rewrites an insertion whose inserted sequence duplicates the
+    /// immediately preceding reference bases into `dup` notation.
+    ///
+    /// This is the same rewrite `VariantMapper::normalize_variant` applies
+    /// after 3'-shifting; it's kept here too so that equivalence checks see
+    /// `ins`/`dup` as the same variant even for inputs that were never run
+    /// through the mapper's normalizer.
     fn normalize_ins_to_dup(&self, var: &SequenceVariant) -> Result<SequenceVariant, HgvsError> {
-        match var {
-            SequenceVariant::Genomic(v) => {
-                if let Some(pos) = &v.posedit.pos {
-                    if let NaEdit::Ins {
-                        alt: Some(seq),
-                        uncertain,
-                    } = &v.posedit.edit
-                    {
-                        let start_0 = pos.start.base.to_index();
-                        if let Some((check_start, start_idx, edit)) = self.try_normalize_to_dup(
-                            &v.ac,
-                            IdentifierKind::Genomic,
-                            start_0.0,
-                            seq,
-                            *uncertain,
-                        )? {
-                            let mut new_v = v.clone();
-                            new_v.posedit.pos = Some(SimpleInterval {
-                                start: SimplePosition {
-                                    base: GenomicPos(check_start).to_hgvs(),
-                                    end: None,
-                                    uncertain: false,
-                                },
-                                end: if check_start != start_idx {
-                                    Some(SimplePosition {
-                                        base: GenomicPos(start_idx).to_hgvs(),
-                                        end: None,
-                                        uncertain: false,
-                                    })
-                                } else {
-                                    None
-                                },
-                                uncertain: false,
-                            });
-                            new_v.posedit.edit = edit;
-                            return Ok(SequenceVariant::Genomic(new_v));
-                        }
-                    }
-                }
-                Ok(var.clone())
-            }
-            SequenceVariant::Coding(v) => {
-                if let Some(pos) = &v.posedit.pos {
-                    if let NaEdit::Ins {
-                        alt: Some(seq),
-                        uncertain,
-                    } = &v.posedit.edit
-                    {
-                        if pos.start.offset.is_some()
-                            || pos.end.as_ref().map_or(false, |e| e.offset.is_some())
-                        {
-                            return Ok(var.clone());
-                        }
-                        let transcript = self.hdp.get_transcript(&v.ac, None)?;
-                        let (start_idx_usize, _) = self.mapper.get_c_indices(pos, &transcript)?;
-                        let start_idx = start_idx_usize as i32;
-
-                        if let Some((check_start, last_idx, edit)) = self.try_normalize_to_dup(
-                            &v.ac,
-                            IdentifierKind::Transcript,
-                            start_idx,
-                            seq,
-                            *uncertain,
-                        )? {
-                            let mut new_v = v.clone();
-                            let am = crate::transcript_mapper::TranscriptMapper::new(transcript)?;
-                            let (c_pos_index, _, anchor) = am.n_to_c(TranscriptPos(check_start))?;
-                            new_v.posedit.pos = Some(BaseOffsetInterval {
-                                start: BaseOffsetPosition {
-                                    base: c_pos_index.to_hgvs(),
-                                    offset: None,
-                                    anchor,
-                                    uncertain: false,
-                                },
-                                end: if check_start != last_idx {
-                                    let (c_pos_e_index, _, anchor_e) =
-                                        am.n_to_c(TranscriptPos(last_idx))?;
-                                    Some(BaseOffsetPosition {
-                                        base: c_pos_e_index.to_hgvs(),
-                                        offset: None,
-                                        anchor: anchor_e,
-                                        uncertain: false,
-                                    })
-                                } else {
-                                    None
-                                },
-                                uncertain: false,
-                            });
-                            new_v.posedit.edit = edit;
-                            return Ok(SequenceVariant::Coding(new_v));
-                        }
-                    }
-                }
-                Ok(var.clone())
-            }
-            SequenceVariant::NonCoding(v) => {
-                if let Some(pos) = &v.posedit.pos {
-                    if let NaEdit::Ins {
-                        alt: Some(seq),
-                        uncertain,
-                    } = &v.posedit.edit
-                    {
-                        if pos.start.offset.is_some()
-                            || pos.end.as_ref().map_or(false, |e| e.offset.is_some())
-                        {
-                            return Ok(var.clone());
-                        }
-                        let transcript = self.hdp.get_transcript(&v.ac, None)?;
-                        let (start_idx_usize, _) = self.mapper.get_n_indices(pos, &transcript)?;
-                        let start_idx = start_idx_usize as i32;
-
-                        if let Some((check_start, last_idx, edit)) = self.try_normalize_to_dup(
-                            &v.ac,
-                            IdentifierKind::Transcript,
-                            start_idx,
-                            seq,
-                            *uncertain,
-                        )? {
-                            let mut new_v = v.clone();
-                            let am = crate::transcript_mapper::TranscriptMapper::new(transcript)?;
-                            let (c_pos_index, _, anchor) = am.n_to_c(TranscriptPos(check_start))?;
-                            new_v.posedit.pos = Some(BaseOffsetInterval {
-                                start: BaseOffsetPosition {
-                                    base: c_pos_index.to_hgvs(),
-                                    offset: None,
-                                    anchor,
-                                    uncertain: false,
-                                },
-                                end: if check_start != last_idx {
-                                    let (c_pos_e_index, _, anchor_e) =
-                                        am.n_to_c(TranscriptPos(last_idx))?;
-                                    Some(BaseOffsetPosition {
-                                        base: c_pos_e_index.to_hgvs(),
-                                        offset: None,
-                                        anchor: anchor_e,
-                                        uncertain: false,
-                                    })
-                                } else {
-                                    None
-                                },
-                                uncertain: false,
-                            });
-                            new_v.posedit.edit = edit;
-                            return Ok(SequenceVariant::NonCoding(new_v));
-                        }
-                    }
-                }
-                Ok(var.clone())
-            }
-            _ => Ok(var.clone()),
-        }
-    }
-
-    fn try_normalize_to_dup(
-        &self,
-        ac: &str,
-        kind: IdentifierKind,
-        start_idx: i32,
-        seq: &str,
-        uncertain: bool,
-    ) -> Result<Option<(i32, i32, NaEdit)>, HgvsError> {
-        let len = seq.len() as i32;
-        let check_start = start_idx - len + 1;
-        if check_start < 0 {
-            return Ok(None);
-        }
-        let ref_seq =
-            self.hdp
-                .get_seq(ac, check_start, start_idx + 1, kind.into_identifier_type())?;
-        if ref_seq == *seq {
-            Ok(Some((
-                check_start,
-                start_idx,
-                NaEdit::Dup {
-                    ref_: Some(seq.to_string()),
-                    uncertain,
-                },
-            )))
-        } else {
-            Ok(None)
-        }
+        self.mapper.normalize_ins_to_dup(var)
     }
 
     fn n_vs_n_equivalent_c(
@@ -1005,7 +1447,9 @@ impl<'a> VariantEquivalence<'a> {
         vc: &crate::structs::CVariant,
         vp: &crate::structs::PVariant,
     ) -> Result<bool, HgvsError> {
-        let vp_generated = self.mapper.c_to_p(vc, Some(&vp.ac))?;
+        let vp_generated = self
+            .mapper
+            .c_to_p_with_ref_policy(vc, Some(&vp.ac), self.ref_mismatch_policy)?;
         Ok(self.normalize_format(&vp_generated.to_string())
             == self.normalize_format(&vp.to_string()))
     }
@@ -1165,4 +1609,288 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_equivalent_report_explains_coding_ins_dup_analogy() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        // Same duplicated base, spelled as an insertion on one side and a
+        // dup on the other; the identity pre-check can't catch this (the
+        // strings differ), so the report should come from the Coding
+        // projection arm.
+        let var1 = crate::parse_hgvs_variant("NM_000123.4:c.2_3insC")?;
+        let var2 = crate::parse_hgvs_variant("NM_000123.4:c.2dupC")?;
+
+        let report = eq.equivalent_report(&var1, &var2)?;
+        assert_eq!(report.level, EquivalenceLevel::Analogous);
+        assert_eq!(report.arm, ComparisonArm::Coding);
+        assert!(report.window.is_some());
+        assert_eq!(report.projected1, report.projected2);
+        assert!(report.first_divergence.is_none());
+        assert!(report.merge_conflict.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_equivalent_report_locates_first_divergence_for_different_edits() -> Result<(), HgvsError>
+    {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        // Same position, different inserted base: genuinely different outcomes.
+        let var1 = crate::parse_hgvs_variant("NM_000123.4:c.2_3insC")?;
+        let var2 = crate::parse_hgvs_variant("NM_000123.4:c.2_3insG")?;
+
+        let report = eq.equivalent_report(&var1, &var2)?;
+        assert_eq!(report.level, EquivalenceLevel::Different);
+        assert_eq!(report.arm, ComparisonArm::Coding);
+        assert!(report.first_divergence.is_some());
+        assert_ne!(report.projected1, report.projected2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rna_edit_to_dna_normalizes_lowercase_rna_alphabet() {
+        let subst = NaEdit::RefAlt {
+            ref_: Some("a".to_string()),
+            alt: Some("u".to_string()),
+        };
+        assert_eq!(
+            rna_edit_to_dna(&subst),
+            NaEdit::RefAlt {
+                ref_: Some("A".to_string()),
+                alt: Some("T".to_string()),
+            }
+        );
+
+        let ins = NaEdit::Ins {
+            alt: Some("gu".to_string()),
+            uncertain: false,
+        };
+        assert_eq!(
+            rna_edit_to_dna(&ins),
+            NaEdit::Ins {
+                alt: Some("GT".to_string()),
+                uncertain: false,
+            }
+        );
+
+        let dup = NaEdit::Dup {
+            ref_: Some("u".to_string()),
+            uncertain: true,
+        };
+        assert_eq!(
+            rna_edit_to_dna(&dup),
+            NaEdit::Dup {
+                ref_: Some("T".to_string()),
+                uncertain: true,
+            }
+        );
+    }
+
+    /// Wraps `MockDataProvider` to count `get_seq` calls, so tests can assert
+    /// a [`SequenceCache`] actually elides redundant fetches.
+    struct CountingDataProvider {
+        inner: MockDataProvider,
+        get_seq_calls: std::cell::Cell<u32>,
+    }
+
+    impl DataProvider for CountingDataProvider {
+        fn get_transcript(
+            &self,
+            ac: &str,
+            ref_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            self.inner.get_transcript(ac, ref_ac)
+        }
+        fn get_seq(
+            &self,
+            ac: &str,
+            start: i32,
+            end: i32,
+            kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            self.get_seq_calls.set(self.get_seq_calls.get() + 1);
+            self.inner.get_seq(ac, start, end, kind)
+        }
+        fn get_symbol_accessions(
+            &self,
+            s: &str,
+            f: IdentifierKind,
+            t: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            self.inner.get_symbol_accessions(s, f, t)
+        }
+        fn get_identifier_type(&self, id: &str) -> Result<IdentifierType, HgvsError> {
+            self.inner.get_identifier_type(id)
+        }
+        fn c_to_g(
+            &self,
+            transcript_ac: &str,
+            pos: TranscriptPos,
+            offset: IntronicOffset,
+        ) -> Result<(String, GenomicPos), HgvsError> {
+            self.inner.c_to_g(transcript_ac, pos, offset)
+        }
+    }
+
+    #[test]
+    fn test_with_sequence_cache_elides_redundant_get_seq_calls() -> Result<(), HgvsError> {
+        let hdp = CountingDataProvider {
+            inner: MockDataProvider,
+            get_seq_calls: std::cell::Cell::new(0),
+        };
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search).with_sequence_cache(SequenceCache::new());
+
+        let var1 = crate::parse_hgvs_variant("NM_000123.4:c.2_3insC")?;
+        let var2 = crate::parse_hgvs_variant("NM_000123.4:c.2dupC")?;
+
+        eq.equivalent_level(&var1, &var2)?;
+        let calls_after_first = hdp.get_seq_calls.get();
+        assert!(calls_after_first > 0);
+
+        eq.equivalent_level(&var1, &var2)?;
+        assert_eq!(
+            hdp.get_seq_calls.get(),
+            calls_after_first,
+            "second comparison should be served entirely from the sequence cache"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_cross_type_identity_memoizes_by_canonical_string() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        let var1 = crate::parse_hgvs_variant("NC_000001.11:g.5A>T")?;
+        let var2 = crate::parse_hgvs_variant("NC_000001.11:g.6A>T")?;
+
+        assert!(!eq.is_cross_type_identity(&var1, &var2));
+        assert_eq!(eq.identity_cache.borrow().len(), 1);
+
+        assert!(!eq.is_cross_type_identity(&var1, &var2));
+        assert_eq!(
+            eq.identity_cache.borrow().len(),
+            1,
+            "repeating the same pair should hit the cache rather than add a second entry"
+        );
+
+        // Order-independence: the reversed pair resolves to the same key.
+        assert!(!eq.is_cross_type_identity(&var2, &var1));
+        assert_eq!(eq.identity_cache.borrow().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_cache_elides_redundant_get_seq_and_get_transcript_calls() -> Result<(), HgvsError>
+    {
+        let hdp = CountingDataProvider {
+            inner: MockDataProvider,
+            get_seq_calls: std::cell::Cell::new(0),
+        };
+        let cache = crate::caching_provider::CachingDataProvider::new(&hdp as &dyn DataProvider);
+        let search = MockSearch;
+        let eq = VariantEquivalence::with_cache(&cache, &search);
+
+        let var1 = crate::parse_hgvs_variant("NM_000123.4:c.2_3insC")?;
+        let var2 = crate::parse_hgvs_variant("NM_000123.4:c.2dupC")?;
+
+        eq.equivalent_level(&var1, &var2)?;
+        let calls_after_first = hdp.get_seq_calls.get();
+        assert!(calls_after_first > 0);
+
+        eq.equivalent_level(&var1, &var2)?;
+        assert_eq!(
+            hdp.get_seq_calls.get(),
+            calls_after_first,
+            "second comparison should be served entirely from the caching provider"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cluster_equivalent_groups_by_canonical_genomic_key() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        // ins vs dup: different spellings of the same `c.` change, so they
+        // should share a canonical genomic key and land in one bucket
+        // without ever going through `are_equivalent_single`.
+        let ins = crate::parse_hgvs_variant("NM_000123.4:c.2_3insC")?;
+        let dup = crate::parse_hgvs_variant("NM_000123.4:c.2dupC")?;
+        // A genuinely different substitution: its own class.
+        let distinct = crate::parse_hgvs_variant("NM_000123.4:c.5A>T")?;
+
+        let variants = vec![ins, dup, distinct];
+        let classes = eq.cluster_equivalent(&variants)?;
+
+        assert_eq!(classes.len(), 2);
+        let ins_dup_class = classes
+            .iter()
+            .find(|c| c.len() == 2)
+            .expect("ins and dup should share a class");
+        assert!(ins_dup_class.contains(&0));
+        assert!(ins_dup_class.contains(&1));
+
+        let singleton = classes.iter().find(|c| c.len() == 1).unwrap();
+        assert_eq!(singleton, &vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_allele_equivalent_level_identity_for_same_order() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        let a1 = crate::parser::parse_allele("NM_000123.4:c.[2_3insC;5A>T]")?;
+        let a2 = crate::parser::parse_allele("NM_000123.4:c.[2_3insC;5A>T]")?;
+
+        assert_eq!(
+            eq.allele_equivalent_level(&a1, &a2)?,
+            EquivalenceLevel::Identity
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_allele_equivalent_level_reordered_for_permuted_components() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        let a1 = crate::parser::parse_allele("NM_000123.4:c.[2_3insC;5A>T]")?;
+        let a2 = crate::parser::parse_allele("NM_000123.4:c.[5A>T;2dupC]")?;
+
+        assert_eq!(
+            eq.allele_equivalent_level(&a1, &a2)?,
+            EquivalenceLevel::AlleleReordered
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_allele_equivalent_level_different_for_disjoint_sets() -> Result<(), HgvsError> {
+        let hdp = MockDataProvider;
+        let search = MockSearch;
+        let eq = VariantEquivalence::new(&hdp, &search);
+
+        let a1 = crate::parser::parse_allele("NM_000123.4:c.[2_3insC;5A>T]")?;
+        let a2 = crate::parser::parse_allele("NM_000123.4:c.[2_3insC;6A>T]")?;
+
+        assert_eq!(
+            eq.allele_equivalent_level(&a1, &a2)?,
+            EquivalenceLevel::Different
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,724 @@
+//! A read-only [`DataProvider`] backed by a bundle of transcript records and
+//! sequences loaded from a single JSON file, for offline use and tests that
+//! shouldn't need a live UTA/Postgres connection.
+//!
+//! [`crate::data::TranscriptData`] already round-trips through `serde_json`
+//! (the pyo3 bridge uses it to deserialize transcripts handed over from
+//! Python), so a bundle exported once from a live provider can be replayed
+//! here verbatim. This is one of several backends behind [`DataProvider`]
+//! alongside the Postgres-backed one and [`GffDataProvider`] below: any
+//! code written against `&dyn DataProvider` — the mapper, the normalizer,
+//! equivalence — runs unchanged against any of them.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+use crate::data::{DataProvider, IdentifierKind, IdentifierType, Transcript, TranscriptData};
+use crate::error::HgvsError;
+
+/// The on-disk shape of a flatfile bundle: every transcript record the
+/// provider can serve, the full sequence for each accession it or its
+/// transcripts reference, and (optionally) symbol-to-accession lookups.
+#[derive(Deserialize)]
+pub struct FlatFileBundle {
+    pub transcripts: Vec<TranscriptData>,
+    #[serde(default)]
+    pub sequences: HashMap<String, String>,
+    #[serde(default)]
+    pub symbol_accessions: HashMap<String, Vec<(IdentifierType, String)>>,
+}
+
+/// A `Transcript` that cheaply clones a loaded record out of the bundle,
+/// mirroring [`crate::caching_provider::CachingDataProvider`]'s approach to
+/// returning owned `Box<dyn Transcript>` from a shared, immutable store.
+struct BundledTranscript(Rc<TranscriptData>);
+
+impl Transcript for BundledTranscript {
+    fn ac(&self) -> &str {
+        self.0.ac()
+    }
+    fn gene(&self) -> &str {
+        self.0.gene()
+    }
+    fn strand(&self) -> i32 {
+        self.0.strand()
+    }
+    fn cds_start_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_start_index()
+    }
+    fn cds_end_index(&self) -> Option<crate::coords::TranscriptPos> {
+        self.0.cds_end_index()
+    }
+    fn reference_accession(&self) -> &str {
+        self.0.reference_accession()
+    }
+    fn exons(&self) -> &[crate::data::ExonData] {
+        self.0.exons()
+    }
+}
+
+/// Offline, read-only [`DataProvider`] over a [`FlatFileBundle`] loaded
+/// entirely into memory. Intended for tests and for running projection
+/// without a database; there is no write path and no lazy fetch — a
+/// transcript or sequence lookup either hits the bundle or fails with
+/// [`HgvsError::DataProviderError`]; a symbol with no bundled transcripts
+/// yields an empty list.
+pub struct FlatFileDataProvider {
+    transcripts: HashMap<String, Rc<TranscriptData>>,
+    sequences: HashMap<String, String>,
+    symbol_accessions: HashMap<String, Vec<(IdentifierType, String)>>,
+}
+
+impl FlatFileDataProvider {
+    pub fn from_bundle(bundle: FlatFileBundle) -> Self {
+        let transcripts = bundle
+            .transcripts
+            .into_iter()
+            .map(|t| (t.ac().to_string(), Rc::new(t)))
+            .collect();
+        FlatFileDataProvider {
+            transcripts,
+            sequences: bundle.sequences,
+            symbol_accessions: bundle.symbol_accessions,
+        }
+    }
+
+    /// Loads a bundle from a JSON file on disk.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, HgvsError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        let bundle: FlatFileBundle = serde_json::from_str(&text)
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        Ok(Self::from_bundle(bundle))
+    }
+}
+
+impl DataProvider for FlatFileDataProvider {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        _reference_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        self.transcripts
+            .get(ac)
+            .map(|t| Box::new(BundledTranscript(t.clone())) as Box<dyn Transcript>)
+            .ok_or_else(|| HgvsError::DataProviderError(format!("no bundled transcript for {ac}")))
+    }
+
+    fn get_seq(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let seq = self.sequences.get(ac).ok_or_else(|| {
+            HgvsError::DataProviderError(format!("no bundled sequence for {ac}"))
+        })?;
+        let s = start.max(0) as usize;
+        let e = if end == -1 { seq.len() } else { end as usize };
+        if s > seq.len() || e > seq.len() || s > e {
+            return Err(HgvsError::DataProviderError(format!(
+                "requested range {}..{} out of bounds for {} ({} bp)",
+                start,
+                end,
+                ac,
+                seq.len()
+            )));
+        }
+        Ok(seq[s..e].to_string())
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        _from: IdentifierKind,
+        _to: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        Ok(self
+            .symbol_accessions
+            .get(symbol)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn get_identifier_type(&self, identifier: &str) -> Result<IdentifierType, HgvsError> {
+        if self.transcripts.contains_key(identifier) {
+            Ok(IdentifierType::TranscriptAccession)
+        } else if self.sequences.contains_key(identifier) {
+            Ok(IdentifierType::GenomicAccession)
+        } else if let Some(kind) = crate::database_source::identifier_type_for_accession(identifier) {
+            Ok(kind)
+        } else {
+            Ok(IdentifierType::Unknown)
+        }
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: crate::coords::TranscriptPos,
+        offset: crate::coords::IntronicOffset,
+    ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+        let transcript = self.get_transcript(transcript_ac, None)?;
+        let reference_ac = transcript.reference_accession().to_string();
+        let mapper = crate::transcript_mapper::TranscriptMapper::new(transcript)?;
+        let g_pos = mapper.n_to_g(pos, offset)?;
+        Ok((reference_ac, g_pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ExonData;
+
+    fn bundle() -> FlatFileBundle {
+        FlatFileBundle {
+            transcripts: vec![TranscriptData {
+                ac: "NM_0001.1".to_string(),
+                gene: "MOCK".to_string(),
+                strand: 1,
+                cds_start_index: Some(crate::coords::TranscriptPos(0)),
+                cds_end_index: Some(crate::coords::TranscriptPos(19)),
+                reference_accession: "NC_0001.1".to_string(),
+                exons: vec![ExonData {
+                    transcript_start: crate::coords::TranscriptPos(0),
+                    transcript_end: crate::coords::TranscriptPos(19),
+                    reference_start: crate::coords::GenomicPos(0),
+                    reference_end: crate::coords::GenomicPos(19),
+                    alt_strand: 1,
+                    cigar: "20M".to_string(),
+                }],
+            }],
+            sequences: HashMap::from([(
+                "NM_0001.1".to_string(),
+                "ACGTACGTACGTACGTACGT".to_string(),
+            )]),
+            symbol_accessions: HashMap::from([(
+                "MOCK".to_string(),
+                vec![(IdentifierType::TranscriptAccession, "NM_0001.1".to_string())],
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_get_transcript_returns_bundled_record() {
+        let provider = FlatFileDataProvider::from_bundle(bundle());
+        let t = provider.get_transcript("NM_0001.1", None).unwrap();
+        assert_eq!(t.gene(), "MOCK");
+        assert_eq!(t.ac(), "NM_0001.1");
+    }
+
+    #[test]
+    fn test_get_transcript_missing_accession_errors() {
+        let provider = FlatFileDataProvider::from_bundle(bundle());
+        assert!(provider.get_transcript("NM_9999.1", None).is_err());
+    }
+
+    #[test]
+    fn test_get_seq_slices_bundled_sequence() {
+        let provider = FlatFileDataProvider::from_bundle(bundle());
+        assert_eq!(
+            provider
+                .get_seq("NM_0001.1", 4, 8, IdentifierType::TranscriptAccession)
+                .unwrap(),
+            "ACGT"
+        );
+        assert_eq!(
+            provider
+                .get_seq("NM_0001.1", 0, -1, IdentifierType::TranscriptAccession)
+                .unwrap(),
+            "ACGTACGTACGTACGTACGT"
+        );
+    }
+
+    #[test]
+    fn test_get_seq_out_of_bounds_errors() {
+        let provider = FlatFileDataProvider::from_bundle(bundle());
+        assert!(provider
+            .get_seq("NM_0001.1", 0, 1000, IdentifierType::TranscriptAccession)
+            .is_err());
+    }
+
+    #[test]
+    fn test_get_symbol_accessions_looks_up_bundle() {
+        let provider = FlatFileDataProvider::from_bundle(bundle());
+        let accs = provider
+            .get_symbol_accessions("MOCK", IdentifierKind::Gene, IdentifierKind::Transcript)
+            .unwrap();
+        assert_eq!(
+            accs,
+            vec![(IdentifierType::TranscriptAccession, "NM_0001.1".to_string())]
+        );
+    }
+}
+
+/// A single `exon`/`CDS` feature line parsed out of a GFF3/GTF gene model,
+/// in GFF's native 1-based inclusive coordinates.
+struct GffFeature {
+    seqid: String,
+    feature_type: String,
+    start: i64,
+    end: i64,
+    strand: i32,
+    transcript_id: String,
+    gene_id: String,
+}
+
+/// Parses a GFF3 (`key=value;...`) or GTF (`key "value"; ...`) attribute
+/// column into a lookup table. The two dialects are told apart per pair by
+/// whether it contains `=`; GTF values have their surrounding quotes stripped.
+fn parse_gff_attributes(raw: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for pair in raw.split(';') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        if let Some((k, v)) = pair.split_once('=') {
+            out.insert(k.trim().to_string(), v.trim().to_string());
+        } else if let Some((k, v)) = pair.split_once(' ') {
+            out.insert(k.trim().to_string(), v.trim().trim_matches('"').to_string());
+        }
+    }
+    out
+}
+
+/// Parses one tab-delimited GFF3/GTF line, keeping only `exon`/`CDS`
+/// features -- the rest (`gene`, `mRNA`, `five_prime_UTR`, ...) aren't
+/// needed to reconstruct [`crate::data::TranscriptData`].
+fn parse_gff_line(line: &str) -> Option<GffFeature> {
+    if line.starts_with('#') || line.trim().is_empty() {
+        return None;
+    }
+    let cols: Vec<&str> = line.split('\t').collect();
+    if cols.len() < 9 {
+        return None;
+    }
+    let feature_type = cols[2].to_string();
+    if feature_type != "exon" && feature_type != "CDS" {
+        return None;
+    }
+    let start: i64 = cols[3].parse().ok()?;
+    let end: i64 = cols[4].parse().ok()?;
+    let strand = if cols[6] == "-" { -1 } else { 1 };
+    let attrs = parse_gff_attributes(cols[8]);
+    let transcript_id = attrs
+        .get("transcript_id")
+        .or_else(|| attrs.get("Parent"))?
+        .trim_start_matches("transcript:")
+        .to_string();
+    let gene_id = attrs
+        .get("gene_id")
+        .or_else(|| attrs.get("gene"))
+        .or_else(|| attrs.get("gene_name"))
+        .cloned()
+        .unwrap_or_default();
+    Some(GffFeature {
+        seqid: cols[0].to_string(),
+        feature_type,
+        start,
+        end,
+        strand,
+        transcript_id,
+        gene_id,
+    })
+}
+
+/// The genomic intervals and metadata collected for one transcript while
+/// scanning a gene model, before they're projected into transcript-relative
+/// coordinates.
+#[derive(Default)]
+struct GffTranscriptBuild {
+    seqid: String,
+    strand: i32,
+    gene: String,
+    /// Genomic exon spans, 1-based inclusive, in whatever order they were
+    /// encountered (sorted ascending before use).
+    exons: Vec<(i64, i64)>,
+    /// The union of every `CDS` feature's genomic span for this transcript.
+    cds: Option<(i64, i64)>,
+}
+
+fn total_exon_len(exons_asc: &[(i64, i64)]) -> i64 {
+    exons_asc.iter().map(|(s, e)| e - s + 1).sum()
+}
+
+/// Projects a 1-based inclusive genomic position onto its 0-based
+/// transcript-relative position, given the transcript's exons in ascending
+/// genomic order. On the minus strand the transcript reads 5'->3' in
+/// descending genomic order, so the forward (ascending) cumulative offset
+/// is mirrored around the transcript's total exonic length.
+fn genomic_to_transcript_pos(exons_asc: &[(i64, i64)], strand: i32, genomic_pos: i64) -> Option<i64> {
+    let mut cum = 0i64;
+    for &(s, e) in exons_asc {
+        if genomic_pos >= s && genomic_pos <= e {
+            let fwd = cum + (genomic_pos - s);
+            return Some(if strand < 0 {
+                total_exon_len(exons_asc) - 1 - fwd
+            } else {
+                fwd
+            });
+        }
+        cum += e - s + 1;
+    }
+    None
+}
+
+/// Splices a transcript's mRNA sequence out of its genomic contig: the
+/// exon spans are concatenated in ascending genomic order and, on the
+/// minus strand, the whole result is reverse-complemented once -- which
+/// both reorders the exons into transcript (5'->3') order and complements
+/// their bases in one step, rather than doing each exon separately.
+fn splice_transcript_sequence(genome_seq: &str, exons_asc: &[(i64, i64)], strand: i32) -> String {
+    let mut spliced = String::new();
+    for &(s, e) in exons_asc {
+        let start0 = (s - 1).max(0) as usize;
+        let end0 = e as usize;
+        if start0 <= genome_seq.len() && end0 <= genome_seq.len() {
+            spliced.push_str(&genome_seq[start0..end0]);
+        }
+    }
+    if strand < 0 {
+        crate::utils::reverse_complement(&spliced)
+    } else {
+        spliced
+    }
+}
+
+/// A [`DataProvider`] built directly from a GFF3/GTF gene model and a
+/// genome FASTA, for running projection against a real RefSeq/Ensembl
+/// annotation release instead of hand-built [`TranscriptData`] fixtures.
+///
+/// `exon`/`CDS` features are grouped by transcript, sorted by genomic
+/// coordinate, and turned into the same `TranscriptData`/`ExonData`
+/// structures a live UTA- or flatfile-backed provider would hand back:
+/// `cds_start_index`/`cds_end_index` are derived from the `CDS` features'
+/// genomic span projected onto transcript coordinates, `strand`/
+/// `alt_strand` come from the feature strand, and each exon's CIGAR is
+/// synthesized as a single `"{len}="` run since the model carries no
+/// alignment gaps of its own. `get_seq` splices the relevant exons out of
+/// the FASTA, reverse-complementing on the minus strand.
+///
+/// The FASTA is read fully into memory rather than through a faidx/bgzip
+/// index -- sufficient for a single chromosome or a test contig, but a
+/// whole-genome FASTA should go through an indexed reader instead; that
+/// integration is left for whoever wires this up against a real `.fai`.
+pub struct GffDataProvider {
+    transcripts: HashMap<String, Rc<TranscriptData>>,
+    transcript_sequences: HashMap<String, String>,
+    genome: HashMap<String, String>,
+    symbol_accessions: HashMap<String, Vec<(IdentifierType, String)>>,
+}
+
+impl GffDataProvider {
+    /// Builds a provider from an already-parsed gene model and genome.
+    /// `genome` maps a GFF `seqid` (contig/chromosome accession) to its
+    /// full sequence.
+    pub fn from_gff_and_genome(gff_text: &str, genome: HashMap<String, String>) -> Self {
+        let mut builds: HashMap<String, GffTranscriptBuild> = HashMap::new();
+
+        for line in gff_text.lines() {
+            let Some(feature) = parse_gff_line(line) else {
+                continue;
+            };
+            let build = builds.entry(feature.transcript_id.clone()).or_default();
+            build.seqid = feature.seqid;
+            build.strand = feature.strand;
+            if !feature.gene_id.is_empty() {
+                build.gene = feature.gene_id;
+            }
+            match feature.feature_type.as_str() {
+                "exon" => build.exons.push((feature.start, feature.end)),
+                "CDS" => {
+                    build.cds = Some(match build.cds {
+                        Some((s, e)) => (s.min(feature.start), e.max(feature.end)),
+                        None => (feature.start, feature.end),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        let mut transcripts = HashMap::new();
+        let mut transcript_sequences = HashMap::new();
+        let mut symbol_accessions: HashMap<String, Vec<(IdentifierType, String)>> = HashMap::new();
+
+        for (ac, mut build) in builds {
+            if build.exons.is_empty() {
+                continue;
+            }
+            build.exons.sort();
+
+            let (cds_start_index, cds_end_index) = match build.cds {
+                Some((cs, ce)) => {
+                    let a = genomic_to_transcript_pos(&build.exons, build.strand, cs);
+                    let b = genomic_to_transcript_pos(&build.exons, build.strand, ce);
+                    match (a, b) {
+                        (Some(a), Some(b)) => (
+                            Some(crate::coords::TranscriptPos(a.min(b) as i32)),
+                            Some(crate::coords::TranscriptPos(a.max(b) as i32)),
+                        ),
+                        _ => (None, None),
+                    }
+                }
+                None => (None, None),
+            };
+
+            let exons = build
+                .exons
+                .iter()
+                .filter_map(|&(s, e)| {
+                    let t_a = genomic_to_transcript_pos(&build.exons, build.strand, s)?;
+                    let t_b = genomic_to_transcript_pos(&build.exons, build.strand, e)?;
+                    Some(crate::data::ExonData {
+                        transcript_start: crate::coords::TranscriptPos(t_a.min(t_b) as i32),
+                        transcript_end: crate::coords::TranscriptPos(t_a.max(t_b) as i32),
+                        reference_start: crate::coords::GenomicPos((s - 1) as i32),
+                        reference_end: crate::coords::GenomicPos((e - 1) as i32),
+                        alt_strand: build.strand,
+                        cigar: format!("{}=", e - s + 1),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(genome_seq) = build.get_genome_seq(&genome) {
+                transcript_sequences.insert(
+                    ac.clone(),
+                    splice_transcript_sequence(genome_seq, &build.exons, build.strand),
+                );
+            }
+
+            if !build.gene.is_empty() {
+                symbol_accessions
+                    .entry(build.gene.clone())
+                    .or_default()
+                    .push((IdentifierType::TranscriptAccession, ac.clone()));
+            }
+
+            transcripts.insert(
+                ac.clone(),
+                Rc::new(TranscriptData {
+                    ac: ac.clone(),
+                    gene: build.gene,
+                    strand: build.strand,
+                    cds_start_index,
+                    cds_end_index,
+                    reference_accession: build.seqid,
+                    exons,
+                }),
+            );
+        }
+
+        GffDataProvider {
+            transcripts,
+            transcript_sequences,
+            genome,
+            symbol_accessions,
+        }
+    }
+
+    /// Loads a gene model and genome FASTA from disk. The FASTA is read
+    /// fully into memory -- see the struct-level note about faidx/bgzip.
+    pub fn load(gff_path: impl AsRef<Path>, fasta_path: impl AsRef<Path>) -> Result<Self, HgvsError> {
+        let gff_text =
+            std::fs::read_to_string(gff_path).map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        let fasta_text = std::fs::read_to_string(fasta_path)
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        Ok(Self::from_gff_and_genome(&gff_text, parse_fasta(&fasta_text)))
+    }
+
+    /// Every transcript this provider can serve, keyed by accession. Used to
+    /// build a [`crate::genomic_index::GenomicIntervalIndex`] over the whole
+    /// gene model without re-parsing the GFF.
+    pub fn transcripts(&self) -> impl Iterator<Item = (&str, &TranscriptData)> {
+        self.transcripts
+            .iter()
+            .map(|(ac, td)| (ac.as_str(), td.as_ref()))
+    }
+}
+
+impl GffTranscriptBuild {
+    fn get_genome_seq<'a>(&self, genome: &'a HashMap<String, String>) -> Option<&'a String> {
+        genome.get(&self.seqid)
+    }
+}
+
+/// Parses a plain multi-FASTA text into a `seqid -> sequence` map,
+/// upper-casing bases and taking the header up to the first whitespace as
+/// the id, matching how a `.fai` index names its records.
+fn parse_fasta(text: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut seq = String::new();
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix('>') {
+            if let Some(id) = current.take() {
+                out.insert(id, std::mem::take(&mut seq));
+            }
+            current = Some(header.split_whitespace().next().unwrap_or("").to_string());
+        } else {
+            seq.push_str(&line.trim().to_uppercase());
+        }
+    }
+    if let Some(id) = current {
+        out.insert(id, seq);
+    }
+    out
+}
+
+impl DataProvider for GffDataProvider {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        _reference_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        self.transcripts
+            .get(ac)
+            .map(|t| Box::new(BundledTranscript(t.clone())) as Box<dyn Transcript>)
+            .ok_or_else(|| HgvsError::DataProviderError(format!("no transcript for {ac} in gene model")))
+    }
+
+    fn get_seq(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let seq = self
+            .transcript_sequences
+            .get(ac)
+            .or_else(|| self.genome.get(ac))
+            .ok_or_else(|| HgvsError::DataProviderError(format!("no sequence for {ac}")))?;
+        let s = start.max(0) as usize;
+        let e = if end == -1 { seq.len() } else { end as usize };
+        if s > seq.len() || e > seq.len() || s > e {
+            return Err(HgvsError::DataProviderError(format!(
+                "requested range {}..{} out of bounds for {} ({} bp)",
+                start,
+                end,
+                ac,
+                seq.len()
+            )));
+        }
+        Ok(seq[s..e].to_string())
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        _from: IdentifierKind,
+        _to: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        Ok(self
+            .symbol_accessions
+            .get(symbol)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn get_identifier_type(&self, identifier: &str) -> Result<IdentifierType, HgvsError> {
+        if self.transcripts.contains_key(identifier) {
+            Ok(IdentifierType::TranscriptAccession)
+        } else if self.genome.contains_key(identifier) {
+            Ok(IdentifierType::GenomicAccession)
+        } else if let Some(kind) = crate::database_source::identifier_type_for_accession(identifier) {
+            Ok(kind)
+        } else {
+            Ok(IdentifierType::Unknown)
+        }
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: crate::coords::TranscriptPos,
+        offset: crate::coords::IntronicOffset,
+    ) -> Result<(String, crate::coords::GenomicPos), HgvsError> {
+        let transcript = self.get_transcript(transcript_ac, None)?;
+        let reference_ac = transcript.reference_accession().to_string();
+        let mapper = crate::transcript_mapper::TranscriptMapper::new(transcript)?;
+        let g_pos = mapper.n_to_g(pos, offset)?;
+        Ok((reference_ac, g_pos))
+    }
+}
+
+#[cfg(test)]
+mod gff_provider_tests {
+    use super::*;
+
+    /// A minimal two-exon plus-strand transcript ("CASP8-shaped" but tiny):
+    /// exon1 covers genomic 1..=10, exon2 covers genomic 21..=30, with the
+    /// CDS spanning the middle of the spliced transcript.
+    const GFF3: &str = "\
+##gff-version 3
+chr1\ttest\texon\t1\t10\t.\t+\t.\tID=exon:1;Parent=transcript:ENST00000001.1
+chr1\ttest\tCDS\t3\t10\t.\t+\t0\tID=cds:1;Parent=transcript:ENST00000001.1;gene_id=ENSG00000001
+chr1\ttest\texon\t21\t30\t.\t+\t.\tID=exon:2;Parent=transcript:ENST00000001.1
+chr1\ttest\tCDS\t21\t25\t.\t+\t0\tID=cds:2;Parent=transcript:ENST00000001.1;gene_id=ENSG00000001
+";
+
+    fn genome() -> HashMap<String, String> {
+        // 30bp contig; exon1 = bases 1..10, gap 11..20, exon2 = 21..30.
+        HashMap::from([(
+            "chr1".to_string(),
+            "AAAAAAAAAAGGGGGGGGGGCCCCCCCCCC".to_string(),
+        )])
+    }
+
+    #[test]
+    fn test_from_gff_and_genome_builds_transcript_with_spliced_sequence() {
+        let provider = GffDataProvider::from_gff_and_genome(GFF3, genome());
+        let t = provider.get_transcript("ENST00000001.1", None).unwrap();
+        assert_eq!(t.gene(), "ENSG00000001");
+        assert_eq!(t.strand(), 1);
+        assert_eq!(t.exons().len(), 2);
+
+        // 10bp exon1 + 10bp exon2 = 20bp mRNA: AAAAAAAAAA CCCCCCCCCC
+        let mrna = provider
+            .get_seq("ENST00000001.1", 0, -1, IdentifierType::TranscriptAccession)
+            .unwrap();
+        assert_eq!(mrna, "AAAAAAAAAACCCCCCCCCC");
+    }
+
+    #[test]
+    fn test_from_gff_and_genome_derives_cds_indices_across_exon_boundary() {
+        let provider = GffDataProvider::from_gff_and_genome(GFF3, genome());
+        let t = provider.get_transcript("ENST00000001.1", None).unwrap();
+        // CDS starts at genomic 3 (transcript pos 2, 0-based) and ends at
+        // genomic 25 (transcript pos 14: 8 remaining exon1 bases + 5 into exon2).
+        assert_eq!(t.cds_start_index().unwrap().0, 2);
+        assert_eq!(t.cds_end_index().unwrap().0, 14);
+    }
+
+    #[test]
+    fn test_from_gff_and_genome_resolves_gene_symbol_to_transcript() {
+        let provider = GffDataProvider::from_gff_and_genome(GFF3, genome());
+        let accs = provider
+            .get_symbol_accessions("ENSG00000001", IdentifierKind::Gene, IdentifierKind::Transcript)
+            .unwrap();
+        assert_eq!(
+            accs,
+            vec![(IdentifierType::TranscriptAccession, "ENST00000001.1".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_from_gff_and_genome_minus_strand_reverse_complements() {
+        let gff = "\
+chr1\ttest\texon\t1\t10\t.\t-\t.\tParent=transcript:ENST00000002.1;gene_id=ENSG00000002
+";
+        let provider = GffDataProvider::from_gff_and_genome(gff, genome());
+        let mrna = provider
+            .get_seq("ENST00000002.1", 0, -1, IdentifierType::TranscriptAccession)
+            .unwrap();
+        // Forward strand bases 1..10 are all 'A'; reverse-complementing an
+        // all-A run still yields all-T.
+        assert_eq!(mrna, "TTTTTTTTTT");
+    }
+}
@@ -0,0 +1,406 @@
+//! NCBI genetic-code tables used when translating codons to amino acids.
+//!
+//! [`GeneticCodeTable::Standard`] (NCBI `transl_table=1`) is what
+//! [`crate::sequence::TranslatedSequence`] has always assumed. This module adds
+//! [`GeneticCodeTable::VertebrateMitochondrial`] (`transl_table=2`),
+//! [`GeneticCodeTable::YeastMitochondrial`] (`transl_table=3`),
+//! [`GeneticCodeTable::InvertebrateMitochondrial`] (`transl_table=5`) and
+//! [`GeneticCodeTable::Custom`], plus a lookup keyed by codon, so `c_to_p` can
+//! translate mitochondrial transcripts correctly instead of reporting spurious
+//! premature stops, and callers with a translation table NCBI hasn't seeded
+//! here can still supply one.
+
+use crate::error::HgvsError;
+
+/// An NCBI genetic-code table, identified by its `transl_table` id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneticCodeTable {
+    /// `transl_table=1`: the standard nuclear genetic code.
+    Standard,
+    /// `transl_table=2`: vertebrate mitochondrial code.
+    VertebrateMitochondrial,
+    /// `transl_table=3`: yeast mitochondrial code.
+    YeastMitochondrial,
+    /// `transl_table=5`: invertebrate mitochondrial code.
+    InvertebrateMitochondrial,
+    /// A caller-supplied table, keyed by [`codon_index`], for translation
+    /// tables NCBI hasn't seeded here (or a non-NCBI code entirely). Build
+    /// one with [`GeneticCodeTable::custom`].
+    Custom(&'static [char; 64]),
+}
+
+impl GeneticCodeTable {
+    /// The NCBI `transl_table` id for this table, or `0` (not a real NCBI id)
+    /// for [`GeneticCodeTable::Custom`].
+    pub fn transl_table_id(&self) -> u8 {
+        match self {
+            GeneticCodeTable::Standard => 1,
+            GeneticCodeTable::VertebrateMitochondrial => 2,
+            GeneticCodeTable::YeastMitochondrial => 3,
+            GeneticCodeTable::InvertebrateMitochondrial => 5,
+            GeneticCodeTable::Custom(_) => 0,
+        }
+    }
+
+    /// Builds a [`GeneticCodeTable::Custom`] table from a 64-entry amino acid
+    /// map indexed by [`codon_index`]. The table is leaked to give it a
+    /// `'static` lifetime, the same way a process-lifetime config value would
+    /// be; this is only meant to be called once per distinct custom table
+    /// (e.g. at startup), not per variant.
+    pub fn custom(table: [char; 64]) -> Self {
+        GeneticCodeTable::Custom(Box::leak(Box::new(table)))
+    }
+
+    /// Looks up a table by its NCBI `transl_table` id. Only the ids this
+    /// crate has seeded a table for are recognized; anything else is an
+    /// error rather than silently falling back to [`GeneticCodeTable::Standard`].
+    /// [`GeneticCodeTable::Custom`] tables have no id and are never returned
+    /// here; construct them with [`GeneticCodeTable::custom`] instead.
+    pub fn by_id(id: u8) -> Result<Self, HgvsError> {
+        match id {
+            1 => Ok(GeneticCodeTable::Standard),
+            2 => Ok(GeneticCodeTable::VertebrateMitochondrial),
+            3 => Ok(GeneticCodeTable::YeastMitochondrial),
+            5 => Ok(GeneticCodeTable::InvertebrateMitochondrial),
+            _ => Err(HgvsError::UnsupportedOperation(format!(
+                "unsupported NCBI transl_table id: {id}"
+            ))),
+        }
+    }
+
+    /// Picks the table a transcript should use, based on its genomic reference
+    /// accession. Defaults to [`GeneticCodeTable::Standard`] for anything that
+    /// isn't the human mitochondrial contig.
+    pub fn for_reference_accession(reference_accession: &str) -> Self {
+        // NC_012920.1 is the revised Cambridge Reference Sequence (rCRS) for
+        // human mtDNA; strip the version so `NC_012920` and `NC_012920.1` both match.
+        let base = reference_accession.split('.').next().unwrap_or(reference_accession);
+        if base == "NC_012920" {
+            GeneticCodeTable::VertebrateMitochondrial
+        } else {
+            GeneticCodeTable::Standard
+        }
+    }
+
+    /// Picks the table for a specific transcript, honoring an explicit
+    /// per-accession override before falling back to
+    /// [`Self::for_reference_accession`].
+    ///
+    /// `TranscriptData` has no `translation_table` field of its own, so a
+    /// caller that knows a transcript uses a non-default table despite its
+    /// reference accession not being recognized by auto-detection (e.g. a
+    /// nuclear-encoded transcript sharing a mitochondrial contig in a
+    /// non-human genome) can supply it here, keyed by transcript accession
+    /// (version included or not -- the version suffix is stripped the same
+    /// way [`Self::for_reference_accession`] strips it from the contig).
+    pub fn for_transcript(
+        transcript_ac: &str,
+        reference_accession: &str,
+        overrides: &std::collections::HashMap<String, GeneticCodeTable>,
+    ) -> Self {
+        let base_ac = transcript_ac.split('.').next().unwrap_or(transcript_ac);
+        overrides
+            .get(transcript_ac)
+            .or_else(|| overrides.get(base_ac))
+            .copied()
+            .unwrap_or_else(|| Self::for_reference_accession(reference_accession))
+    }
+
+    /// Translates a single codon (must be exactly 3 bases, `T`/`U` both
+    /// accepted) to its one-letter amino acid code, or `'X'` if the codon
+    /// contains ambiguous/invalid bases.
+    pub fn translate_codon(&self, codon: [char; 3]) -> char {
+        let dna_codon = [to_dna(codon[0]), to_dna(codon[1]), to_dna(codon[2])];
+        if let GeneticCodeTable::Custom(table) = self {
+            return match codon_index(dna_codon) {
+                Some(idx) => table[idx],
+                None => 'X',
+            };
+        }
+        let diffs: &[([char; 3], char)] = match self {
+            GeneticCodeTable::Standard => &[],
+            GeneticCodeTable::VertebrateMitochondrial => &[
+                (['A', 'G', 'A'], '*'),
+                (['A', 'G', 'G'], '*'),
+                (['A', 'T', 'A'], 'M'),
+                (['T', 'G', 'A'], 'W'),
+            ],
+            GeneticCodeTable::YeastMitochondrial => &[
+                (['C', 'T', 'T'], 'T'),
+                (['C', 'T', 'C'], 'T'),
+                (['C', 'T', 'A'], 'T'),
+                (['C', 'T', 'G'], 'T'),
+                (['A', 'T', 'A'], 'M'),
+                (['T', 'G', 'A'], 'W'),
+            ],
+            GeneticCodeTable::InvertebrateMitochondrial => &[
+                (['A', 'G', 'A'], 'S'),
+                (['A', 'G', 'G'], 'S'),
+                (['A', 'T', 'A'], 'M'),
+                (['T', 'G', 'A'], 'W'),
+            ],
+            GeneticCodeTable::Custom(_) => unreachable!("handled above"),
+        };
+        for (c, aa) in diffs {
+            if *c == dna_codon {
+                return *aa;
+            }
+        }
+        crate::sequence::translate_codon_standard(dna_codon)
+    }
+
+    /// Codons that may initiate translation under this table.
+    ///
+    /// [`GeneticCodeTable::Custom`] tables don't carry their own start-codon
+    /// set, so they assume the standard `ATG`-only rule.
+    pub fn start_codons(&self) -> &'static [[char; 3]] {
+        match self {
+            GeneticCodeTable::Standard => &[['A', 'T', 'G']],
+            GeneticCodeTable::VertebrateMitochondrial => {
+                &[['A', 'T', 'G'], ['A', 'T', 'A'], ['A', 'T', 'T'], ['G', 'T', 'G']]
+            }
+            GeneticCodeTable::YeastMitochondrial => &[['A', 'T', 'G'], ['A', 'T', 'A'], ['G', 'T', 'G']],
+            GeneticCodeTable::InvertebrateMitochondrial => &[
+                ['A', 'T', 'G'],
+                ['A', 'T', 'A'],
+                ['A', 'T', 'T'],
+                ['A', 'T', 'C'],
+                ['G', 'T', 'G'],
+                ['T', 'T', 'G'],
+            ],
+            GeneticCodeTable::Custom(_) => &[['A', 'T', 'G']],
+        }
+    }
+
+    /// Returns `true` if `codon` is a valid start codon under this table.
+    pub fn is_start_codon(&self, codon: [char; 3]) -> bool {
+        let dna_codon = [to_dna(codon[0]), to_dna(codon[1]), to_dna(codon[2])];
+        self.start_codons().contains(&dna_codon)
+    }
+}
+
+fn to_dna(c: char) -> char {
+    match c {
+        'U' => 'T',
+        'u' => 't',
+        _ => c,
+    }
+}
+
+/// Index of a base within the `TCAG` ordering [`codon_index`] uses, or `None`
+/// for anything other than a plain `A`/`C`/`G`/`T`/`U` (case-insensitive).
+fn base_index(c: char) -> Option<usize> {
+    match to_dna(c).to_ascii_uppercase() {
+        'T' => Some(0),
+        'C' => Some(1),
+        'A' => Some(2),
+        'G' => Some(3),
+        _ => None,
+    }
+}
+
+/// Maps a codon to its position (0-63) in the 64-entry array a
+/// [`GeneticCodeTable::Custom`] table is built from, using the standard `TCAG`
+/// base ordering NCBI genetic-code tables are conventionally listed in:
+/// `index = 16 * pos(base1) + 4 * pos(base2) + pos(base3)` with `pos` being
+/// `T`=0, `C`=1, `A`=2, `G`=3. Returns `None` for a codon containing anything
+/// other than `A`/`C`/`G`/`T`/`U`. See [`index_codon`] for the inverse.
+pub fn codon_index(codon: [char; 3]) -> Option<usize> {
+    Some(16 * base_index(codon[0])? + 4 * base_index(codon[1])? + base_index(codon[2])?)
+}
+
+/// The codon at `index` (0-63) under the same ordering as [`codon_index`].
+/// Panics if `index >= 64`. Useful when building a [`GeneticCodeTable::Custom`]
+/// table entry-by-entry instead of from an existing lookup keyed by codon.
+pub fn index_codon(index: usize) -> [char; 3] {
+    assert!(index < 64, "codon index {index} out of range");
+    const BASES: [char; 4] = ['T', 'C', 'A', 'G'];
+    [BASES[index / 16], BASES[(index / 4) % 4], BASES[index % 4]]
+}
+
+/// The 0-based codon positions, within a CDS's translated amino acid
+/// sequence, where an in-frame `TGA` is a SECIS-directed selenocysteine
+/// (Sec, `U`) recoding site rather than a stop. `TranscriptData` has no
+/// field for this, so a caller that knows a transcript is a selenoprotein
+/// (e.g. from an external Sec-annotation database) supplies the recoded
+/// positions explicitly, the same way [`GeneticCodeTable::for_transcript`]
+/// supplies an explicit per-transcript table override.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelenocysteineSites(std::collections::HashSet<i32>);
+
+impl SelenocysteineSites {
+    /// Builds a site set from codon positions (0-based, counting from the
+    /// first codon of the CDS).
+    pub fn new(positions: impl IntoIterator<Item = i32>) -> Self {
+        Self(positions.into_iter().collect())
+    }
+
+    /// No recoding sites -- every `TGA` is a stop, as if this type didn't exist.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Whether `codon_position` is an annotated recoding site.
+    pub fn contains(&self, codon_position: i32) -> bool {
+        self.0.contains(&codon_position)
+    }
+}
+
+/// Overrides a translated stop (`'*'`) to selenocysteine (`'U'`) when `codon`
+/// is `TGA` and `is_recoded_tga` says this position is an annotated
+/// [`SelenocysteineSites`] member; otherwise returns `aa` unchanged. Shared
+/// by the reference- and alternate-CDS translation paths so both honor
+/// recoding sites identically -- an unannotated `TGA`, or one created or
+/// destroyed by the variant, still calls a plain stop.
+pub fn apply_selenocysteine_recoding(codon: [char; 3], aa: char, is_recoded_tga: bool) -> char {
+    if is_recoded_tga && aa == '*' {
+        let dna_codon = [to_dna(codon[0]), to_dna(codon[1]), to_dna(codon[2])];
+        if dna_codon == ['T', 'G', 'A'] {
+            return 'U';
+        }
+    }
+    aa
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_reference_accession() {
+        assert_eq!(
+            GeneticCodeTable::for_reference_accession("NC_012920.1"),
+            GeneticCodeTable::VertebrateMitochondrial
+        );
+        assert_eq!(
+            GeneticCodeTable::for_reference_accession("NC_000001.11"),
+            GeneticCodeTable::Standard
+        );
+    }
+
+    #[test]
+    fn test_vertebrate_mitochondrial_differences() {
+        let t = GeneticCodeTable::VertebrateMitochondrial;
+        assert_eq!(t.translate_codon(['A', 'G', 'A']), '*');
+        assert_eq!(t.translate_codon(['A', 'G', 'G']), '*');
+        assert_eq!(t.translate_codon(['A', 'T', 'A']), 'M');
+        assert_eq!(t.translate_codon(['T', 'G', 'A']), 'W');
+        // Unaffected codons fall back to the standard table.
+        assert_eq!(t.translate_codon(['A', 'T', 'G']), 'M');
+    }
+
+    #[test]
+    fn test_start_codons() {
+        let t = GeneticCodeTable::VertebrateMitochondrial;
+        assert!(t.is_start_codon(['A', 'T', 'T']));
+        assert!(t.is_start_codon(['A', 'T', 'A']));
+        assert!(!GeneticCodeTable::Standard.is_start_codon(['A', 'T', 'A']));
+    }
+
+    #[test]
+    fn test_yeast_mitochondrial_differences() {
+        let t = GeneticCodeTable::YeastMitochondrial;
+        assert_eq!(t.translate_codon(['C', 'T', 'G']), 'T');
+        assert_eq!(t.translate_codon(['A', 'T', 'A']), 'M');
+        assert_eq!(t.translate_codon(['T', 'G', 'A']), 'W');
+        // Unaffected codons fall back to the standard table.
+        assert_eq!(t.translate_codon(['A', 'T', 'G']), 'M');
+    }
+
+    #[test]
+    fn test_invertebrate_mitochondrial_differences() {
+        let t = GeneticCodeTable::InvertebrateMitochondrial;
+        assert_eq!(t.translate_codon(['A', 'G', 'A']), 'S');
+        assert_eq!(t.translate_codon(['A', 'G', 'G']), 'S');
+        assert_eq!(t.translate_codon(['A', 'T', 'A']), 'M');
+        assert_eq!(t.translate_codon(['T', 'G', 'A']), 'W');
+        assert!(t.is_start_codon(['T', 'T', 'G']));
+    }
+
+    #[test]
+    fn test_for_transcript_honors_explicit_override() {
+        let mut overrides = std::collections::HashMap::new();
+        overrides.insert("NM_999999.1".to_string(), GeneticCodeTable::VertebrateMitochondrial);
+        // Keyed without a version, so any version of this accession matches.
+        overrides.insert("NM_888888".to_string(), GeneticCodeTable::YeastMitochondrial);
+
+        assert_eq!(
+            GeneticCodeTable::for_transcript("NM_999999.1", "NC_000001.11", &overrides),
+            GeneticCodeTable::VertebrateMitochondrial
+        );
+        assert_eq!(
+            GeneticCodeTable::for_transcript("NM_888888.3", "NC_000001.11", &overrides),
+            GeneticCodeTable::YeastMitochondrial
+        );
+        // A different version of an exact-versioned override key doesn't match.
+        assert_eq!(
+            GeneticCodeTable::for_transcript("NM_999999.2", "NC_000001.11", &overrides),
+            GeneticCodeTable::Standard
+        );
+        // No override at all falls back to reference-accession auto-detection.
+        assert_eq!(
+            GeneticCodeTable::for_transcript("NM_000001.1", "NC_012920.1", &overrides),
+            GeneticCodeTable::VertebrateMitochondrial
+        );
+    }
+
+    #[test]
+    fn test_selenocysteine_recoding_only_applies_at_annotated_sites() {
+        let sites = SelenocysteineSites::new([5]);
+        let table = GeneticCodeTable::Standard;
+        let tga = ['T', 'G', 'A'];
+        let aa = table.translate_codon(tga);
+        assert_eq!(aa, '*');
+
+        // Annotated recoding site: TGA reads as Sec, not a stop.
+        assert_eq!(apply_selenocysteine_recoding(tga, aa, sites.contains(5)), 'U');
+        // Same codon at an unannotated position: still a stop.
+        assert_eq!(apply_selenocysteine_recoding(tga, aa, sites.contains(6)), '*');
+        // A non-TGA stop codon isn't recoded even at an annotated position.
+        let taa_aa = table.translate_codon(['T', 'A', 'A']);
+        assert_eq!(apply_selenocysteine_recoding(['T', 'A', 'A'], taa_aa, sites.contains(5)), '*');
+    }
+
+    #[test]
+    fn test_codon_index_roundtrip() {
+        for idx in 0..64 {
+            assert_eq!(codon_index(index_codon(idx)), Some(idx));
+        }
+        assert_eq!(codon_index(['A', 'T', 'G']), Some(2 * 16 + 0 * 4 + 3));
+        assert_eq!(codon_index(['N', 'T', 'G']), None);
+    }
+
+    #[test]
+    fn test_custom_table_translates_per_map() {
+        // A custom table that agrees with the standard code everywhere
+        // except it recodes TAG (amber) to glutamine, like some ciliates do.
+        let mut entries = ['X'; 64];
+        for idx in 0..64 {
+            entries[idx] = crate::sequence::translate_codon_standard(index_codon(idx));
+        }
+        entries[codon_index(['T', 'A', 'G']).unwrap()] = 'Q';
+        let t = GeneticCodeTable::custom(entries);
+
+        assert_eq!(t.translate_codon(['T', 'A', 'G']), 'Q');
+        assert_eq!(t.translate_codon(['A', 'T', 'G']), 'M');
+        assert_eq!(t.translate_codon(['T', 'A', 'A']), '*');
+        assert_eq!(t.transl_table_id(), 0);
+        assert_eq!(t.start_codons(), &[['A', 'T', 'G']]);
+    }
+
+    #[test]
+    fn test_custom_table_ambiguous_codon_is_x() {
+        let t = GeneticCodeTable::custom(['M'; 64]);
+        assert_eq!(t.translate_codon(['N', 'T', 'G']), 'X');
+    }
+
+    #[test]
+    fn test_by_id() {
+        assert_eq!(GeneticCodeTable::by_id(1).unwrap(), GeneticCodeTable::Standard);
+        assert_eq!(GeneticCodeTable::by_id(2).unwrap(), GeneticCodeTable::VertebrateMitochondrial);
+        assert_eq!(GeneticCodeTable::by_id(3).unwrap(), GeneticCodeTable::YeastMitochondrial);
+        assert_eq!(GeneticCodeTable::by_id(5).unwrap(), GeneticCodeTable::InvertebrateMitochondrial);
+        assert!(GeneticCodeTable::by_id(4).is_err());
+        assert_eq!(GeneticCodeTable::by_id(2).unwrap().transl_table_id(), 2);
+    }
+}
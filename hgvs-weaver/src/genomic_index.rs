@@ -0,0 +1,214 @@
+//! A genomic-position interval index over transcript exon spans, for
+//! turning a `(contig, pos, ref, alt)` VCF-style locus into the
+//! transcripts overlapping it without a linear scan over every known
+//! transcript.
+//!
+//! This is meant to sit in front of a [`crate::data::DataProvider`] whose
+//! transcripts are already resident in memory -- most commonly
+//! [`crate::flatfile_provider::GffDataProvider`] -- via
+//! [`GenomicIntervalIndex::from_transcripts`]: building the index is a
+//! one-time cost, after which [`TranscriptSearch::get_transcripts_for_region`]
+//! answers purely from the tree, which [`crate::annotate::annotate_record`]
+//! and friends already consume.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::{TranscriptData, TranscriptSearch};
+use crate::error::HgvsError;
+
+/// One node of a per-contig interval tree: an exon span, the transcript it
+/// belongs to, and the largest end coordinate anywhere in its subtree (used
+/// to prune overlap queries).
+///
+/// This is a plain unbalanced BST keyed by interval start, not a
+/// self-balancing tree -- fine for the handful of thousand transcripts a
+/// typical gene model carries, but a pathological insertion order (e.g.
+/// exons fed in already start-sorted) degrades query time to linear. A
+/// balanced variant is left for whoever needs this over a much larger index.
+#[derive(Debug)]
+struct Node {
+    start: i32,
+    end: i32,
+    transcript_ac: String,
+    max_end: i32,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn insert(node: Option<Box<Node>>, start: i32, end: i32, transcript_ac: String) -> Box<Node> {
+        match node {
+            None => Box::new(Node {
+                start,
+                end,
+                transcript_ac,
+                max_end: end,
+                left: None,
+                right: None,
+            }),
+            Some(mut n) => {
+                if start < n.start {
+                    n.left = Some(Node::insert(n.left.take(), start, end, transcript_ac));
+                } else {
+                    n.right = Some(Node::insert(n.right.take(), start, end, transcript_ac));
+                }
+                n.max_end = n.max_end.max(end);
+                n
+            }
+        }
+    }
+
+    fn query(&self, q_start: i32, q_end: i32, out: &mut Vec<String>) {
+        if q_start > self.max_end {
+            return;
+        }
+        if let Some(left) = &self.left {
+            left.query(q_start, q_end, out);
+        }
+        if self.start <= q_end && q_start <= self.end {
+            out.push(self.transcript_ac.clone());
+        }
+        if self.start <= q_end {
+            if let Some(right) = &self.right {
+                right.query(q_start, q_end, out);
+            }
+        }
+    }
+}
+
+/// Maps a genomic contig to the transcripts whose exons overlap a given
+/// range, backed by one interval tree per contig.
+#[derive(Debug, Default)]
+pub struct GenomicIntervalIndex {
+    contigs: HashMap<String, Box<Node>>,
+}
+
+impl GenomicIntervalIndex {
+    pub fn new() -> Self {
+        GenomicIntervalIndex {
+            contigs: HashMap::new(),
+        }
+    }
+
+    /// Indexes one exon span (0-based, half-open on neither end -- the same
+    /// `reference_start`/`reference_end` convention [`crate::data::ExonData`]
+    /// already uses) under `transcript_ac`.
+    pub fn insert(&mut self, contig: &str, start: i32, end: i32, transcript_ac: &str) {
+        let root = self.contigs.remove(contig);
+        let root = Node::insert(root, start, end, transcript_ac.to_string());
+        self.contigs.insert(contig.to_string(), root);
+    }
+
+    /// Builds an index over every exon of every transcript in `transcripts`,
+    /// keyed by each transcript's own `reference_accession`.
+    pub fn from_transcripts<'a>(
+        transcripts: impl IntoIterator<Item = (&'a str, &'a TranscriptData)>,
+    ) -> Self {
+        let mut index = Self::new();
+        for (ac, td) in transcripts {
+            for exon in &td.exons {
+                index.insert(
+                    &td.reference_accession,
+                    exon.reference_start.0,
+                    exon.reference_end.0,
+                    ac,
+                );
+            }
+        }
+        index
+    }
+
+    /// Every transcript with at least one exon overlapping `start..=end` on
+    /// `contig`, deduplicated (a transcript can contribute more than one
+    /// overlapping exon) but otherwise in tree-visit order.
+    pub fn query(&self, contig: &str, start: i32, end: i32) -> Vec<String> {
+        let Some(root) = self.contigs.get(contig) else {
+            return Vec::new();
+        };
+        let mut hits = Vec::new();
+        root.query(start, end, &mut hits);
+        let mut seen = HashSet::new();
+        hits.retain(|ac| seen.insert(ac.clone()));
+        hits
+    }
+}
+
+impl TranscriptSearch for GenomicIntervalIndex {
+    fn get_transcripts_for_region(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+    ) -> Result<Vec<String>, HgvsError> {
+        Ok(self.query(ac, start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::{GenomicPos, TranscriptPos};
+    use crate::data::ExonData;
+
+    fn transcript(ac: &str, contig: &str, exons: &[(i32, i32)]) -> TranscriptData {
+        TranscriptData {
+            ac: ac.to_string(),
+            gene: "TEST".to_string(),
+            cds_start_index: None,
+            cds_end_index: None,
+            strand: 1,
+            reference_accession: contig.to_string(),
+            exons: exons
+                .iter()
+                .map(|&(s, e)| ExonData {
+                    transcript_start: TranscriptPos(0),
+                    transcript_end: TranscriptPos(e - s),
+                    reference_start: GenomicPos(s),
+                    reference_end: GenomicPos(e),
+                    alt_strand: 1,
+                    cigar: format!("{}=", e - s + 1),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_query_finds_transcript_overlapping_an_exon() {
+        let a = transcript("NM_1.1", "chr1", &[(100, 200), (500, 600)]);
+        let index = GenomicIntervalIndex::from_transcripts([("NM_1.1", &a)]);
+
+        assert_eq!(index.query("chr1", 150, 150), vec!["NM_1.1".to_string()]);
+        assert_eq!(index.query("chr1", 550, 560), vec!["NM_1.1".to_string()]);
+        assert!(index.query("chr1", 300, 400).is_empty());
+    }
+
+    #[test]
+    fn test_query_is_scoped_to_the_right_contig() {
+        let a = transcript("NM_1.1", "chr1", &[(100, 200)]);
+        let index = GenomicIntervalIndex::from_transcripts([("NM_1.1", &a)]);
+
+        assert!(index.query("chr2", 150, 150).is_empty());
+    }
+
+    #[test]
+    fn test_query_dedupes_a_transcript_with_multiple_overlapping_exons() {
+        // A query range spanning both exons of the same transcript should
+        // only report it once.
+        let a = transcript("NM_1.1", "chr1", &[(100, 200), (210, 300)]);
+        let index = GenomicIntervalIndex::from_transcripts([("NM_1.1", &a)]);
+
+        assert_eq!(index.query("chr1", 0, 1000), vec!["NM_1.1".to_string()]);
+    }
+
+    #[test]
+    fn test_query_returns_every_transcript_overlapping_the_range() {
+        let a = transcript("NM_1.1", "chr1", &[(100, 200)]);
+        let b = transcript("NM_2.1", "chr1", &[(150, 250)]);
+        let index =
+            GenomicIntervalIndex::from_transcripts([("NM_1.1", &a), ("NM_2.1", &b)]);
+
+        let mut hits = index.query("chr1", 160, 170);
+        hits.sort();
+        assert_eq!(hits, vec!["NM_1.1".to_string(), "NM_2.1".to_string()]);
+    }
+}
@@ -3,132 +3,241 @@ use crate::error::HgvsError;
 use crate::structs::*;
 use pest::iterators::Pair;
 
-pub fn parse_g_posedit(pair: Pair<Rule>) -> Result<PosEdit<SimpleInterval, NaEdit>, HgvsError> {
+fn sentinel_simple_pos() -> SimplePosition {
+    SimplePosition {
+        base: HgvsGenomicPos(0),
+        end: None,
+        uncertain: true,
+    }
+}
+
+fn sentinel_base_offset_pos(anchor: Anchor) -> BaseOffsetPosition {
+    BaseOffsetPosition {
+        base: HgvsTranscriptPos(0),
+        offset: None,
+        anchor,
+        uncertain: true,
+    }
+}
+
+fn sentinel_aa_pos() -> AAPosition {
+    AAPosition {
+        base: HgvsProteinPos(0),
+        aa: String::new(),
+        uncertain: true,
+    }
+}
+
+/// Parses a `g.` posedit, pushing a [`Diagnostic`] and substituting a
+/// sentinel node for any sub-rule that's missing instead of bailing, so the
+/// rest of the variant still comes back best-effort.
+pub fn parse_g_posedit(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> PosEdit<SimpleInterval, NaEdit> {
+    let span = pair.as_span();
     let mut inner = pair.into_inner();
-    let pos = parse_simple_interval(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing interval".into()))?,
-    )?;
-    let edit = parse_na_edit(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing edit".into()))?,
-    )?;
-    Ok(PosEdit {
-        pos: Some(pos),
+
+    let pos = match inner.next() {
+        Some(p) => Some(parse_simple_interval(p, diagnostics)),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an interval"],
+                "missing interval in g. variant",
+            ));
+            None
+        }
+    };
+    let edit = match inner.next() {
+        Some(p) => parse_na_edit(p, diagnostics),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an edit"],
+                "missing edit in g. variant",
+            ));
+            NaEdit::None
+        }
+    };
+
+    PosEdit {
+        pos,
         edit,
         uncertain: false,
         predicted: false,
-    })
+    }
 }
 
-pub fn parse_c_posedit(pair: Pair<Rule>) -> Result<PosEdit<BaseOffsetInterval, NaEdit>, HgvsError> {
+/// Parses a `c.` posedit; see [`parse_g_posedit`] for the recovery contract.
+pub fn parse_c_posedit(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> PosEdit<BaseOffsetInterval, NaEdit> {
+    let span = pair.as_span();
     let mut inner = pair.into_inner();
-    let pos = parse_base_offset_interval(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing interval".into()))?,
-    )?;
-    let edit = parse_na_edit(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing edit".into()))?,
-    )?;
-    Ok(PosEdit {
-        pos: Some(pos),
+
+    let pos = match inner.next() {
+        Some(p) => Some(parse_transcript_interval(p, diagnostics)),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an interval"],
+                "missing interval in c. variant",
+            ));
+            None
+        }
+    };
+    let edit = match inner.next() {
+        Some(p) => parse_na_edit(p, diagnostics),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an edit"],
+                "missing edit in c. variant",
+            ));
+            NaEdit::None
+        }
+    };
+
+    PosEdit {
+        pos,
         edit,
         uncertain: false,
         predicted: false,
-    })
+    }
 }
 
-pub fn parse_p_posedit(pair: Pair<Rule>) -> Result<PosEdit<AaInterval, AaEdit>, HgvsError> {
+/// Parses a `p.` posedit; see [`parse_g_posedit`] for the recovery contract.
+pub fn parse_p_posedit(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> PosEdit<AaInterval, AaEdit> {
     let mut predicted = false;
     let s = pair.as_str();
     if s.starts_with('(') && s.ends_with(')') {
         predicted = true;
     }
+    let span = pair.as_span();
 
     let mut inner = pair.into_inner();
-    let inner_pair = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty p_posedit".into()))?;
+    let Some(inner_pair) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a protein position/edit"],
+            "empty p. posedit",
+        ));
+        return PosEdit {
+            pos: None,
+            edit: AaEdit::None,
+            uncertain: false,
+            predicted,
+        };
+    };
+
     if inner_pair.as_rule() == Rule::p_posedit_special {
         let special = inner_pair.as_str();
         let edit = AaEdit::Special {
             value: special.replace(['(', ')'], ""),
             uncertain: false,
         };
-        return Ok(PosEdit {
+        return PosEdit {
             pos: None,
             edit,
             uncertain: false,
             predicted,
-        });
+        };
     }
 
     let mut pos = None;
     let mut edit = AaEdit::None;
 
     if inner_pair.as_rule() == Rule::p_interval {
-        pos = Some(parse_aa_interval(inner_pair)?);
+        pos = Some(parse_aa_interval(inner_pair, diagnostics));
         if let Some(e) = inner.next() {
-            edit = parse_pro_edit(e)?;
+            edit = parse_pro_edit(e, diagnostics);
         }
     } else if inner_pair.as_rule() == Rule::pro_edit {
-        edit = parse_pro_edit(inner_pair)?;
+        edit = parse_pro_edit(inner_pair, diagnostics);
     }
 
-    Ok(PosEdit {
+    PosEdit {
         pos,
         edit,
         uncertain: false,
         predicted,
-    })
+    }
 }
 
-pub fn parse_simple_interval(pair: Pair<Rule>) -> Result<SimpleInterval, HgvsError> {
+/// Parses a `g.`/`m.` interval, substituting [`sentinel_simple_pos`] for a
+/// missing start/end rather than failing the whole variant.
+pub fn parse_simple_interval(pair: Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) -> SimpleInterval {
     let s = pair.as_str();
     let mut uncertain = false;
     if s.starts_with('(') && s.ends_with(')') && !s.contains('_') {
         uncertain = true;
     }
+    let span = pair.as_span();
 
     let mut inner = pair.into_inner();
-    let p = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty interval".into()))?;
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a position"],
+            "empty interval",
+        ));
+        return SimpleInterval {
+            start: sentinel_simple_pos(),
+            end: None,
+            uncertain,
+        };
+    };
+
     match p.as_rule() {
         Rule::def_g_interval | Rule::def_m_interval => {
+            let p_span = p.as_span();
             let mut parts = p.into_inner();
-            let start = parse_simple_pos(
-                parts
-                    .next()
-                    .ok_or_else(|| HgvsError::PestError("Missing start position".into()))?,
-            )?;
-            let end = parts.next().map(parse_simple_pos).transpose()?;
-            Ok(SimpleInterval {
+            let start = match parts.next() {
+                Some(sp) => parse_simple_pos(sp, diagnostics),
+                None => {
+                    diagnostics.push(Diagnostic::error(
+                        (p_span.start(), p_span.end()),
+                        &["a start position"],
+                        "missing start position",
+                    ));
+                    sentinel_simple_pos()
+                }
+            };
+            let end = parts.next().map(|p| parse_simple_pos(p, diagnostics));
+            SimpleInterval {
                 start,
                 end,
                 uncertain,
-            })
+            }
         }
         Rule::uncertain_g_interval => {
             let mut start = None;
             let mut end = None;
             for sub in p.into_inner() {
                 if sub.as_rule() == Rule::def_g_interval {
+                    let sub_span = sub.as_span();
                     let mut parts = sub.into_inner();
-                    let s =
-                        parse_simple_pos(parts.next().ok_or_else(|| {
-                            HgvsError::PestError("Missing start position".into())
-                        })?)?;
-                    let e = parts.next().map(parse_simple_pos).transpose()?;
+                    let s_ = match parts.next() {
+                        Some(sp) => parse_simple_pos(sp, diagnostics),
+                        None => {
+                            diagnostics.push(Diagnostic::error(
+                                (sub_span.start(), sub_span.end()),
+                                &["a start position"],
+                                "missing start position in uncertain interval",
+                            ));
+                            sentinel_simple_pos()
+                        }
+                    };
+                    let e_ = parts.next().map(|p| parse_simple_pos(p, diagnostics));
 
                     let pos = SimplePosition {
-                        base: s.base,
-                        end: e.map(|x| x.base),
+                        base: s_.base,
+                        end: e_.map(|x| x.base),
                         uncertain: true,
                     };
 
@@ -139,124 +248,285 @@ pub fn parse_simple_interval(pair: Pair<Rule>) -> Result<SimpleInterval, HgvsErr
                     }
                 }
             }
-            Ok(SimpleInterval {
-                start: start.ok_or_else(|| {
-                    HgvsError::PestError("Missing start position in uncertain interval".into())
-                })?,
+            let start = start.unwrap_or_else(|| {
+                diagnostics.push(Diagnostic::error(
+                    (span.start(), span.end()),
+                    &["a start position"],
+                    "missing start position in uncertain interval",
+                ));
+                sentinel_simple_pos()
+            });
+            SimpleInterval {
+                start,
                 end,
                 uncertain: false,
-            })
+            }
+        }
+        other => {
+            let p_span = p.as_span();
+            diagnostics.push(Diagnostic::error(
+                (p_span.start(), p_span.end()),
+                &["def_g_interval", "uncertain_g_interval"],
+                format!("unexpected interval rule: {other:?}"),
+            ));
+            SimpleInterval {
+                start: sentinel_simple_pos(),
+                end: None,
+                uncertain,
+            }
         }
-        _ => Err(HgvsError::PestError(format!(
-            "Unexpected interval rule: {:?}",
-            p.as_rule()
-        ))),
     }
 }
 
-pub fn parse_simple_pos(pair: Pair<Rule>) -> Result<SimplePosition, HgvsError> {
+/// Parses a single genomic/mito position. `?` (unknown position) and an
+/// unparseable number both degrade to an uncertain sentinel position with a
+/// diagnostic, rather than failing the enclosing interval.
+pub fn parse_simple_pos(pair: Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) -> SimplePosition {
+    let span = pair.as_span();
     let s = pair.as_str();
     if s == "?" {
-        return Ok(SimplePosition {
+        return SimplePosition {
             base: HgvsGenomicPos(0),
             end: None,
             uncertain: true,
-        });
+        };
+    }
+    match s.parse::<i32>() {
+        Ok(hgvs_base) => SimplePosition {
+            base: HgvsGenomicPos(hgvs_base),
+            end: None,
+            uncertain: false,
+        },
+        Err(_) => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an integer position"],
+                format!("invalid position {s:?}"),
+            ));
+            sentinel_simple_pos()
+        }
     }
-    let hgvs_base = s
-        .parse::<i32>()
-        .map_err(|_| HgvsError::PestError("Invalid position".into()))?;
-    Ok(SimplePosition {
-        base: HgvsGenomicPos(hgvs_base),
-        end: None,
-        uncertain: false,
-    })
 }
 
-pub fn parse_n_posedit(pair: Pair<Rule>) -> Result<PosEdit<BaseOffsetInterval, NaEdit>, HgvsError> {
+/// Parses an `n.` posedit; see [`parse_g_posedit`] for the recovery contract.
+pub fn parse_n_posedit(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> PosEdit<BaseOffsetInterval, NaEdit> {
+    let span = pair.as_span();
     let mut inner = pair.into_inner();
-    let pos = parse_base_offset_interval_n(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing interval".into()))?,
-    )?;
-    let edit = parse_na_edit(
-        inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing edit".into()))?,
-    )?;
-    Ok(PosEdit {
-        pos: Some(pos),
+
+    let pos = match inner.next() {
+        Some(p) => Some(parse_transcript_interval_n(p, diagnostics)),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an interval"],
+                "missing interval in n. variant",
+            ));
+            None
+        }
+    };
+    let edit = match inner.next() {
+        Some(p) => parse_na_edit(p, diagnostics),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["an edit"],
+                "missing edit in n. variant",
+            ));
+            NaEdit::None
+        }
+    };
+
+    PosEdit {
+        pos,
         edit,
         uncertain: false,
         predicted: false,
-    })
+    }
+}
+
+/// Dispatches a `c.` `transcript-interval`, which is either a plain
+/// [`BaseOffsetInterval`] or one wrapped in parens to mark the whole span as
+/// uncertain (e.g. a multi-exon deletion whose exact breakpoints are
+/// unresolved: `c.(4_100)del`).
+pub fn parse_transcript_interval(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> BaseOffsetInterval {
+    let span = pair.as_span();
+    let mut inner = pair.into_inner();
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["an interval"],
+            "empty transcript interval",
+        ));
+        return BaseOffsetInterval {
+            start: sentinel_base_offset_pos(Anchor::CdsStart),
+            end: None,
+            uncertain: true,
+        };
+    };
+    match p.as_rule() {
+        Rule::uncertain_base_offset_interval => {
+            let mut inner = p.into_inner();
+            let Some(base) = inner.next() else {
+                return BaseOffsetInterval {
+                    start: sentinel_base_offset_pos(Anchor::CdsStart),
+                    end: None,
+                    uncertain: true,
+                };
+            };
+            let mut interval = parse_base_offset_interval(base, diagnostics);
+            interval.uncertain = true;
+            interval
+        }
+        _ => parse_base_offset_interval(p, diagnostics),
+    }
+}
+
+/// `n.` counterpart of [`parse_transcript_interval`]; see it for the
+/// uncertain-interval contract.
+pub fn parse_transcript_interval_n(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> BaseOffsetInterval {
+    let span = pair.as_span();
+    let mut inner = pair.into_inner();
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["an interval"],
+            "empty transcript interval",
+        ));
+        return BaseOffsetInterval {
+            start: sentinel_base_offset_pos(Anchor::TranscriptStart),
+            end: None,
+            uncertain: true,
+        };
+    };
+    match p.as_rule() {
+        Rule::uncertain_base_offset_interval => {
+            let mut inner = p.into_inner();
+            let Some(base) = inner.next() else {
+                return BaseOffsetInterval {
+                    start: sentinel_base_offset_pos(Anchor::TranscriptStart),
+                    end: None,
+                    uncertain: true,
+                };
+            };
+            let mut interval = parse_base_offset_interval_n(base, diagnostics);
+            interval.uncertain = true;
+            interval
+        }
+        _ => parse_base_offset_interval_n(p, diagnostics),
+    }
 }
 
-pub fn parse_base_offset_interval_n(pair: Pair<Rule>) -> Result<BaseOffsetInterval, HgvsError> {
+pub fn parse_base_offset_interval_n(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> BaseOffsetInterval {
     let mut uncertain = false;
     let s = pair.as_str();
     if s.starts_with('(') && s.ends_with(')') {
         uncertain = true;
     }
+    let span = pair.as_span();
 
     let mut inner = pair.into_inner();
-    let p = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty base offset interval".into()))?;
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a position"],
+            "empty base offset interval",
+        ));
+        return BaseOffsetInterval {
+            start: sentinel_base_offset_pos(Anchor::TranscriptStart),
+            end: None,
+            uncertain,
+        };
+    };
+    let p_span = p.as_span();
     let mut p_inner = p.into_inner();
 
-    let start = parse_base_offset_pos_with_default(
-        p_inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing start position".into()))?,
-        Anchor::TranscriptStart,
-    )?;
+    let start = match p_inner.next() {
+        Some(sp) => parse_base_offset_pos_with_default(sp, Anchor::TranscriptStart),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (p_span.start(), p_span.end()),
+                &["a start position"],
+                "missing start position",
+            ));
+            sentinel_base_offset_pos(Anchor::TranscriptStart)
+        }
+    };
     let end = p_inner
         .next()
-        .map(|p| parse_base_offset_pos_with_default(p, Anchor::TranscriptStart))
-        .transpose()?;
-    Ok(BaseOffsetInterval {
+        .map(|p| parse_base_offset_pos_with_default(p, Anchor::TranscriptStart));
+    BaseOffsetInterval {
         start,
         end,
         uncertain,
-    })
+    }
 }
 
-pub fn parse_base_offset_interval(pair: Pair<Rule>) -> Result<BaseOffsetInterval, HgvsError> {
+pub fn parse_base_offset_interval(
+    pair: Pair<Rule>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> BaseOffsetInterval {
     let mut uncertain = false;
     let s = pair.as_str();
     if s.starts_with('(') && s.ends_with(')') {
         uncertain = true;
     }
+    let span = pair.as_span();
 
     let mut inner = pair.into_inner();
-    let p = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty base offset interval".into()))?;
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a position"],
+            "empty base offset interval",
+        ));
+        return BaseOffsetInterval {
+            start: sentinel_base_offset_pos(Anchor::CdsStart),
+            end: None,
+            uncertain,
+        };
+    };
+    let p_span = p.as_span();
     let mut p_inner = p.into_inner();
 
-    let start = parse_base_offset_pos(
-        p_inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing start position".into()))?,
-    )?;
-    let end = p_inner.next().map(parse_base_offset_pos).transpose()?;
-    Ok(BaseOffsetInterval {
+    let start = match p_inner.next() {
+        Some(sp) => parse_base_offset_pos(sp),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (p_span.start(), p_span.end()),
+                &["a start position"],
+                "missing start position",
+            ));
+            sentinel_base_offset_pos(Anchor::CdsStart)
+        }
+    };
+    let end = p_inner.next().map(parse_base_offset_pos);
+    BaseOffsetInterval {
         start,
         end,
         uncertain,
-    })
+    }
 }
 
-pub fn parse_base_offset_pos(pair: Pair<Rule>) -> Result<BaseOffsetPosition, HgvsError> {
+pub fn parse_base_offset_pos(pair: Pair<Rule>) -> BaseOffsetPosition {
     parse_base_offset_pos_with_default(pair, Anchor::CdsStart)
 }
 
 pub fn parse_base_offset_pos_with_default(
     pair: Pair<Rule>,
     default_anchor: Anchor,
-) -> Result<BaseOffsetPosition, HgvsError> {
+) -> BaseOffsetPosition {
     let mut anchor = default_anchor;
     if pair.as_str().starts_with('*') {
         anchor = Anchor::CdsEnd;
@@ -282,41 +552,58 @@ pub fn parse_base_offset_pos_with_default(
         }
     }
 
-    Ok(BaseOffsetPosition {
+    BaseOffsetPosition {
         base: HgvsTranscriptPos(hgvs_base),
         offset: hgvs_offset,
         anchor,
         uncertain: false,
-    })
+    }
 }
 
-pub fn parse_aa_interval(pair: Pair<Rule>) -> Result<AaInterval, HgvsError> {
+pub fn parse_aa_interval(pair: Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) -> AaInterval {
     let s = pair.as_str();
     let mut uncertain = false;
     if s.starts_with('(') && s.ends_with(')') {
         uncertain = true;
     }
+    let span = pair.as_span();
 
     let mut inner = pair.into_inner();
-    let p = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty AA interval".into()))?;
+    let Some(p) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["an AA position"],
+            "empty AA interval",
+        ));
+        return AaInterval {
+            start: sentinel_aa_pos(),
+            end: None,
+            uncertain,
+        };
+    };
+    let p_span = p.as_span();
     let mut p_inner = p.into_inner();
 
-    let start = parse_aa_pos(
-        p_inner
-            .next()
-            .ok_or_else(|| HgvsError::PestError("Missing start AA position".into()))?,
-    )?;
-    let end = p_inner.next().map(parse_aa_pos).transpose()?;
-    Ok(AaInterval {
+    let start = match p_inner.next() {
+        Some(sp) => parse_aa_pos(sp),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (p_span.start(), p_span.end()),
+                &["a start AA position"],
+                "missing start AA position",
+            ));
+            sentinel_aa_pos()
+        }
+    };
+    let end = p_inner.next().map(parse_aa_pos);
+    AaInterval {
         start,
         end,
         uncertain,
-    })
+    }
 }
 
-pub fn parse_aa_pos(pair: Pair<Rule>) -> Result<AAPosition, HgvsError> {
+pub fn parse_aa_pos(pair: Pair<Rule>) -> AAPosition {
     let mut aa = String::new();
     let mut pos = 0;
 
@@ -332,48 +619,56 @@ pub fn parse_aa_pos(pair: Pair<Rule>) -> Result<AAPosition, HgvsError> {
         }
     }
 
-    Ok(AAPosition {
+    AAPosition {
         base: HgvsProteinPos(pos),
         aa,
         uncertain: false,
-    })
+    }
 }
 
-pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
+/// Parses a DNA/RNA edit, degrading to [`NaEdit::None`] with a diagnostic
+/// when the edit node itself is missing.
+pub fn parse_na_edit(pair: Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) -> NaEdit {
+    let span = pair.as_span();
     let mut inner = pair.into_inner();
-    let inner_feat = inner
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty na_edit".into()))?;
+    let Some(inner_feat) = inner.next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a DNA/RNA edit"],
+            "empty na_edit",
+        ));
+        return NaEdit::None;
+    };
     match inner_feat.as_rule() {
         Rule::dna_subst | Rule::rna_subst => {
             let mut parts = inner_feat.into_inner();
             let ref_ = parts.next().map(|p: Pair<Rule>| p.as_str().to_string());
             let alt = parts.next().map(|p: Pair<Rule>| p.as_str().to_string());
-            Ok(NaEdit::RefAlt {
+            NaEdit::RefAlt {
                 ref_,
                 alt,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_del | Rule::rna_del => {
             let ref_ = inner_feat
                 .into_inner()
                 .next()
                 .map(|p: Pair<Rule>| p.as_str().to_string());
-            Ok(NaEdit::Del {
+            NaEdit::Del {
                 ref_,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_ins | Rule::rna_ins => {
             let alt = inner_feat
                 .into_inner()
                 .next()
                 .map(|p: Pair<Rule>| p.as_str().to_string());
-            Ok(NaEdit::Ins {
+            NaEdit::Ins {
                 alt,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_delins | Rule::rna_delins => {
             let mut parts = inner_feat.into_inner();
@@ -383,17 +678,17 @@ pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
                 .unwrap_or_default();
             let second = parts.next().map(|p: Pair<Rule>| p.as_str().to_string());
             if second.is_none() {
-                Ok(NaEdit::RefAlt {
+                NaEdit::RefAlt {
                     ref_: Some("".to_string()),
                     alt: Some(first),
                     uncertain: false,
-                })
+                }
             } else {
-                Ok(NaEdit::RefAlt {
+                NaEdit::RefAlt {
                     ref_: Some(first),
                     alt: second,
                     uncertain: false,
-                })
+                }
             }
         }
         Rule::dna_dup | Rule::rna_dup => {
@@ -401,20 +696,20 @@ pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
                 .into_inner()
                 .next()
                 .map(|p: Pair<Rule>| p.as_str().to_string());
-            Ok(NaEdit::Dup {
+            NaEdit::Dup {
                 ref_,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_inv | Rule::rna_inv => {
             let ref_ = inner_feat
                 .into_inner()
                 .next()
                 .map(|p: Pair<Rule>| p.as_str().to_string());
-            Ok(NaEdit::Inv {
+            NaEdit::Inv {
                 ref_,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_ident | Rule::rna_ident => {
             let mut inner = inner_feat.into_inner();
@@ -424,11 +719,11 @@ pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
                     ref_ = Some(p.as_str().to_string());
                 }
             }
-            Ok(NaEdit::RefAlt {
+            NaEdit::RefAlt {
                 ref_: ref_.clone(),
                 alt: ref_.clone(),
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_repeat | Rule::rna_repeat => {
             let inner = inner_feat.into_inner();
@@ -450,16 +745,31 @@ pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
                 }
             }
             let max = second.unwrap_or(first);
-            Ok(NaEdit::Repeat {
+            NaEdit::Repeat {
                 ref_,
                 min: first,
                 max,
                 uncertain: false,
-            })
+            }
         }
         Rule::dna_con | Rule::rna_con => {
-            // Placeholder/Generic for now as struct support is minimal
-            Ok(NaEdit::None)
+            // STATUS: NOT IMPLEMENTED. The requested `con`-edit support
+            // (chunk6-4: a real `NaEdit::Con` carrying the transposed
+            // source accession + interval, populated from this parse) is
+            // blocked, not delivered -- `edits.rs`, where `NaEdit` lives,
+            // isn't part of this checkout and can't be extended from here.
+            // Do not read the diagnostic below as the feature; it exists
+            // only so a conversion edit reads as "recognized but not yet
+            // representable" instead of silently discarding the parse like
+            // the old placeholder did (indistinguishable from a parse
+            // failure).
+            let span = inner_feat.as_span();
+            diagnostics.push(Diagnostic::error(
+                (span.start(), span.end()),
+                &["NaEdit::Con (not yet available)"],
+                "conversion (con) edits are recognized but cannot be represented until NaEdit gains a Con variant",
+            ));
+            NaEdit::None
         }
         Rule::dna_copy => {
             let mut inner = inner_feat.into_inner();
@@ -467,44 +777,51 @@ pub fn parse_na_edit(pair: Pair<Rule>) -> Result<NaEdit, HgvsError> {
                 .next()
                 .map(|p| p.as_str().parse().unwrap_or(0))
                 .unwrap_or(0);
-            Ok(NaEdit::NACopy {
+            NaEdit::NACopy {
                 copy,
                 uncertain: false,
-            })
+            }
         }
-        _ => Ok(NaEdit::None),
+        _ => NaEdit::None,
     }
 }
 
-pub fn parse_pro_edit(pair: Pair<Rule>) -> Result<AaEdit, HgvsError> {
-    let inner = pair
-        .into_inner()
-        .next()
-        .ok_or_else(|| HgvsError::PestError("Empty pro_edit".into()))?;
+/// Parses a protein edit, degrading to [`AaEdit::None`] with a diagnostic
+/// when the edit node itself is missing.
+pub fn parse_pro_edit(pair: Pair<Rule>, diagnostics: &mut Vec<Diagnostic>) -> AaEdit {
+    let span = pair.as_span();
+    let Some(inner) = pair.into_inner().next() else {
+        diagnostics.push(Diagnostic::error(
+            (span.start(), span.end()),
+            &["a protein edit"],
+            "empty pro_edit",
+        ));
+        return AaEdit::None;
+    };
     match inner.as_rule() {
-        Rule::pro_ident => Ok(AaEdit::Identity { uncertain: false }),
-        Rule::pro_subst => Ok(AaEdit::Subst {
+        Rule::pro_ident => AaEdit::Identity { uncertain: false },
+        Rule::pro_subst => AaEdit::Subst {
             ref_: "".into(),
             alt: inner.as_str().to_string(),
             uncertain: false,
-        }),
-        Rule::pro_del => Ok(AaEdit::Del {
+        },
+        Rule::pro_del => AaEdit::Del {
             ref_: "".into(),
             uncertain: false,
-        }),
-        Rule::pro_ins => Ok(AaEdit::Ins {
+        },
+        Rule::pro_ins => AaEdit::Ins {
             alt: inner
                 .into_inner()
                 .next()
                 .map(|p| p.as_str().to_string())
                 .unwrap_or_default(),
             uncertain: false,
-        }),
-        Rule::pro_dup => Ok(AaEdit::Dup {
+        },
+        Rule::pro_dup => AaEdit::Dup {
             ref_: None,
             uncertain: false,
-        }),
-        Rule::pro_delins => Ok(AaEdit::DelIns {
+        },
+        Rule::pro_delins => AaEdit::DelIns {
             ref_: "".into(),
             alt: inner
                 .into_inner()
@@ -512,7 +829,7 @@ pub fn parse_pro_edit(pair: Pair<Rule>) -> Result<AaEdit, HgvsError> {
                 .map(|p| p.as_str().to_string())
                 .unwrap_or_default(),
             uncertain: false,
-        }),
+        },
         Rule::pro_fs => {
             let mut alt = String::new();
             let mut term = None;
@@ -531,13 +848,13 @@ pub fn parse_pro_edit(pair: Pair<Rule>) -> Result<AaEdit, HgvsError> {
                     _ => {}
                 }
             }
-            Ok(AaEdit::Fs {
+            AaEdit::Fs {
                 ref_: "".into(),
                 alt,
                 term,
                 length,
                 uncertain: false,
-            })
+            }
         }
         Rule::pro_ext => {
             let ref_ = String::new();
@@ -564,13 +881,13 @@ pub fn parse_pro_edit(pair: Pair<Rule>) -> Result<AaEdit, HgvsError> {
                     _ => {}
                 }
             }
-            Ok(AaEdit::Ext {
+            AaEdit::Ext {
                 ref_,
                 alt,
                 aaterm,
                 length,
                 uncertain: false,
-            })
+            }
         }
         Rule::pro_repeat => {
             let inner = inner.into_inner();
@@ -592,19 +909,228 @@ pub fn parse_pro_edit(pair: Pair<Rule>) -> Result<AaEdit, HgvsError> {
                 }
             }
             let max = second.unwrap_or(first);
-            Ok(AaEdit::Repeat {
+            AaEdit::Repeat {
                 ref_,
                 min: first,
                 max,
                 uncertain: false,
-            })
+            }
         }
-        _ => Ok(AaEdit::None),
+        _ => AaEdit::None,
     }
 }
 
+/// How serious a [`Diagnostic`] from the recovering parser is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// One problem the recovering parser ran into: the byte span of the
+/// offending substring in the original input, what was expected there
+/// instead, and a human-readable explanation. Spans are suitable for
+/// editor-style underlining of the offending substring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub span: (usize, usize),
+    pub expected: Vec<String>,
+    pub message: String,
+    pub severity: DiagnosticSeverity,
+}
+
+impl Diagnostic {
+    fn error(span: (usize, usize), expected: &[&str], message: impl Into<String>) -> Self {
+        Self {
+            span,
+            expected: expected.iter().map(|s| s.to_string()).collect(),
+            message: message.into(),
+            severity: DiagnosticSeverity::Error,
+        }
+    }
+}
+
+/// Parsed-as-far-as-possible fragments of an HGVS variant string, produced
+/// by [`parse_hgvs_variant_recovering`] when the input doesn't fully match
+/// the grammar. Any field may be `None` if recovery failed before reaching
+/// that production.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartialVariant {
+    pub accession: Option<String>,
+    pub kind: Option<char>,
+    pub posedit_text: Option<String>,
+}
+
+const HGVS_COORDINATE_KINDS: &[char] = &['g', 'c', 'n', 'm', 'p', 'r'];
+
+/// Error-recovering counterpart to [`crate::parse_hgvs_variant`]. Walks the
+/// same top-level productions (accession, coordinate type, posedit) but on
+/// a mismatch emits a [`Diagnostic`] instead of aborting: a missing `:`
+/// ends recovery with that one diagnostic, while a bad coordinate type
+/// resynchronizes on the next `.` or `_`, so callers get every fragment
+/// that could be recovered plus a diagnostic for each fragment that couldn't.
+/// This lets batch ingestion (e.g. ClinVar) surface every problem in a
+/// variant string at once instead of stopping at the first one.
+///
+/// This operates on the raw string rather than a pest `Pair`, so it can't
+/// yet hand off into [`parse_g_posedit`]/[`parse_c_posedit`]/etc.'s own
+/// diagnostic threading once the position/edit text is reached; it falls
+/// back to [`crate::parse_hgvs_variant`] for that final check and folds any
+/// failure into a single diagnostic spanning the posedit text.
+pub fn parse_hgvs_variant_recovering(input: &str) -> (PartialVariant, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let mut partial = PartialVariant::default();
+
+    let (accession_part, rest) = match input.split_once(':') {
+        Some((acc, rest)) => (acc, rest),
+        None => {
+            diagnostics.push(Diagnostic::error(
+                (0, input.len()),
+                &[":"],
+                "expected ':' separating accession from coordinate type",
+            ));
+            return (partial, diagnostics);
+        }
+    };
+    partial.accession = Some(accession_part.to_string());
+    let rest_start = accession_part.len() + 1;
+
+    let kind = rest.chars().next();
+    let kind_len = kind.map_or(0, |c| c.len_utf8());
+    match kind {
+        Some(c) if HGVS_COORDINATE_KINDS.contains(&c) => partial.kind = Some(c),
+        _ => diagnostics.push(Diagnostic::error(
+            (rest_start, rest_start + kind_len),
+            &["g", "c", "n", "m", "p", "r"],
+            format!("unrecognized coordinate type {:?}, expected one of g/c/n/m/p/r", kind),
+        )),
+    }
+
+    let after_kind = &rest[kind_len..];
+    let dot_start = rest_start + kind_len;
+    if !after_kind.starts_with('.') {
+        diagnostics.push(Diagnostic::error(
+            (dot_start, dot_start + 1),
+            &["."],
+            "expected '.' after coordinate type",
+        ));
+        // Try to resynchronize on the next sync token so a caller batching
+        // many malformed strings still gets whatever comes after.
+        let resync = after_kind.find(['.', '_']);
+        let Some(resync) = resync else {
+            return (partial, diagnostics);
+        };
+        let posedit_text = &after_kind[resync + 1..];
+        partial.posedit_text = Some(posedit_text.to_string());
+    } else {
+        partial.posedit_text = Some(after_kind[1..].to_string());
+    }
+
+    if let Err(e) = crate::parse_hgvs_variant(input) {
+        let posedit_start = dot_start + 1;
+        diagnostics.push(Diagnostic::error(
+            (posedit_start, input.len()),
+            &["a valid position/edit"],
+            format!("could not parse position/edit: {e}"),
+        ));
+    }
+
+    (partial, diagnostics)
+}
+
+/// Parses a single- or multi-variant (cis allele) HGVS description into its
+/// ordered list of component variants.
+///
+/// `ac:kind.[edit1;edit2;...]` is split on `;` inside the brackets, and each
+/// component is reassembled into a standalone `ac:kind.editN` string and
+/// parsed via [`crate::parse_hgvs_variant`], preserving input order. A plain
+/// single-edit description (no brackets) parses to a one-element vec, so
+/// callers can treat every description uniformly as an allele.
+///
+/// This does not produce a single composite [`SequenceVariant`] for an
+/// allele -- that type is defined in `coords.rs` and has no variant for a
+/// multi-edit allele yet -- so a cis allele is represented here as the
+/// ordered `Vec` of its components instead.
+pub fn parse_allele(input: &str) -> Result<Vec<SequenceVariant>, HgvsError> {
+    let (accession_part, rest) = input.split_once(':').ok_or_else(|| {
+        HgvsError::ValidationError("expected ':' separating accession from coordinate type".into())
+    })?;
+
+    let mut kind_chars = rest.char_indices();
+    let (_, kind) = kind_chars.next().ok_or_else(|| {
+        HgvsError::ValidationError("missing coordinate type after accession".into())
+    })?;
+    let after_kind = &rest[kind.len_utf8()..];
+    let after_dot = after_kind.strip_prefix('.').ok_or_else(|| {
+        HgvsError::ValidationError("expected '.' after coordinate type".into())
+    })?;
+
+    let Some(inner) = after_dot
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+    else {
+        return crate::parse_hgvs_variant(input).map(|v| vec![v]);
+    };
+
+    if inner.contains("];[") {
+        return Err(HgvsError::ValidationError(
+            "in-trans allele notation '[...];[...]' is not a cis allele; use parse_trans_allele"
+                .into(),
+        ));
+    }
+
+    inner
+        .split(';')
+        .map(|component| {
+            let single = format!("{accession_part}:{kind}.{}", component.trim());
+            crate::parse_hgvs_variant(&single)
+        })
+        .collect()
+}
+
+/// Parses in-trans HGVS allele notation, `ac:kind.[...];[...]`, into its two
+/// independently-phased component groups. Each side of the `];[` split is
+/// itself a cis allele per [`parse_allele`]'s grammar, so
+/// `c.[4A>T;5G>C];[7T>G]` splits into a two-component first group and a
+/// one-component second group -- members within a group sit on the same
+/// molecule and are interpreted jointly (see
+/// [`crate::altseq::AltSeqBuilder::build_cis_allele_altseq`]); the two groups
+/// sit on different molecules and are interpreted independently (see
+/// [`crate::mapper::VariantMapper::c_to_p_trans`]).
+pub fn parse_trans_allele(input: &str) -> Result<(Vec<SequenceVariant>, Vec<SequenceVariant>), HgvsError> {
+    let (accession_part, rest) = input.split_once(':').ok_or_else(|| {
+        HgvsError::ValidationError("expected ':' separating accession from coordinate type".into())
+    })?;
+
+    let mut kind_chars = rest.char_indices();
+    let (_, kind) = kind_chars.next().ok_or_else(|| {
+        HgvsError::ValidationError("missing coordinate type after accession".into())
+    })?;
+    let after_kind = &rest[kind.len_utf8()..];
+    let after_dot = after_kind.strip_prefix('.').ok_or_else(|| {
+        HgvsError::ValidationError("expected '.' after coordinate type".into())
+    })?;
+
+    let (first, second) = after_dot.split_once("];[").ok_or_else(|| {
+        HgvsError::ValidationError(
+            "expected in-trans allele notation '[...];[...]'".into(),
+        )
+    })?;
+    let first = first.strip_prefix('[').ok_or_else(|| {
+        HgvsError::ValidationError("expected '[' starting the first trans group".into())
+    })?;
+    let second = second.strip_suffix(']').ok_or_else(|| {
+        HgvsError::ValidationError("expected ']' closing the second trans group".into())
+    })?;
+
+    let group1 = parse_allele(&format!("{accession_part}:{kind}.[{first}]"))?;
+    let group2 = parse_allele(&format!("{accession_part}:{kind}.[{second}]"))?;
+    Ok((group1, group2))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{parse_allele, parse_hgvs_variant_recovering, parse_trans_allele, DiagnosticSeverity};
     use crate::parse_hgvs_variant;
 
     #[test]
@@ -644,6 +1170,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_con_is_blocked_not_implemented() {
+        // chunk6-4 asked for a real `NaEdit::Con` populated from the
+        // dna_con/rna_con parse, mirroring `test_parse_repeats`. That is
+        // NOT done here: `NaEdit` has no `Con` variant (it would need to
+        // live in `edits.rs`, which this checkout can't extend), so
+        // conversion edits still parse down to `NaEdit::None`. This test
+        // pins down the current, honest limitation -- it is not a
+        // substitute for the requested feature, and chunk6-4 should stay
+        // open rather than counted as delivered.
+        let v_g = parse_hgvs_variant("NC_000001.11:g.123_456con789_1012").unwrap();
+        match v_g {
+            crate::coords::SequenceVariant::Genomic(v) => {
+                assert_eq!(v.posedit.edit, crate::edits::NaEdit::None);
+            }
+            _ => panic!("Expected Genomic variant"),
+        }
+
+        let v_c = parse_hgvs_variant("NM_000123.4:c.123_456con789_1012").unwrap();
+        match v_c {
+            crate::coords::SequenceVariant::Coding(v) => {
+                assert_eq!(v.posedit.edit, crate::edits::NaEdit::None);
+            }
+            _ => panic!("Expected Coding variant"),
+        }
+    }
+
+    #[test]
+    fn test_recovering_parse_valid_input_has_no_diagnostics() {
+        let (partial, diagnostics) = parse_hgvs_variant_recovering("NM_000123.4:c.2_3insC");
+        assert!(diagnostics.is_empty());
+        assert_eq!(partial.accession, Some("NM_000123.4".to_string()));
+        assert_eq!(partial.kind, Some('c'));
+        assert_eq!(partial.posedit_text, Some("2_3insC".to_string()));
+    }
+
+    #[test]
+    fn test_recovering_parse_recovers_fragments_around_bad_edit() {
+        // The edit ("Tyrrr165Ter") is malformed, but the accession and
+        // coordinate type should still come back.
+        let (partial, diagnostics) = parse_hgvs_variant_recovering("NP_000001.1:p.Tyrrr165Ter");
+        assert_eq!(partial.accession, Some("NP_000001.1".to_string()));
+        assert_eq!(partial.kind, Some('p'));
+        assert_eq!(partial.posedit_text, Some("Tyrrr165Ter".to_string()));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+    }
+
+    #[test]
+    fn test_recovering_parse_missing_colon() {
+        let (partial, diagnostics) = parse_hgvs_variant_recovering("NM_000123.4c.2_3insC");
+        assert_eq!(partial.accession, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].expected, vec![":".to_string()]);
+    }
+
     #[test]
     fn test_parse_extension() {
         let v_p = parse_hgvs_variant("NP_001116078.1:p.Ter312Argext*5").unwrap();
@@ -666,4 +1248,75 @@ mod tests {
             _ => panic!("Expected Protein variant"),
         }
     }
+
+    #[test]
+    fn test_parse_allele_splits_cis_edits_in_order() {
+        let components = parse_allele("NM_000123.4:c.[76A>C;83G>T]").unwrap();
+        assert_eq!(components.len(), 2);
+        match &components[0] {
+            crate::coords::SequenceVariant::Coding(v) => {
+                assert_eq!(v.posedit.to_string(), "76A>C")
+            }
+            _ => panic!("Expected Coding variant"),
+        }
+        match &components[1] {
+            crate::coords::SequenceVariant::Coding(v) => {
+                assert_eq!(v.posedit.to_string(), "83G>T")
+            }
+            _ => panic!("Expected Coding variant"),
+        }
+    }
+
+    #[test]
+    fn test_parse_allele_single_edit_has_one_component() {
+        let components = parse_allele("NM_000123.4:c.76A>C").unwrap();
+        assert_eq!(components.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_allele_rejects_trans_notation() {
+        let err = parse_allele("NM_000123.4:c.[4A>T];[7T>G]").unwrap_err();
+        assert!(err.to_string().contains("parse_trans_allele"));
+    }
+
+    #[test]
+    fn test_parse_trans_allele_splits_independent_groups() {
+        let (group1, group2) = parse_trans_allele("NM_000123.4:c.[4A>T];[7T>G]").unwrap();
+        assert_eq!(group1.len(), 1);
+        assert_eq!(group2.len(), 1);
+        match &group1[0] {
+            crate::coords::SequenceVariant::Coding(v) => assert_eq!(v.posedit.to_string(), "4A>T"),
+            _ => panic!("Expected Coding variant"),
+        }
+        match &group2[0] {
+            crate::coords::SequenceVariant::Coding(v) => assert_eq!(v.posedit.to_string(), "7T>G"),
+            _ => panic!("Expected Coding variant"),
+        }
+    }
+
+    #[test]
+    fn test_parse_trans_allele_allows_multi_member_group() {
+        let (group1, group2) =
+            parse_trans_allele("NM_000123.4:c.[4A>T;5G>C];[7T>G]").unwrap();
+        assert_eq!(group1.len(), 2);
+        assert_eq!(group2.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_uncertain_coding_interval_marks_interval_uncertain() {
+        // A multi-exon deletion whose breakpoints aren't known to the base
+        // pair is reported as `c.(4_100)del` -- the whole span in parens,
+        // not a per-position uncertainty like `g.(?_100)`.
+        let v_c = parse_hgvs_variant("NM_000123.4:c.(4_100)del").unwrap();
+        match v_c {
+            crate::coords::SequenceVariant::Coding(v) => {
+                let pos = v.posedit.pos.unwrap();
+                assert!(pos.uncertain);
+                assert_eq!(pos.start.base.0, 4);
+                assert_eq!(pos.end.unwrap().base.0, 100);
+                assert_eq!(v.posedit.edit, crate::edits::NaEdit::Del { ref_: None, uncertain: false });
+            }
+            _ => panic!("Expected Coding variant"),
+        }
+    }
 }
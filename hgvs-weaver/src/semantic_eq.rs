@@ -0,0 +1,323 @@
+//! Structural equality for [`NaEdit`]/[`AaEdit`] that treats spelling
+//! variants of the same change as equal, rather than comparing the enum
+//! discriminant and every field verbatim. This mirrors the nodeless
+//! AST-equality helpers used for pattern-matching lints: a dedicated set of
+//! comparison functions that canonicalize incidental representation
+//! differences away before comparing, instead of asking every caller to
+//! normalize edits themselves.
+//!
+//! Canonicalization folds:
+//! - `Del`/`Dup`/`Inv`/`Repeat` with `ref_: Some(_)` vs `ref_: None` (an
+//!   explicit reference sequence is incidental to *which* edit it is).
+//! - `RefAlt { ref_: Some(""), alt: Some(x) }` (how [`parser::parse_na_edit`]
+//!   folds a one-operand `delins`) vs `Ins { alt: Some(x) }`.
+//! - `RefAlt { ref_: Some(r), alt: Some(a) }` with `r == a` (a `dna_ident`
+//!   with no actual change) vs `RefAlt` with both `None`.
+//! - `Dup { ref_: r }` vs `Ins { alt: r }` (duplicating `r` inserts another
+//!   copy of it right after itself).
+//!
+//! By default `uncertain` is ignored, since two edits usually denote "the
+//! same change" regardless of whether either side is flagged uncertain; pass
+//! `strict: true` to require `uncertain` to match too.
+//!
+//! [`parser::parse_na_edit`]: crate::parser::parse_na_edit
+
+use crate::structs::{AaEdit, NaEdit};
+
+/// Compares two [`NaEdit`]s for semantic equivalence. See the module docs for
+/// exactly which spelling differences are folded away.
+pub fn semantic_eq(a: &NaEdit, b: &NaEdit) -> bool {
+    na_eq(a, b, false)
+}
+
+/// Like [`semantic_eq`], but two edits that differ only in their `uncertain`
+/// flag are *not* considered equivalent.
+pub fn semantic_eq_strict(a: &NaEdit, b: &NaEdit) -> bool {
+    na_eq(a, b, true)
+}
+
+/// Compares two [`AaEdit`]s for semantic equivalence.
+pub fn semantic_eq_aa(a: &AaEdit, b: &AaEdit) -> bool {
+    aa_eq(a, b, false)
+}
+
+/// Like [`semantic_eq_aa`], but respects the `uncertain` flag.
+pub fn semantic_eq_aa_strict(a: &AaEdit, b: &AaEdit) -> bool {
+    aa_eq(a, b, true)
+}
+
+/// Two optional reference/repeat-unit strings are equivalent if either is
+/// absent (an implicit reference is incidental to which edit it is) or if
+/// they're textually equal.
+fn ref_compatible(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+fn is_refalt_noop(ref_: Option<&str>, alt: Option<&str>) -> bool {
+    match (ref_, alt) {
+        (None, None) => true,
+        (Some(r), Some(a)) => r == a,
+        _ => false,
+    }
+}
+
+/// Views `edit` as a `(ref, alt)` replacement pair when it's one of the
+/// variants that can denote a no-op (`RefAlt`, `Ins`, `None`). Returns `None`
+/// for variants (`Del`, `Dup`, `Inv`, `Repeat`) where an absent `alt` means
+/// something different (pure deletion) rather than "no alt recorded yet", so
+/// those are compared by their own explicit arm in [`na_eq`] instead.
+fn refalt_view(edit: &NaEdit) -> Option<(Option<&str>, Option<&str>)> {
+    match edit {
+        // A one-operand `delins` is folded by `parse_na_edit` into
+        // `RefAlt { ref_: Some(""), .. }`; treat an empty explicit ref_ the
+        // same as an absent one so it lines up with `Ins`'s `(None, alt)`.
+        NaEdit::RefAlt { ref_, alt, .. } => {
+            Some((ref_.as_deref().filter(|r| !r.is_empty()), alt.as_deref()))
+        }
+        NaEdit::Ins { alt, .. } => Some((None, alt.as_deref())),
+        NaEdit::None => Some((None, None)),
+        _ => None,
+    }
+}
+
+fn na_uncertain(edit: &NaEdit) -> bool {
+    match edit {
+        NaEdit::RefAlt { uncertain, .. }
+        | NaEdit::Del { uncertain, .. }
+        | NaEdit::Ins { uncertain, .. }
+        | NaEdit::Dup { uncertain, .. }
+        | NaEdit::Inv { uncertain, .. }
+        | NaEdit::Repeat { uncertain, .. } => *uncertain,
+        _ => false,
+    }
+}
+
+fn na_eq(a: &NaEdit, b: &NaEdit, strict: bool) -> bool {
+    if strict && na_uncertain(a) != na_uncertain(b) {
+        return false;
+    }
+
+    if let (Some((ref_a, alt_a)), Some((ref_b, alt_b))) = (refalt_view(a), refalt_view(b)) {
+        let noop_a = is_refalt_noop(ref_a, alt_a);
+        let noop_b = is_refalt_noop(ref_b, alt_b);
+        if noop_a || noop_b {
+            return noop_a == noop_b;
+        }
+        return ref_compatible(ref_a, ref_b) && alt_a == alt_b;
+    }
+
+    match (a, b) {
+        (NaEdit::Del { ref_: ra, .. }, NaEdit::Del { ref_: rb, .. }) => {
+            ref_compatible(ra.as_deref(), rb.as_deref())
+        }
+        (NaEdit::Inv { ref_: ra, .. }, NaEdit::Inv { ref_: rb, .. }) => {
+            ref_compatible(ra.as_deref(), rb.as_deref())
+        }
+        (NaEdit::Dup { ref_: ra, .. }, NaEdit::Dup { ref_: rb, .. }) => {
+            ref_compatible(ra.as_deref(), rb.as_deref())
+        }
+        // A duplication of `ref_` is, net effect, an insertion of another
+        // copy of `ref_` right after it.
+        (NaEdit::Dup { ref_: ra, .. }, NaEdit::Ins { alt: ab, .. })
+        | (NaEdit::Ins { alt: ab, .. }, NaEdit::Dup { ref_: ra, .. }) => {
+            ref_compatible(ra.as_deref(), ab.as_deref())
+        }
+        (
+            NaEdit::Repeat {
+                ref_: ra,
+                min: min_a,
+                max: max_a,
+                ..
+            },
+            NaEdit::Repeat {
+                ref_: rb,
+                min: min_b,
+                max: max_b,
+                ..
+            },
+        ) => ref_compatible(ra.as_deref(), rb.as_deref()) && min_a == min_b && max_a == max_b,
+        _ => false,
+    }
+}
+
+fn aa_eq(a: &AaEdit, b: &AaEdit, strict: bool) -> bool {
+    if strict && aa_uncertain(a) != aa_uncertain(b) {
+        return false;
+    }
+    match (a, b) {
+        (AaEdit::Identity { .. }, AaEdit::Identity { .. }) => true,
+        (AaEdit::Subst { alt: a, .. }, AaEdit::Subst { alt: b, .. }) => a == b,
+        (AaEdit::Del { .. }, AaEdit::Del { .. }) => true,
+        (AaEdit::Ins { alt: a, .. }, AaEdit::Ins { alt: b, .. }) => a == b,
+        (AaEdit::Dup { .. }, AaEdit::Dup { .. }) => true,
+        (AaEdit::DelIns { alt: a, .. }, AaEdit::DelIns { alt: b, .. }) => a == b,
+        (
+            AaEdit::Fs {
+                alt: a,
+                term: ta,
+                length: la,
+                ..
+            },
+            AaEdit::Fs {
+                alt: b,
+                term: tb,
+                length: lb,
+                ..
+            },
+        ) => a == b && ta == tb && la == lb,
+        (
+            AaEdit::Ext {
+                alt: a,
+                aaterm: ta,
+                length: la,
+                ..
+            },
+            AaEdit::Ext {
+                alt: b,
+                aaterm: tb,
+                length: lb,
+                ..
+            },
+        ) => a == b && ta == tb && la == lb,
+        (
+            AaEdit::Repeat {
+                ref_: ra,
+                min: mina,
+                max: maxa,
+                ..
+            },
+            AaEdit::Repeat {
+                ref_: rb,
+                min: minb,
+                max: maxb,
+                ..
+            },
+        ) => ref_compatible(ra.as_deref(), rb.as_deref()) && mina == minb && maxa == maxb,
+        (AaEdit::Special { value: a, .. }, AaEdit::Special { value: b, .. }) => a == b,
+        (AaEdit::None, AaEdit::None) => true,
+        _ => false,
+    }
+}
+
+fn aa_uncertain(edit: &AaEdit) -> bool {
+    match edit {
+        AaEdit::Identity { uncertain }
+        | AaEdit::Subst { uncertain, .. }
+        | AaEdit::Del { uncertain, .. }
+        | AaEdit::Ins { uncertain, .. }
+        | AaEdit::Dup { uncertain, .. }
+        | AaEdit::DelIns { uncertain, .. }
+        | AaEdit::Fs { uncertain, .. }
+        | AaEdit::Ext { uncertain, .. }
+        | AaEdit::Repeat { uncertain, .. }
+        | AaEdit::Special { uncertain, .. } => *uncertain,
+        AaEdit::None => false,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delins_single_operand_equals_ins() {
+        let delins = NaEdit::RefAlt {
+            ref_: Some("".to_string()),
+            alt: Some("AC".to_string()),
+            uncertain: false,
+        };
+        let ins = NaEdit::Ins {
+            alt: Some("AC".to_string()),
+            uncertain: false,
+        };
+        assert!(semantic_eq(&delins, &ins));
+    }
+
+    #[test]
+    fn test_dna_ident_equals_explicit_no_change() {
+        let ident = NaEdit::RefAlt {
+            ref_: Some("A".to_string()),
+            alt: Some("A".to_string()),
+            uncertain: false,
+        };
+        let explicit = NaEdit::RefAlt {
+            ref_: None,
+            alt: None,
+            uncertain: false,
+        };
+        assert!(semantic_eq(&ident, &explicit));
+    }
+
+    #[test]
+    fn test_del_ignores_explicit_vs_implicit_reference() {
+        let with_ref = NaEdit::Del {
+            ref_: Some("A".to_string()),
+            uncertain: false,
+        };
+        let without_ref = NaEdit::Del {
+            ref_: None,
+            uncertain: false,
+        };
+        assert!(semantic_eq(&with_ref, &without_ref));
+    }
+
+    #[test]
+    fn test_del_disagrees_on_conflicting_reference() {
+        let a = NaEdit::Del {
+            ref_: Some("A".to_string()),
+            uncertain: false,
+        };
+        let b = NaEdit::Del {
+            ref_: Some("G".to_string()),
+            uncertain: false,
+        };
+        assert!(!semantic_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_uncertain_ignored_by_default_but_not_in_strict_mode() {
+        let a = NaEdit::Dup {
+            ref_: Some("AC".to_string()),
+            uncertain: false,
+        };
+        let b = NaEdit::Dup {
+            ref_: Some("AC".to_string()),
+            uncertain: true,
+        };
+        assert!(semantic_eq(&a, &b));
+        assert!(!semantic_eq_strict(&a, &b));
+    }
+
+    #[test]
+    fn test_dup_equals_equivalent_insertion() {
+        let dup = NaEdit::Dup {
+            ref_: Some("AC".to_string()),
+            uncertain: false,
+        };
+        let ins = NaEdit::Ins {
+            alt: Some("AC".to_string()),
+            uncertain: false,
+        };
+        assert!(semantic_eq(&dup, &ins));
+    }
+
+    #[test]
+    fn test_aa_subst_semantic_eq_ignores_uncertain_unless_strict() {
+        let a = AaEdit::Subst {
+            ref_: "".to_string(),
+            alt: "Cys".to_string(),
+            uncertain: false,
+        };
+        let b = AaEdit::Subst {
+            ref_: "".to_string(),
+            alt: "Cys".to_string(),
+            uncertain: true,
+        };
+        assert!(semantic_eq_aa(&a, &b));
+        assert!(!semantic_eq_aa_strict(&a, &b));
+    }
+}
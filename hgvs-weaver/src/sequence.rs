@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
 use crate::error::HgvsError;
 use crate::data::{DataProvider, IdentifierType};
 
@@ -58,6 +61,242 @@ impl Sequence for MemSequence {
     }
 }
 
+/// A 2-bit packed DNA sequence (A=0, C=1, G=2, T=3) giving O(1) base and
+/// codon access without the repeated allocation/slicing `MemSequence`
+/// incurs when `VariantMapper` pulls codons out of a CDS window.
+///
+/// Falls back to a boxed string for inputs containing non-ACGT characters
+/// (N, IUPAC ambiguity codes, RNA `U`) so those sequences still translate
+/// correctly, just without the packed fast path.
+pub enum PackedDnaSequence {
+    Packed { bits: Box<[u8]>, len: usize },
+    Fallback(Box<str>),
+}
+
+impl PackedDnaSequence {
+    pub fn new(s: &str) -> Self {
+        let mut bits = Vec::with_capacity(s.len() / 4 + 1);
+        let mut byte = 0u8;
+        let mut filled = 0u8;
+        for c in s.chars() {
+            let code = match c {
+                'A' => 0u8,
+                'C' => 1,
+                'G' => 2,
+                'T' => 3,
+                _ => return PackedDnaSequence::Fallback(s.into()),
+            };
+            byte |= code << (filled * 2);
+            filled += 1;
+            if filled == 4 {
+                bits.push(byte);
+                byte = 0;
+                filled = 0;
+            }
+        }
+        if filled > 0 {
+            bits.push(byte);
+        }
+        PackedDnaSequence::Packed {
+            bits: bits.into_boxed_slice(),
+            len: s.len(),
+        }
+    }
+
+    /// O(1) access to the base at `idx`. Panics if `idx` is out of bounds,
+    /// same as indexing a `String`.
+    pub fn base_at(&self, idx: usize) -> char {
+        match self {
+            PackedDnaSequence::Packed { bits, len } => {
+                assert!(idx < *len, "index {idx} out of bounds for length {len}");
+                let code = (bits[idx / 4] >> ((idx % 4) * 2)) & 0b11;
+                match code {
+                    0 => 'A',
+                    1 => 'C',
+                    2 => 'G',
+                    _ => 'T',
+                }
+            }
+            PackedDnaSequence::Fallback(s) => s.as_bytes()[idx] as char,
+        }
+    }
+
+    /// Translates the codon at `idx..idx+3` directly from the packed bases,
+    /// without allocating an intermediate `String`/`Vec<char>` the way
+    /// `TranslatedSequenceWithTable` does for a generic `dyn Sequence`.
+    /// Returns `None` once the codon would run past the end of the sequence.
+    pub fn translate_codon_at(
+        &self,
+        idx: usize,
+        table: crate::genetic_code::GeneticCodeTable,
+    ) -> Option<char> {
+        if idx + 3 > self.len() {
+            return None;
+        }
+        Some(table.translate_codon([
+            self.base_at(idx),
+            self.base_at(idx + 1),
+            self.base_at(idx + 2),
+        ]))
+    }
+
+    /// Like [`Self::translate_codon_at`], but overrides a stop to
+    /// selenocysteine (`'U'`) when `is_recoded_tga` marks this codon as an
+    /// annotated [`crate::genetic_code::SelenocysteineSites`] member. See
+    /// [`crate::genetic_code::apply_selenocysteine_recoding`].
+    pub fn translate_codon_at_with_recoding(
+        &self,
+        idx: usize,
+        table: crate::genetic_code::GeneticCodeTable,
+        is_recoded_tga: bool,
+    ) -> Option<char> {
+        let aa = self.translate_codon_at(idx, table)?;
+        let codon = [self.base_at(idx), self.base_at(idx + 1), self.base_at(idx + 2)];
+        Some(crate::genetic_code::apply_selenocysteine_recoding(codon, aa, is_recoded_tga))
+    }
+}
+
+impl Sequence for PackedDnaSequence {
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
+        Box::new((0..self.len()).map(move |i| self.base_at(i)))
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            PackedDnaSequence::Packed { len, .. } => *len,
+            PackedDnaSequence::Fallback(s) => s.len(),
+        }
+    }
+}
+
+/// A 2-bit packed sequence with a compact binary serialization.
+///
+/// Unlike [`PackedDnaSequence`], which falls back to a boxed string the
+/// moment it sees a non-ACGT character, `PackedSequence` keeps packing
+/// everything else as 2-bit codes and records the handful of non-ACGT
+/// positions (N runs, IUPAC ambiguity codes) as `(position, symbol)`
+/// exceptions overlaid on top. That keeps mostly-clean sequences compact
+/// even when they contain the occasional ambiguity code.
+///
+/// [`Self::to_bytes`]/[`Self::from_bytes`] round-trip the packed form to a
+/// binary layout: an 8-byte little-endian length, then `ceil(len/4)` packed
+/// bytes (bases packed MSB-pair-first -- base 0 of each byte occupies bits
+/// 6-7), then a 4-byte little-endian exception count followed by that many
+/// `(4-byte little-endian position, 1-byte symbol)` pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedSequence {
+    bits: Box<[u8]>,
+    len: usize,
+    /// Sorted by position (iteration over `seq.len()` positions is
+    /// monotonic), so `base_at` can binary-search it.
+    exceptions: Vec<(u32, u8)>,
+}
+
+impl PackedSequence {
+    /// Packs `seq` into 2-bit codes, recording any non-ACGT base as an
+    /// exception rather than giving up on packing entirely.
+    pub fn encode(seq: &dyn Sequence) -> Result<PackedSequence, HgvsError> {
+        let len = seq.len();
+        let mut bits = vec![0u8; (len + 3) / 4];
+        let mut exceptions = Vec::new();
+        for (idx, c) in seq.iter().enumerate() {
+            let code = match c {
+                'A' => 0u8,
+                'C' => 1,
+                'G' => 2,
+                'T' => 3,
+                _ => {
+                    if !c.is_ascii() {
+                        return Err(HgvsError::ValidationError(format!(
+                            "PackedSequence only supports ASCII symbols, found {c:?} at position {idx}"
+                        )));
+                    }
+                    exceptions.push((idx as u32, c as u8));
+                    0
+                }
+            };
+            bits[idx / 4] |= code << (6 - (idx % 4) * 2);
+        }
+        Ok(PackedSequence { bits: bits.into_boxed_slice(), len, exceptions })
+    }
+
+    /// O(1) packed lookup plus an O(log n) exception-list probe.
+    pub fn base_at(&self, idx: usize) -> char {
+        assert!(idx < self.len, "index {idx} out of bounds for length {}", self.len);
+        if let Ok(pos) = self.exceptions.binary_search_by_key(&(idx as u32), |(p, _)| *p) {
+            return self.exceptions[pos].1 as char;
+        }
+        let code = (self.bits[idx / 4] >> (6 - (idx % 4) * 2)) & 0b11;
+        match code {
+            0 => 'A',
+            1 => 'C',
+            2 => 'G',
+            _ => 'T',
+        }
+    }
+
+    /// Serializes to the binary layout documented on [`PackedSequence`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(8 + self.bits.len() + 4 + self.exceptions.len() * 5);
+        out.extend_from_slice(&(self.len as u64).to_le_bytes());
+        out.extend_from_slice(&self.bits);
+        out.extend_from_slice(&(self.exceptions.len() as u32).to_le_bytes());
+        for (pos, symbol) in &self.exceptions {
+            out.extend_from_slice(&pos.to_le_bytes());
+            out.push(*symbol);
+        }
+        out
+    }
+
+    /// Deserializes the layout written by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<PackedSequence, HgvsError> {
+        let truncated = || HgvsError::ValidationError("truncated PackedSequence byte stream".into());
+
+        if bytes.len() < 8 {
+            return Err(truncated());
+        }
+        let len = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+        let mut offset = 8;
+
+        let packed_len = (len + 3) / 4;
+        let bits_end = offset + packed_len;
+        if bytes.len() < bits_end {
+            return Err(truncated());
+        }
+        let bits = bytes[offset..bits_end].to_vec().into_boxed_slice();
+        offset = bits_end;
+
+        if bytes.len() < offset + 4 {
+            return Err(truncated());
+        }
+        let exception_count = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        offset += 4;
+
+        let mut exceptions = Vec::with_capacity(exception_count);
+        for _ in 0..exception_count {
+            if bytes.len() < offset + 5 {
+                return Err(truncated());
+            }
+            let pos = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+            let symbol = bytes[offset + 4];
+            exceptions.push((pos, symbol));
+            offset += 5;
+        }
+
+        Ok(PackedSequence { bits, len, exceptions })
+    }
+}
+
+impl Sequence for PackedSequence {
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
+        Box::new((0..self.len).map(move |i| self.base_at(i)))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
 /// A sequence that is fetched lazily from a data provider.
 pub struct LazySequence<'a> {
     pub hdp: &'a dyn DataProvider,
@@ -78,6 +317,91 @@ impl<'a> Sequence for LazySequence<'a> {
     }
 }
 
+/// A sequence that is fetched in fixed-size windows instead of all at once,
+/// so scanning a chromosome-scale range doesn't require [`LazySequence`]'s
+/// single `get_seq` call to materialize the entire range into memory up front.
+///
+/// `fetch` is called with `(window_start, window_end)` each time the
+/// internal buffer runs dry; it's generic rather than tied to
+/// `&dyn DataProvider` so callers can wrap any source (a provider, a cache,
+/// a test fixture) without an adapter type.
+pub struct ChunkedSequence<F>
+where
+    F: FnMut(usize, usize) -> Result<String, HgvsError>,
+{
+    fetch: RefCell<F>,
+    start: usize,
+    end: usize,
+    chunk_size: usize,
+}
+
+impl<F> ChunkedSequence<F>
+where
+    F: FnMut(usize, usize) -> Result<String, HgvsError>,
+{
+    /// `chunk_size` is the number of bases pulled per `fetch` call; the
+    /// request this adapter was built for expects something in the
+    /// 8KB-64KB range, but any positive value works.
+    pub fn new(start: usize, end: usize, chunk_size: usize, fetch: F) -> Self {
+        ChunkedSequence {
+            fetch: RefCell::new(fetch),
+            start,
+            end,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+}
+
+impl<F> Sequence for ChunkedSequence<F>
+where
+    F: FnMut(usize, usize) -> Result<String, HgvsError>,
+{
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
+        Box::new(ChunkedIterator {
+            fetch: &self.fetch,
+            pos: self.start,
+            end: self.end,
+            chunk_size: self.chunk_size,
+            buffer: VecDeque::new(),
+            errored: false,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+struct ChunkedIterator<'a, F: FnMut(usize, usize) -> Result<String, HgvsError>> {
+    fetch: &'a RefCell<F>,
+    pos: usize,
+    end: usize,
+    chunk_size: usize,
+    buffer: VecDeque<char>,
+    errored: bool,
+}
+
+impl<'a, F: FnMut(usize, usize) -> Result<String, HgvsError>> Iterator for ChunkedIterator<'a, F> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.buffer.is_empty() && !self.errored && self.pos < self.end {
+            let window_end = (self.pos + self.chunk_size).min(self.end);
+            match (self.fetch.borrow_mut())(self.pos, window_end) {
+                Ok(window) => {
+                    self.buffer.extend(window.chars());
+                    self.pos = window_end;
+                }
+                // Matches LazySequence::iter's unwrap_or_default: a fetch
+                // failure surfaces as premature end-of-sequence rather than
+                // a panic, since Iterator::next can't return a Result.
+                Err(_) => self.errored = true,
+            }
+        }
+        self.buffer.pop_front()
+    }
+}
+
 /// Adapter for reverse-complementation.
 pub struct RevCompSequence<'a> {
     pub inner: &'a dyn Sequence,
@@ -85,12 +409,7 @@ pub struct RevCompSequence<'a> {
 
 impl<'a> Sequence for RevCompSequence<'a> {
     fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
-        Box::new(self.inner.iter().collect::<Vec<_>>().into_iter().rev().map(|c| match c {
-            'A' => 'T', 'T' => 'A', 'C' => 'G', 'G' => 'C', 'N' => 'N',
-            'a' => 't', 't' => 'a', 'c' => 'g', 'g' => 'c', 'n' => 'n',
-            'U' => 'A', 'u' => 'a',
-            _ => c
-        }))
+        Box::new(self.inner.iter().collect::<Vec<_>>().into_iter().rev().map(complement_dna_char))
     }
 
     fn len(&self) -> usize {
@@ -98,6 +417,36 @@ impl<'a> Sequence for RevCompSequence<'a> {
     }
 }
 
+/// Complements a single base, including the full IUPAC ambiguity set
+/// (`R`<->`Y`, `S`<->`S`, `W`<->`W`, `K`<->`M`, `B`<->`V`, `D`<->`H`), case
+/// preserved. `U` complements to `A` like `T` does; anything else passes
+/// through unchanged.
+pub fn complement_dna_char(c: char) -> char {
+    let complement = match c.to_ascii_uppercase() {
+        'A' => 'T',
+        'T' | 'U' => 'A',
+        'C' => 'G',
+        'G' => 'C',
+        'R' => 'Y',
+        'Y' => 'R',
+        'S' => 'S',
+        'W' => 'W',
+        'K' => 'M',
+        'M' => 'K',
+        'B' => 'V',
+        'V' => 'B',
+        'D' => 'H',
+        'H' => 'D',
+        'N' => 'N',
+        _ => return c,
+    };
+    if c.is_ascii_lowercase() {
+        complement.to_ascii_lowercase()
+    } else {
+        complement
+    }
+}
+
 /// Adapter for transcription (T -> U).
 pub struct TranscribedSequence<'a> {
     pub inner: &'a dyn Sequence,
@@ -162,48 +511,293 @@ impl<'a> Iterator for TranslateIterator<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done { return None; }
-        
+
         let c1 = self.inner.next()?;
         let c2 = self.inner.next()?;
         let c3 = self.inner.next()?;
-        
-        let codon = [c1, c2, c3];
-        let aa = match codon {
-            ['T', 'T', 'T'] | ['T', 'T', 'C'] | ['U', 'U', 'U'] | ['U', 'U', 'C'] => 'F',
-            ['T', 'T', 'A'] | ['T', 'T', 'G'] | ['U', 'U', 'A'] | ['U', 'U', 'G'] | 
-            ['C', 'T', 'T'] | ['C', 'T', 'C'] | ['C', 'T', 'A'] | ['C', 'T', 'G'] | 
-            ['C', 'U', 'T'] | ['C', 'U', 'C'] | ['C', 'U', 'A'] | ['C', 'U', 'G'] => 'L',
-            ['A', 'T', 'T'] | ['A', 'T', 'C'] | ['A', 'T', 'A'] | ['A', 'U', 'T'] | ['A', 'U', 'C'] | ['A', 'U', 'A'] => 'I',
-            ['A', 'T', 'G'] | ['A', 'U', 'G'] => 'M',
-            ['G', 'T', 'T'] | ['G', 'T', 'C'] | ['G', 'T', 'A'] | ['G', 'T', 'G'] | 
-            ['G', 'U', 'T'] | ['G', 'U', 'C'] | ['G', 'U', 'A'] | ['G', 'U', 'G'] => 'V',
-            ['T', 'C', 'T'] | ['T', 'C', 'C'] | ['T', 'C', 'A'] | ['T', 'C', 'G'] | 
-            ['U', 'C', 'T'] | ['U', 'C', 'C'] | ['U', 'C', 'A'] | ['U', 'C', 'G'] | 
-            ['A', 'G', 'T'] | ['A', 'G', 'C'] | ['A', 'G', 'U'] => 'S',
-            ['C', 'C', 'T'] | ['C', 'C', 'C'] | ['C', 'C', 'A'] | ['C', 'C', 'G'] | ['C', 'C', 'U'] => 'P', 
-            ['A', 'C', 'T'] | ['A', 'C', 'C'] | ['A', 'C', 'A'] | ['A', 'C', 'G'] | ['A', 'C', 'U'] => 'T',
-            ['G', 'C', 'T'] | ['G', 'C', 'C'] | ['G', 'C', 'A'] | ['G', 'C', 'G'] | ['G', 'C', 'U'] => 'A',
-            ['T', 'A', 'T'] | ['T', 'A', 'C'] | ['U', 'A', 'U'] | ['U', 'A', 'C'] => 'Y',
-            ['T', 'A', 'A'] | ['T', 'A', 'G'] | ['T', 'G', 'A'] | ['U', 'A', 'A'] | ['U', 'A', 'G'] | ['U', 'G', 'A'] => '*',
-            ['C', 'A', 'T'] | ['C', 'A', 'C'] | ['C', 'A', 'U'] => 'H', 
-            ['C', 'A', 'A'] | ['C', 'A', 'G'] => 'Q',
-            ['A', 'A', 'T'] | ['A', 'A', 'C'] | ['A', 'A', 'U'] => 'N', 
-            ['A', 'A', 'A'] | ['A', 'A', 'G'] => 'K',
-            ['G', 'A', 'T'] | ['G', 'A', 'C'] | ['G', 'A', 'U'] => 'D', 
-            ['G', 'A', 'A'] | ['G', 'A', 'G'] => 'E',
-            ['T', 'G', 'T'] | ['T', 'G', 'C'] | ['U', 'G', 'T'] | ['U', 'G', 'C'] => 'C', 
-            ['T', 'G', 'G'] | ['U', 'G', 'G'] => 'W',
-            ['C', 'G', 'T'] | ['C', 'G', 'C'] | ['C', 'G', 'A'] | ['C', 'G', 'G'] | ['C', 'G', 'U'] | 
-            ['A', 'G', 'A'] | ['A', 'G', 'G'] => 'R', 
-            ['G', 'G', 'T'] | ['G', 'G', 'C'] | ['G', 'G', 'A'] | ['G', 'G', 'G'] | ['G', 'G', 'U'] => 'G',
-            _ => 'X',
-        };
-        
+
+        let aa = translate_codon_ambiguous([c1, c2, c3], crate::genetic_code::GeneticCodeTable::Standard);
+
         if aa == '*' { self.done = true; }
         Some(aa)
     }
 }
 
+/// The concrete bases a (possibly degenerate) IUPAC base code stands for.
+/// Empty for anything that isn't a recognized nucleotide code.
+fn expand_iupac_base(c: char) -> &'static [char] {
+    match c.to_ascii_uppercase() {
+        'A' => &['A'],
+        'C' => &['C'],
+        'G' => &['G'],
+        'T' | 'U' => &['T'],
+        'R' => &['A', 'G'],
+        'Y' => &['C', 'T'],
+        'S' => &['G', 'C'],
+        'W' => &['A', 'T'],
+        'K' => &['G', 'T'],
+        'M' => &['A', 'C'],
+        'B' => &['C', 'G', 'T'],
+        'D' => &['A', 'G', 'T'],
+        'H' => &['A', 'C', 'T'],
+        'V' => &['A', 'C', 'G'],
+        'N' => &['A', 'C', 'G', 'T'],
+        _ => &[],
+    }
+}
+
+/// Translates a codon that may contain IUPAC ambiguity codes, under `table`.
+/// Expands each degenerate position to its concrete bases and translates
+/// every combination: if they all agree on an amino acid (e.g. `GCN` is
+/// always Ala), that amino acid is returned; if they disagree, or any
+/// position isn't a recognized nucleotide code at all, the result is `'X'`.
+pub fn translate_codon_ambiguous(codon: [char; 3], table: crate::genetic_code::GeneticCodeTable) -> char {
+    let options = [
+        expand_iupac_base(codon[0]),
+        expand_iupac_base(codon[1]),
+        expand_iupac_base(codon[2]),
+    ];
+    if options.iter().any(|o| o.is_empty()) {
+        return 'X';
+    }
+
+    let mut result: Option<char> = None;
+    for &b0 in options[0] {
+        for &b1 in options[1] {
+            for &b2 in options[2] {
+                let aa = table.translate_codon([b0, b1, b2]);
+                match result {
+                    None => result = Some(aa),
+                    Some(prev) if prev != aa => return 'X',
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    result.unwrap_or('X')
+}
+
+/// Translates a single codon using the standard (NCBI `transl_table=1`)
+/// genetic code. `T`/`U` are both accepted. Returns `'X'` for ambiguous or
+/// invalid codons. Shared by [`TranslateIterator`] and
+/// [`crate::genetic_code::GeneticCodeTable`], which overlays table-specific
+/// exceptions on top of this base table.
+pub(crate) fn translate_codon_standard(codon: [char; 3]) -> char {
+    let codon = codon.map(|b| if b == 'U' { 'T' } else { b });
+    match codon {
+        ['T', 'T', 'T'] | ['T', 'T', 'C'] | ['U', 'U', 'U'] | ['U', 'U', 'C'] => 'F',
+        ['T', 'T', 'A'] | ['T', 'T', 'G'] | ['U', 'U', 'A'] | ['U', 'U', 'G'] |
+        ['C', 'T', 'T'] | ['C', 'T', 'C'] | ['C', 'T', 'A'] | ['C', 'T', 'G'] |
+        ['C', 'U', 'T'] | ['C', 'U', 'C'] | ['C', 'U', 'A'] | ['C', 'U', 'G'] => 'L',
+        ['A', 'T', 'T'] | ['A', 'T', 'C'] | ['A', 'T', 'A'] | ['A', 'U', 'T'] | ['A', 'U', 'C'] | ['A', 'U', 'A'] => 'I',
+        ['A', 'T', 'G'] | ['A', 'U', 'G'] => 'M',
+        ['G', 'T', 'T'] | ['G', 'T', 'C'] | ['G', 'T', 'A'] | ['G', 'T', 'G'] |
+        ['G', 'U', 'T'] | ['G', 'U', 'C'] | ['G', 'U', 'A'] | ['G', 'U', 'G'] => 'V',
+        ['T', 'C', 'T'] | ['T', 'C', 'C'] | ['T', 'C', 'A'] | ['T', 'C', 'G'] |
+        ['U', 'C', 'T'] | ['U', 'C', 'C'] | ['U', 'C', 'A'] | ['U', 'C', 'G'] |
+        ['A', 'G', 'T'] | ['A', 'G', 'C'] | ['A', 'G', 'U'] => 'S',
+        ['C', 'C', 'T'] | ['C', 'C', 'C'] | ['C', 'C', 'A'] | ['C', 'C', 'G'] | ['C', 'C', 'U'] => 'P',
+        ['A', 'C', 'T'] | ['A', 'C', 'C'] | ['A', 'C', 'A'] | ['A', 'C', 'G'] | ['A', 'C', 'U'] => 'T',
+        ['G', 'C', 'T'] | ['G', 'C', 'C'] | ['G', 'C', 'A'] | ['G', 'C', 'G'] | ['G', 'C', 'U'] => 'A',
+        ['T', 'A', 'T'] | ['T', 'A', 'C'] | ['U', 'A', 'U'] | ['U', 'A', 'C'] => 'Y',
+        ['T', 'A', 'A'] | ['T', 'A', 'G'] | ['T', 'G', 'A'] | ['U', 'A', 'A'] | ['U', 'A', 'G'] | ['U', 'G', 'A'] => '*',
+        ['C', 'A', 'T'] | ['C', 'A', 'C'] | ['C', 'A', 'U'] => 'H',
+        ['C', 'A', 'A'] | ['C', 'A', 'G'] => 'Q',
+        ['A', 'A', 'T'] | ['A', 'A', 'C'] | ['A', 'A', 'U'] => 'N',
+        ['A', 'A', 'A'] | ['A', 'A', 'G'] => 'K',
+        ['G', 'A', 'T'] | ['G', 'A', 'C'] | ['G', 'A', 'U'] => 'D',
+        ['G', 'A', 'A'] | ['G', 'A', 'G'] => 'E',
+        ['T', 'G', 'T'] | ['T', 'G', 'C'] | ['U', 'G', 'T'] | ['U', 'G', 'C'] => 'C',
+        ['T', 'G', 'G'] | ['U', 'G', 'G'] => 'W',
+        ['C', 'G', 'T'] | ['C', 'G', 'C'] | ['C', 'G', 'A'] | ['C', 'G', 'G'] | ['C', 'G', 'U'] |
+        ['A', 'G', 'A'] | ['A', 'G', 'G'] => 'R',
+        ['G', 'G', 'T'] | ['G', 'G', 'C'] | ['G', 'G', 'A'] | ['G', 'G', 'G'] | ['G', 'G', 'U'] => 'G',
+        _ => 'X',
+    }
+}
+
+/// Adapter for translation using a specific [`crate::genetic_code::GeneticCodeTable`]
+/// instead of always assuming the standard code.
+pub struct TranslatedSequenceWithTable<'a> {
+    pub inner: &'a dyn Sequence,
+    pub table: crate::genetic_code::GeneticCodeTable,
+}
+
+impl<'a> Sequence for TranslatedSequenceWithTable<'a> {
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
+        let table = self.table;
+        let chars: Vec<char> = self.inner.iter().collect();
+        let mut aas = Vec::with_capacity(chars.len() / 3);
+        for codon in chars.chunks(3) {
+            if codon.len() < 3 {
+                break;
+            }
+            let aa = translate_codon_ambiguous([codon[0], codon[1], codon[2]], table);
+            let stop = aa == '*';
+            aas.push(aa);
+            if stop {
+                break;
+            }
+        }
+        Box::new(aas.into_iter())
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len() / 3
+    }
+}
+
+/// Like [`TranslatedSequenceWithTable`], but also consults
+/// [`crate::genetic_code::SelenocysteineSites`]: an in-frame `TGA` at an
+/// annotated codon position translates to selenocysteine (`U`) and
+/// translation continues, instead of stopping there.
+pub struct TranslatedSequenceWithRecoding<'a> {
+    pub inner: &'a dyn Sequence,
+    pub table: crate::genetic_code::GeneticCodeTable,
+    pub selenocysteine_sites: &'a crate::genetic_code::SelenocysteineSites,
+}
+
+impl<'a> Sequence for TranslatedSequenceWithRecoding<'a> {
+    fn iter(&self) -> Box<dyn Iterator<Item = char> + '_> {
+        let table = self.table;
+        let chars: Vec<char> = self.inner.iter().collect();
+        let mut aas = Vec::with_capacity(chars.len() / 3);
+        for (codon_pos, codon) in chars.chunks(3).enumerate() {
+            if codon.len() < 3 {
+                break;
+            }
+            let codon_arr = [codon[0], codon[1], codon[2]];
+            let aa = translate_codon_ambiguous(codon_arr, table);
+            let aa = crate::genetic_code::apply_selenocysteine_recoding(
+                codon_arr,
+                aa,
+                self.selenocysteine_sites.contains(codon_pos as i32),
+            );
+            let stop = aa == '*';
+            aas.push(aa);
+            if stop {
+                break;
+            }
+        }
+        Box::new(aas.into_iter())
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len() / 3
+    }
+}
+
+/// Which strand an [`Orf`] was found on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// An open reading frame found by [`find_orfs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orf {
+    /// Which of the 3 codon phases within `strand` this ORF was found in.
+    pub frame: u8,
+    pub strand: Strand,
+    /// 0-based, forward-strand coordinates regardless of `strand` -- for a
+    /// reverse-strand ORF this is the span of the original (not
+    /// reverse-complemented) sequence it occupies.
+    pub start: usize,
+    /// Exclusive, forward-strand.
+    pub end: usize,
+    /// Translated protein, leading residue forced to `M`. Ends in `*` unless
+    /// `partial` is set.
+    pub protein: String,
+    /// Set when the ORF ran off the end of the sequence before hitting an
+    /// in-frame stop codon.
+    pub partial: bool,
+}
+
+/// Scans all 3 forward frames (and, if `include_reverse`, all 3 reverse
+/// frames) of `seq` for open reading frames: a run starting at one of
+/// `table`'s start codons and continuing, in-frame, to either a stop codon
+/// or the end of the sequence.
+///
+/// Nested/overlapping start codons inside an already-open ORF are not
+/// reported separately -- only the outermost ORF covering a given stretch
+/// is returned, matching how ORF finders are normally used (the first AUG
+/// of a transcript is the one that matters, not every internal AUG).
+///
+/// `min_protein_len` filters out ORFs whose translated `protein` (including
+/// the trailing `*`, if present) is shorter than this. Pass `0` for no
+/// filtering. Results are not sorted; sort by `orf.protein.len()` if a
+/// particular order is wanted (e.g. longest-first for a quick summary).
+pub fn find_orfs(
+    seq: &dyn Sequence,
+    table: crate::genetic_code::GeneticCodeTable,
+    min_protein_len: usize,
+    include_reverse: bool,
+) -> Vec<Orf> {
+    let forward: Vec<char> = seq.iter().collect();
+    let mut orfs = find_orfs_in_strand(&forward, table, Strand::Forward, min_protein_len);
+
+    if include_reverse {
+        let rc = RevCompSequence { inner: seq };
+        let reverse: Vec<char> = rc.iter().collect();
+        orfs.extend(find_orfs_in_strand(&reverse, table, Strand::Reverse, min_protein_len));
+    }
+
+    orfs
+}
+
+/// Scans all 3 frames of a single strand, already materialized into
+/// `chars` (already reverse-complemented, for [`Strand::Reverse`]).
+fn find_orfs_in_strand(
+    chars: &[char],
+    table: crate::genetic_code::GeneticCodeTable,
+    strand: Strand,
+    min_protein_len: usize,
+) -> Vec<Orf> {
+    let n = chars.len();
+    let mut orfs = Vec::new();
+
+    for frame in 0u8..3 {
+        let mut i = frame as usize;
+        while i + 3 <= n {
+            let codon = [chars[i], chars[i + 1], chars[i + 2]];
+            if !table.is_start_codon(codon) {
+                i += 3;
+                continue;
+            }
+
+            let start_idx = i;
+            let mut protein = String::new();
+            let mut j = i;
+            let mut partial = true;
+            while j + 3 <= n {
+                let codon = [chars[j], chars[j + 1], chars[j + 2]];
+                let aa = if j == start_idx { 'M' } else { translate_codon_ambiguous(codon, table) };
+                protein.push(aa);
+                j += 3;
+                if aa == '*' {
+                    partial = false;
+                    break;
+                }
+            }
+
+            if protein.len() >= min_protein_len {
+                let (start, end) = match strand {
+                    Strand::Forward => (start_idx, j),
+                    Strand::Reverse => (n - j, n - start_idx),
+                };
+                orfs.push(Orf { frame, strand, start, end, protein, partial });
+            }
+
+            // Skip past this whole ORF so a start codon nested inside it
+            // isn't reported as a second, overlapping ORF.
+            i = j;
+        }
+    }
+
+    orfs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,6 +816,22 @@ mod tests {
         assert_eq!(rc.to_string(), "GCAT");
     }
 
+    #[test]
+    fn test_rev_comp_handles_full_iupac_ambiguity_set() {
+        let s = MemSequence::new("RYSWKMBDHVNacgt".to_string());
+        let rc = RevCompSequence { inner: &s };
+        assert_eq!(rc.to_string(), "acgtNBDHVKMWSRY");
+    }
+
+    #[test]
+    fn test_complement_dna_char_preserves_case() {
+        assert_eq!(complement_dna_char('r'), 'y');
+        assert_eq!(complement_dna_char('R'), 'Y');
+        assert_eq!(complement_dna_char('k'), 'm');
+        assert_eq!(complement_dna_char('b'), 'v');
+        assert_eq!(complement_dna_char('-'), '-');
+    }
+
     #[test]
     fn test_translate() {
         let s = MemSequence::new("ATGGCTTAA".to_string());
@@ -229,6 +839,27 @@ mod tests {
         assert_eq!(t.to_string(), "MA*");
     }
 
+    #[test]
+    fn test_translate_codon_ambiguous_resolves_unambiguous_degenerate_codon() {
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        // GCN is Ala (GCT/GCC/GCA/GCG all translate to 'A') regardless of N.
+        assert_eq!(translate_codon_ambiguous(['G', 'C', 'N'], table), 'A');
+    }
+
+    #[test]
+    fn test_translate_codon_ambiguous_collapses_genuine_ambiguity_to_x() {
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        // YTN spans both Leu (CTN) and Phe/Leu (TTY/TTR) depending on Y -- disagreement -> X.
+        assert_eq!(translate_codon_ambiguous(['Y', 'T', 'N'], table), 'X');
+    }
+
+    #[test]
+    fn test_translated_sequence_with_table_handles_ambiguous_codons() {
+        let s = MemSequence::new("GCNTAA".to_string());
+        let t = TranslatedSequenceWithTable { inner: &s, table: crate::genetic_code::GeneticCodeTable::Standard };
+        assert_eq!(t.to_string(), "A*");
+    }
+
     #[test]
     fn test_spliced_sequence() {
         let s1 = MemSequence::new("ATG".to_string());
@@ -242,6 +873,28 @@ mod tests {
         assert_eq!(trans.to_string(), "MA*");
     }
 
+    #[test]
+    fn test_packed_dna_sequence_round_trips_and_translates() {
+        let packed = PackedDnaSequence::new("ATGGCTTAA");
+        assert!(matches!(packed, PackedDnaSequence::Packed { .. }));
+        assert_eq!(packed.len(), 9);
+        assert_eq!(packed.to_string(), "ATGGCTTAA");
+
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        assert_eq!(packed.translate_codon_at(0, table), Some('M'));
+        assert_eq!(packed.translate_codon_at(3, table), Some('A'));
+        assert_eq!(packed.translate_codon_at(6, table), Some('*'));
+        assert_eq!(packed.translate_codon_at(7, table), None);
+    }
+
+    #[test]
+    fn test_packed_dna_sequence_falls_back_for_ambiguity_codes() {
+        let packed = PackedDnaSequence::new("ATGNCT");
+        assert!(matches!(packed, PackedDnaSequence::Fallback(_)));
+        assert_eq!(packed.len(), 6);
+        assert_eq!(packed.to_string(), "ATGNCT");
+    }
+
     #[test]
     fn test_slice_sequence() {
         let s = MemSequence::new("ATGGCTTAA".to_string());
@@ -249,6 +902,129 @@ mod tests {
         assert_eq!(slice.len(), 3);
         assert_eq!(slice.to_string(), "GCT");
     }
+
+    #[test]
+    fn test_packed_sequence_round_trips_pure_acgt() {
+        let mem = MemSequence::new("ATGGCTTAAGGCCATTCGA".to_string());
+        let packed = PackedSequence::encode(&mem).unwrap();
+        assert_eq!(packed.len(), mem.len());
+        assert_eq!(packed.to_string(), mem.to_string());
+
+        let bytes = packed.to_bytes();
+        let restored = PackedSequence::from_bytes(&bytes).unwrap();
+        assert_eq!(restored, packed);
+        assert_eq!(restored.to_string(), mem.to_string());
+    }
+
+    #[test]
+    fn test_packed_sequence_keeps_non_acgt_as_exceptions() {
+        let mem = MemSequence::new("ATGNNCTRYW".to_string());
+        let packed = PackedSequence::encode(&mem).unwrap();
+        assert_eq!(packed.to_string(), "ATGNNCTRYW");
+        assert_eq!(packed.base_at(3), 'N');
+        assert_eq!(packed.base_at(7), 'R');
+
+        let restored = PackedSequence::from_bytes(&packed.to_bytes()).unwrap();
+        assert_eq!(restored.to_string(), "ATGNNCTRYW");
+    }
+
+    #[test]
+    fn test_packed_sequence_from_bytes_rejects_truncated_input() {
+        let mem = MemSequence::new("ATGGCTTAA".to_string());
+        let packed = PackedSequence::encode(&mem).unwrap();
+        let bytes = packed.to_bytes();
+        assert!(PackedSequence::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PackedSequence::from_bytes(&[0u8; 3]).is_err());
+    }
+
+    #[test]
+    fn test_chunked_sequence_pulls_fixed_size_windows() {
+        let source = "ATGGCTTAAGGCCATTCGA"; // 19 bases
+        let calls = std::cell::Cell::new(0);
+        let seq = ChunkedSequence::new(0, source.len(), 4, |start, end| {
+            calls.set(calls.get() + 1);
+            Ok(source[start..end].to_string())
+        });
+        assert_eq!(seq.len(), 19);
+        assert_eq!(seq.to_string(), source);
+        // ceil(19 / 4) windows.
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn test_chunked_sequence_respects_start_offset() {
+        let source = "ATGGCTTAAGGCCATTCGA";
+        let seq = ChunkedSequence::new(3, 9, 2, |start, end| Ok(source[start..end].to_string()));
+        assert_eq!(seq.to_string(), "GCTTAA");
+    }
+
+    #[test]
+    fn test_chunked_sequence_stops_at_first_fetch_error() {
+        let seq = ChunkedSequence::new(0, 100, 8, |start, _end| {
+            if start == 0 {
+                Ok("ATGGCTTA".to_string())
+            } else {
+                Err(HgvsError::Other("boom".into()))
+            }
+        });
+        assert_eq!(seq.to_string(), "ATGGCTTA");
+    }
+
+    #[test]
+    fn test_find_orfs_locates_forward_frame_orf() {
+        let s = MemSequence::new("GGGATGGCTTAATTT".to_string());
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        let orfs = find_orfs(&s, table, 0, false);
+        assert_eq!(orfs.len(), 1);
+        let orf = &orfs[0];
+        assert_eq!(orf.strand, Strand::Forward);
+        assert_eq!(orf.frame, 0);
+        assert_eq!((orf.start, orf.end), (3, 12));
+        assert_eq!(orf.protein, "MA*");
+        assert!(!orf.partial);
+    }
+
+    #[test]
+    fn test_find_orfs_reports_only_outermost_for_nested_starts() {
+        // A second ATG at index 3 is nested inside the ORF starting at index 0.
+        let s = MemSequence::new("ATGATGGCTTAA".to_string());
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        let orfs = find_orfs(&s, table, 0, false);
+        assert_eq!(orfs.len(), 1);
+        assert_eq!((orfs[0].start, orfs[0].end), (0, 12));
+    }
+
+    #[test]
+    fn test_find_orfs_flags_partial_orf_with_no_stop() {
+        let s = MemSequence::new("GGGATGGCTGCT".to_string());
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        let orfs = find_orfs(&s, table, 0, false);
+        assert_eq!(orfs.len(), 1);
+        let orf = &orfs[0];
+        assert!(orf.partial);
+        assert_eq!(orf.protein, "MAA");
+        assert_eq!((orf.start, orf.end), (3, 12));
+    }
+
+    #[test]
+    fn test_find_orfs_on_reverse_strand_reports_forward_coordinates() {
+        // Reverse complement of this sequence is "GGGATGGCTTAATTT", which has
+        // a forward-frame-0 ORF (MA*) at rev-comp-local [3, 12).
+        let s = MemSequence::new("AAATTAAGCCATCCC".to_string());
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        let orfs = find_orfs(&s, table, 0, true);
+        let reverse_orf = orfs.iter().find(|o| o.strand == Strand::Reverse).expect("a reverse-strand ORF");
+        assert_eq!((reverse_orf.start, reverse_orf.end), (3, 12));
+        assert_eq!(reverse_orf.protein, "MA*");
+    }
+
+    #[test]
+    fn test_find_orfs_filters_by_minimum_protein_length() {
+        let s = MemSequence::new("GGGATGGCTTAATTT".to_string());
+        let table = crate::genetic_code::GeneticCodeTable::Standard;
+        assert_eq!(find_orfs(&s, table, 4, false).len(), 0);
+        assert_eq!(find_orfs(&s, table, 3, false).len(), 1);
+    }
 }
 
 /// Adapter for slicing a sequence.
@@ -0,0 +1,144 @@
+//! On-disk cache of fetched reference-sequence windows, keyed by
+//! `(accession, start, end, IdentifierType)`.
+//!
+//! [`crate::equivalence::VariantEquivalence::get_ref_for_variant`] and
+//! `fill_na_edit` call [`crate::data::DataProvider::get_seq`] once per
+//! comparison; against a remote provider that turns a large equivalence
+//! batch into a network-bound workload. A [`SequenceCache`] lets a process
+//! fetch each `(ac, start, end, kind)` window once, then snapshot it to a
+//! compact CBOR blob a later run can load instead of re-fetching, turning
+//! repeated batches into memory-bound work.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::IdentifierType;
+use crate::error::HgvsError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct SeqCacheKey {
+    ac: String,
+    start: i32,
+    end: i32,
+    kind: IdentifierType,
+}
+
+/// A `(accession, start, end, kind) -> sequence` cache, snapshottable to CBOR.
+///
+/// Interior-mutable so it can be consulted from the `&self` methods on
+/// `VariantEquivalence` without threading `&mut` through the whole
+/// equivalence-checking call graph.
+#[derive(Debug, Default)]
+pub struct SequenceCache {
+    entries: RefCell<HashMap<SeqCacheKey, String>>,
+}
+
+impl SequenceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached sequence for `(ac, start, end, kind)` if present;
+    /// otherwise runs `fetch`, caches its result, and returns that.
+    pub fn get_or_fetch(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        kind: IdentifierType,
+        fetch: impl FnOnce() -> Result<String, HgvsError>,
+    ) -> Result<String, HgvsError> {
+        let key = SeqCacheKey {
+            ac: ac.to_string(),
+            start,
+            end,
+            kind,
+        };
+        if let Some(seq) = self.entries.borrow().get(&key) {
+            return Ok(seq.clone());
+        }
+        let seq = fetch()?;
+        self.entries.borrow_mut().insert(key, seq.clone());
+        Ok(seq)
+    }
+
+    /// Serializes every cached window to a compact CBOR blob.
+    pub fn dump_cache<W: Write>(&self, writer: W) -> Result<(), HgvsError> {
+        let entries = self.entries.borrow();
+        let snapshot: Vec<(SeqCacheKey, String)> = entries
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        serde_cbor::to_writer(writer, &snapshot)
+            .map_err(|e| HgvsError::Other(format!("Failed to serialize sequence cache: {}", e)))
+    }
+
+    /// Loads a CBOR blob written by [`Self::dump_cache`], merging its entries
+    /// into any already present (existing entries for the same key are kept).
+    pub fn load_cache<R: Read>(&self, reader: R) -> Result<(), HgvsError> {
+        let snapshot: Vec<(SeqCacheKey, String)> = serde_cbor::from_reader(reader)
+            .map_err(|e| HgvsError::Other(format!("Failed to deserialize sequence cache: {}", e)))?;
+        let mut entries = self.entries.borrow_mut();
+        for (key, seq) in snapshot {
+            entries.entry(key).or_insert(seq);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_fetch_caches_after_first_miss() {
+        let cache = SequenceCache::new();
+        let mut fetch_count = 0;
+
+        for _ in 0..3 {
+            let seq = cache
+                .get_or_fetch("NC_000001.11", 0, 4, IdentifierType::GenomicAccession, || {
+                    fetch_count += 1;
+                    Ok("ACGT".to_string())
+                })
+                .unwrap();
+            assert_eq!(seq, "ACGT");
+        }
+
+        assert_eq!(fetch_count, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_dump_and_load_cache_round_trips() {
+        let cache = SequenceCache::new();
+        cache
+            .get_or_fetch("NC_000001.11", 0, 4, IdentifierType::GenomicAccession, || {
+                Ok("ACGT".to_string())
+            })
+            .unwrap();
+
+        let mut blob = Vec::new();
+        cache.dump_cache(&mut blob).unwrap();
+
+        let restored = SequenceCache::new();
+        restored.load_cache(blob.as_slice()).unwrap();
+
+        let seq = restored
+            .get_or_fetch("NC_000001.11", 0, 4, IdentifierType::GenomicAccession, || {
+                panic!("should have been served from the loaded cache");
+            })
+            .unwrap();
+        assert_eq!(seq, "ACGT");
+    }
+}
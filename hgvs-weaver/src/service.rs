@@ -0,0 +1,128 @@
+//! Optional gRPC service exposing parse/normalize/project over protobuf.
+//!
+//! Gated behind the `service` feature so library-only consumers don't pull
+//! in tonic/prost. This mirrors how Weaver publishes its interoperation
+//! modules as compiled gRPC services: one long-running process holds a
+//! `DataProvider` (and any [`crate::caching_provider::CachingDataProvider`]
+//! in front of it) warm across requests, instead of a non-Rust caller
+//! re-initializing the provider per invocation. Handlers are thin — they
+//! just convert the wire messages and delegate to the existing parser,
+//! [`VariantMapper`], and normalizer.
+#![cfg(feature = "service")]
+
+pub mod proto {
+    tonic::include_proto!("weaver.v1");
+}
+
+use proto::hgvs_service_server::HgvsService;
+use proto::{
+    CoordinateSystem, HgvsVariant as ProtoVariant, NormalizeRequest, NormalizeResponse,
+    ParseRequest, ParseResponse, ProjectRequest, ProjectResponse,
+};
+use tonic::{Request, Response, Status};
+
+use crate::coords::SequenceVariant;
+use crate::data::DataProvider;
+use crate::error::HgvsError;
+use crate::mapper::VariantMapper;
+use crate::structs::Variant as _;
+
+fn to_proto(var: &SequenceVariant) -> ProtoVariant {
+    ProtoVariant {
+        hgvs: var.to_string(),
+        accession: var.ac().to_string(),
+        gene: var.gene().unwrap_or_default().to_string(),
+        coordinate_type: var.coordinate_type().to_string(),
+        structured_json: serde_json::to_string(var).unwrap_or_default(),
+    }
+}
+
+fn status_for(e: HgvsError) -> Status {
+    Status::invalid_argument(e.to_string())
+}
+
+fn require_variant(proto_variant: Option<ProtoVariant>) -> Result<SequenceVariant, Status> {
+    let proto_variant =
+        proto_variant.ok_or_else(|| Status::invalid_argument("missing variant"))?;
+    crate::parse_hgvs_variant(&proto_variant.hgvs).map_err(status_for)
+}
+
+/// Backs the generated [`HgvsService`] trait with an in-process
+/// [`DataProvider`]. Holds the provider for the lifetime of the server, so
+/// repeated `Project`/`Normalize` calls against the same transcripts benefit
+/// from whatever caching the provider itself does.
+pub struct HgvsGrpcService<D> {
+    provider: D,
+}
+
+impl<D: DataProvider + Send + Sync + 'static> HgvsGrpcService<D> {
+    pub fn new(provider: D) -> Self {
+        HgvsGrpcService { provider }
+    }
+}
+
+#[tonic::async_trait]
+impl<D: DataProvider + Send + Sync + 'static> HgvsService for HgvsGrpcService<D> {
+    async fn parse(
+        &self,
+        request: Request<ParseRequest>,
+    ) -> Result<Response<ParseResponse>, Status> {
+        let hgvs = request.into_inner().hgvs;
+        let variant = crate::parse_hgvs_variant(&hgvs).map_err(status_for)?;
+        Ok(Response::new(ParseResponse {
+            variant: Some(to_proto(&variant)),
+        }))
+    }
+
+    async fn normalize(
+        &self,
+        request: Request<NormalizeRequest>,
+    ) -> Result<Response<NormalizeResponse>, Status> {
+        let variant = require_variant(request.into_inner().variant)?;
+        let mapper = VariantMapper::new(&self.provider);
+        let normalized = mapper.normalize_variant(variant).map_err(status_for)?;
+        Ok(Response::new(NormalizeResponse {
+            variant: Some(to_proto(&normalized)),
+        }))
+    }
+
+    async fn project(
+        &self,
+        request: Request<ProjectRequest>,
+    ) -> Result<Response<ProjectResponse>, Status> {
+        let req = request.into_inner();
+        let variant = require_variant(req.variant)?;
+        let mapper = VariantMapper::new(&self.provider);
+        let target = CoordinateSystem::try_from(req.target)
+            .unwrap_or(CoordinateSystem::Unspecified);
+
+        let projected = match (&variant, target) {
+            (SequenceVariant::Genomic(v), CoordinateSystem::Coding) => {
+                SequenceVariant::Coding(
+                    mapper
+                        .g_to_c(v, &req.transcript_accession)
+                        .map_err(status_for)?,
+                )
+            }
+            (SequenceVariant::Coding(v), CoordinateSystem::Genomic) => {
+                let reference_ac = (!req.reference_accession.is_empty())
+                    .then_some(req.reference_accession.as_str());
+                SequenceVariant::Genomic(mapper.c_to_g(v, reference_ac).map_err(status_for)?)
+            }
+            (SequenceVariant::Coding(v), CoordinateSystem::Protein) => {
+                let protein_ac = (!req.transcript_accession.is_empty())
+                    .then_some(req.transcript_accession.as_str());
+                SequenceVariant::Protein(mapper.c_to_p(v, protein_ac).map_err(status_for)?)
+            }
+            _ => {
+                return Err(Status::unimplemented(
+                    "unsupported source/target coordinate system combination",
+                ))
+            }
+        };
+
+        Ok(Response::new(ProjectResponse {
+            variant: Some(to_proto(&projected)),
+        }))
+    }
+}
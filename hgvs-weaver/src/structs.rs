@@ -3,6 +3,7 @@ pub use crate::coords::{Anchor, GenomicPos, TranscriptPos, ProteinPos, IntronicO
 pub use crate::edits::{NaEdit, AaEdit};
 pub use crate::data::{IdentifierKind, IdentifierType};
 use serde::{Serialize, Deserialize};
+use std::fmt;
 
 /// Common trait for all HGVS variants.
 pub trait Variant {
@@ -14,6 +15,236 @@ pub trait Variant {
     fn coordinate_type(&self) -> &str;
     /// Converts the variant to an SPDI string representation.
     fn to_spdi(&self, data_provider: &dyn crate::data::DataProvider) -> Result<String, HgvsError>;
+    /// Converts the variant to a VCF-style `(CHROM, POS, REF, ALT)` record,
+    /// by reusing [`Variant::to_spdi`] and then re-expressing its
+    /// `ac:pos0:del:ins` tuple under VCF conventions: 1-based POS, and no
+    /// empty alleles (VCF forbids them, unlike SPDI). See [`spdi_to_vcf`]
+    /// for the padding rules applied to pure insertions/deletions.
+    fn to_vcf(&self, data_provider: &dyn crate::data::DataProvider) -> Result<VcfRecord, HgvsError> {
+        let spdi = self.to_spdi(data_provider)?;
+        spdi_to_vcf(&spdi, data_provider)
+    }
+    /// Like [`Variant::to_vcf`], but never materializes the full reference
+    /// span for a `Del`/`Dup`/`Ins` at or above `threshold` bases; it emits a
+    /// symbolic `<DEL>`/`<DUP>`/`<INS>` allele with `END`/`SVLEN` in
+    /// [`VcfRecord::info`] instead. See [`SvThreshold`] for the threshold rule.
+    fn to_vcf_sv(
+        &self,
+        data_provider: &dyn crate::data::DataProvider,
+        threshold: SvThreshold,
+    ) -> Result<VcfRecord, HgvsError>;
+    /// Checks that any explicit reference sequence carried by this variant's
+    /// edit actually matches the reference at its resolved interval, before
+    /// any SPDI/VCF conversion relies on it. IUPAC ambiguity codes in the
+    /// stated reference (`R`, `Y`, `N`, ...) match any compatible reference
+    /// base rather than requiring a literal match. A `ref_` made entirely of
+    /// digits is treated as a stated length rather than a sequence. Edits
+    /// with no explicit `ref_` (implicit reference) trivially pass.
+    fn validate(&self, data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError>;
+}
+
+/// A VCF-style `(CHROM, POS, REF, ALT)` record, as produced by
+/// [`Variant::to_vcf`]/[`spdi_to_vcf`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct VcfRecord {
+    pub chrom: String,
+    pub pos: i32,
+    pub ref_: String,
+    pub alt: String,
+    /// `INFO` key/value pairs, e.g. `END`/`SVLEN` for a symbolic allele
+    /// emitted by [`Variant::to_vcf_sv`]. Empty for ordinary explicit-allele
+    /// records. Kept as an association list (rather than a map) since a VCF
+    /// INFO column is a small, order-significant list of fields.
+    #[serde(default)]
+    pub info: Vec<(String, String)>,
+}
+
+/// Size threshold (in reference bases) at or above which [`Variant::to_vcf_sv`]
+/// emits a symbolic allele instead of expanding the full reference/alternate
+/// sequence. A `Del`/`Dup`/`Ins` spanning fewer than `threshold` bases is
+/// still expanded explicitly, identical to [`Variant::to_vcf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvThreshold(pub u32);
+
+/// Converts an `ac:pos0:del:ins` SPDI string (as produced by
+/// [`EditSpdi::to_spdi`]) into a [`VcfRecord`].
+///
+/// SPDI allows empty `del`/`ins` (a pure insertion or pure deletion), which
+/// VCF doesn't: every REF/ALT must be non-empty. We left-pad both alleles
+/// with the reference base immediately before the variant and shift POS back
+/// by one to compensate, matching how VCF itself represents indels. At the
+/// very start of a contig (`pos0 == 0`) there's no preceding base, so we
+/// right-pad with the base immediately after the variant instead and leave
+/// POS at 1.
+pub fn spdi_to_vcf(spdi: &str, data_provider: &dyn crate::data::DataProvider) -> Result<VcfRecord, HgvsError> {
+    let mut parts = spdi.splitn(4, ':');
+    let ac = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+    let pos0: i32 = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?
+        .parse()
+        .map_err(|_| HgvsError::ValidationError(format!("malformed SPDI position: {spdi}")))?;
+    let del = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+    let ins = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+
+    if !del.is_empty() && !ins.is_empty() {
+        return Ok(VcfRecord {
+            chrom: ac.to_string(),
+            pos: pos0 + 1,
+            ref_: del.to_string(),
+            alt: ins.to_string(),
+            info: Vec::new(),
+        });
+    }
+
+    if pos0 == 0 {
+        let after = pos0 + del.len() as i32;
+        let pad = data_provider.get_seq(ac, after, after + 1, IdentifierType::Unknown)?;
+        Ok(VcfRecord {
+            chrom: ac.to_string(),
+            pos: 1,
+            ref_: format!("{del}{pad}"),
+            alt: format!("{ins}{pad}"),
+            info: Vec::new(),
+        })
+    } else {
+        let pad = data_provider.get_seq(ac, pos0 - 1, pos0, IdentifierType::Unknown)?;
+        Ok(VcfRecord {
+            chrom: ac.to_string(),
+            pos: pos0,
+            ref_: format!("{pad}{del}"),
+            alt: format!("{pad}{ins}"),
+            info: Vec::new(),
+        })
+    }
+}
+
+/// Parses a SPDI string (`sequence:position:deletion:insertion`, NCBI's
+/// 0-based interbase format) into a [`SequenceVariant`].
+///
+/// Resolves whether `sequence` is a genomic or transcript accession via
+/// [`crate::data::DataProvider::get_identifier_type`] to decide between a
+/// `g.` and an `n.` result. A transcript accession's SPDI position maps
+/// directly onto its spliced, offset-free `n.` coordinate; SPDI alone gives
+/// no way to tell whether the caller meant `c.`, so callers wanting `c.`
+/// should map the returned `n.` variant onward (e.g. via a `VariantMapper`).
+pub fn spdi_to_variant(
+    spdi: &str,
+    data_provider: &dyn crate::data::DataProvider,
+) -> Result<SequenceVariant, HgvsError> {
+    let mut parts = spdi.splitn(4, ':');
+    let ac = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+    let pos0: i32 = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?
+        .parse()
+        .map_err(|_| HgvsError::ValidationError(format!("malformed SPDI position: {spdi}")))?;
+    let del = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+    let ins = parts
+        .next()
+        .ok_or_else(|| HgvsError::ValidationError(format!("malformed SPDI string: {spdi}")))?;
+
+    let (hgvs_start, hgvs_end, edit) = if del.is_empty() {
+        (
+            pos0,
+            pos0 + 1,
+            NaEdit::Ins {
+                alt: Some(ins.to_string()),
+                uncertain: false,
+            },
+        )
+    } else if ins.is_empty() {
+        (
+            pos0 + 1,
+            pos0 + del.len() as i32,
+            NaEdit::Del {
+                ref_: Some(del.to_string()),
+                uncertain: false,
+            },
+        )
+    } else {
+        (
+            pos0 + 1,
+            pos0 + del.len() as i32,
+            NaEdit::RefAlt {
+                ref_: Some(del.to_string()),
+                alt: Some(ins.to_string()),
+            },
+        )
+    };
+
+    match data_provider.get_identifier_type(ac)? {
+        IdentifierType::TranscriptAccession => {
+            let posedit = PosEdit {
+                pos: Some(BaseOffsetInterval {
+                    start: BaseOffsetPosition {
+                        base: HgvsTranscriptPos(hgvs_start),
+                        offset: None,
+                        anchor: Anchor::TranscriptStart,
+                        uncertain: false,
+                    },
+                    end: if hgvs_end != hgvs_start {
+                        Some(BaseOffsetPosition {
+                            base: HgvsTranscriptPos(hgvs_end),
+                            offset: None,
+                            anchor: Anchor::TranscriptStart,
+                            uncertain: false,
+                        })
+                    } else {
+                        None
+                    },
+                    uncertain: false,
+                }),
+                edit,
+                uncertain: false,
+                predicted: false,
+            };
+            Ok(SequenceVariant::NonCoding(NVariant {
+                ac: ac.to_string(),
+                gene: None,
+                posedit,
+            }))
+        }
+        _ => {
+            let posedit = PosEdit {
+                pos: Some(SimpleInterval {
+                    start: SimplePosition {
+                        base: HgvsGenomicPos(hgvs_start),
+                        end: None,
+                        uncertain: false,
+                    },
+                    end: if hgvs_end != hgvs_start {
+                        Some(SimplePosition {
+                            base: HgvsGenomicPos(hgvs_end),
+                            end: None,
+                            uncertain: false,
+                        })
+                    } else {
+                        None
+                    },
+                    uncertain: false,
+                }),
+                edit,
+                uncertain: false,
+                predicted: false,
+            };
+            Ok(SequenceVariant::Genomic(GVariant {
+                ac: ac.to_string(),
+                gene: None,
+                posedit,
+            }))
+        }
+    }
 }
 
 macro_rules! impl_variant {
@@ -25,6 +256,16 @@ macro_rules! impl_variant {
             fn to_spdi(&self, data_provider: &dyn crate::data::DataProvider) -> Result<String, HgvsError> {
                 self.posedit.to_spdi(&self.ac, data_provider)
             }
+            fn to_vcf_sv(
+                &self,
+                data_provider: &dyn crate::data::DataProvider,
+                threshold: SvThreshold,
+            ) -> Result<VcfRecord, HgvsError> {
+                self.posedit.to_vcf_sv(&self.ac, data_provider, threshold)
+            }
+            fn validate(&self, data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError> {
+                self.posedit.validate(&self.ac, data_provider)
+            }
         }
     };
 }
@@ -115,6 +356,43 @@ where
     }
 }
 
+impl<I, E> PosEdit<I, E>
+where
+    I: IntervalSpdi,
+    E: EditVcf,
+{
+    pub fn to_vcf_sv(
+        &self,
+        ac: &str,
+        data_provider: &dyn crate::data::DataProvider,
+        threshold: SvThreshold,
+    ) -> Result<VcfRecord, HgvsError> {
+        let (start, end, spdi_ac) = if let Some(pos) = &self.pos {
+            pos.spdi_interval(ac, data_provider)?
+        } else {
+            return Err(HgvsError::ValidationError("VCF conversion requires a position".into()));
+        };
+
+        self.edit.to_vcf(&spdi_ac, start, end, data_provider, threshold)
+    }
+}
+
+impl<I, E> PosEdit<I, E>
+where
+    I: IntervalSpdi,
+    E: ValidateRef,
+{
+    pub fn validate(&self, ac: &str, data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError> {
+        let (start, end, spdi_ac) = if let Some(pos) = &self.pos {
+            pos.spdi_interval(ac, data_provider)?
+        } else {
+            return Err(HgvsError::ValidationError("validation requires a position".into()));
+        };
+
+        self.edit.validate(&spdi_ac, start, end, data_provider)
+    }
+}
+
 pub trait IntervalSpdi {
     /// Returns (start, end, ac) as 0-based integer coordinates and the accession to use for SPDI.
     fn spdi_interval(&self, ac: &str, data_provider: &dyn crate::data::DataProvider) -> Result<(i32, i32, String), HgvsError>;
@@ -168,6 +446,40 @@ pub trait EditSpdi {
     fn to_spdi(&self, ac: &str, start: i32, end: i32, data_provider: &dyn crate::data::DataProvider) -> Result<String, HgvsError>;
 }
 
+/// Counts how many consecutive exact copies of `unit` the reference holds
+/// starting at `start` (0-based). Fetches in batches of `CHUNK_UNITS` copies
+/// at a time so a long run of copies doesn't cost one `get_seq` call per
+/// unit, stopping at the first partial or mismatching copy.
+fn count_repeat_copies(
+    ac: &str,
+    start: i32,
+    unit: &str,
+    data_provider: &dyn crate::data::DataProvider,
+) -> Result<i32, HgvsError> {
+    const CHUNK_UNITS: i32 = 16;
+    let unit_len = unit.len() as i32;
+    let mut observed = 0;
+    loop {
+        let fetch_start = start + observed * unit_len;
+        let fetch_end = fetch_start + unit_len * CHUNK_UNITS;
+        let seq = data_provider.get_seq(ac, fetch_start, fetch_end, IdentifierType::Unknown)?;
+
+        let mut matched_in_chunk = 0;
+        for copy in seq.as_bytes().chunks(unit_len as usize) {
+            if copy.len() < unit_len as usize || copy != unit.as_bytes() {
+                break;
+            }
+            matched_in_chunk += 1;
+        }
+        observed += matched_in_chunk;
+
+        if matched_in_chunk < CHUNK_UNITS {
+            break;
+        }
+    }
+    Ok(observed)
+}
+
 impl EditSpdi for NaEdit {
     fn to_spdi(&self, ac: &str, start: i32, end: i32, data_provider: &dyn crate::data::DataProvider) -> Result<String, HgvsError> {
         match self {
@@ -211,16 +523,25 @@ impl EditSpdi for NaEdit {
                 // Start=1, End=1 (or 2?)
                 // In `BaseOffsetInterval`, if start != end, we calculated range.
                 // But `Ins` usually has start+1 = end in HGVS coordinates?
-                // Wait, logic in `validate.py`: 
+                // Wait, logic in `validate.py`:
                 // `if start_1 < end_1: return ...`
                 // `ac:start_1:ref:alt`
-                
+
                 // Let's stick to strict interpretation:
                 // SPDI for insertion is at the position.
                 // Ref is empty string (or the base before?).
                 // SPDI spec: "Deletion of 0 length at position".
-                
-                let a_seq = alt.as_deref().unwrap_or("");
+
+                let Some(a_seq) = alt else {
+                    return Err(HgvsError::UnsupportedOperation(
+                        "cannot compute SPDI for an insertion with no stated sequence".into(),
+                    ));
+                };
+                if a_seq.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(HgvsError::UnsupportedOperation(format!(
+                        "cannot compute SPDI for a stated-length insertion ins{a_seq} without its sequence"
+                    )));
+                }
                 Ok(format!("{}:{}:{}:{}", ac, start, "", a_seq))
             }
             NaEdit::Dup { ref_, .. } => {
@@ -256,44 +577,41 @@ impl EditSpdi for NaEdit {
                 Ok(format!("{}:{}:{}:{}", ac, end, "", r_seq))
             }
             NaEdit::Repeat { ref_, max, .. } => {
-                // Repeat sequence (e.g., c.7035TGGAAC[3]).
-                // SPDI represents this as a deletion of the original region and insertion of the repeated sequence.
-                // ClinVar SPDI for NM_001291285.3:c.7035TGGAAC[3] is NC_000004.12:125434258:ACTGGAACTGGAAC:ACTGGAACTGGAACTGGAAC
-                // Weaver's interval [start, end) for a point position c.7035 is [7034, 7035).
-                // Repeat unit length is ref_.len().
-                
+                // Repeat sequence (e.g., c.7035TGGAAC[3]): `max` is the
+                // *target* total copy count, not the number of extra copies
+                // to insert, so the minimal SPDI delins depends on how many
+                // copies are already present in the reference -- walk forward
+                // from `start` counting them (`count_repeat_copies`) rather
+                // than assuming the interval covers exactly one copy.
                 let unit = if let Some(r) = ref_ {
                     r.clone()
                 } else {
-                    // If unit not provided, assume it's the whole interval? 
-                    // HGVS repeats usually have a unit sequence or it's implied by the position.
-                    // For now, fetch from data provider if None.
                     data_provider.get_seq(ac, start, end, IdentifierType::Unknown)?
                 };
+                if unit.is_empty() {
+                    return Err(HgvsError::ValidationError("repeat unit must not be empty".into()));
+                }
 
-                // Del seq is the reference sequence at the interval.
-                // HGVS c.7035TGGAAC[3] usually means the unit TGGAAC is present at c.7035 and we want 3 copies total.
-                // We need to know how many copies were already there to determine the actual delta.
-                // BUT SPDI is absolute. It says "Delete this, Insert that".
-                // ClinVar's example has del=ACTGGAACTGGAAC (2 units?) and ins=ACTGGAACTGGAACTGGAAC (3 units?).
-                // Wait, if it's c.7035TGGAAC[3], and it results in 3 units...
-                // If r_seq at [start, end) is 1 unit, and we want 3 units...
-                // SPDI position should be the start of the repeat region.
-                
-                // For simplicity and matching ClinVar's "minimal" (but sometimes expanded) style:
-                // Canonical SPDI for repeats often settles on the smallest delins that describes the change.
-                // If we want [max] copies of [unit]:
-                // Ins seq = unit * max.
-                // Del seq = we need to know how many units are in the reference to be precise.
-                
-                let ins_seq = unit.repeat(*max as usize);
-                
-                // If we don't know the reference repeat count, we might produce a non-minimal SPDI.
-                // However, SPDI normalization (which weaver does) will clean it up.
-                // Let's at least get the reference sequence for the interval.
-                let r_seq = data_provider.get_seq(ac, start, end, IdentifierType::Unknown)?;
-                
-                Ok(format!("{}:{}:{}:{}", ac, start, r_seq, ins_seq))
+                let unit_len = unit.len() as i32;
+                let first = data_provider.get_seq(ac, start, start + unit_len, IdentifierType::Unknown)?;
+                if first != unit {
+                    return Err(HgvsError::ValidationError(format!(
+                        "reference at {ac}:{start} does not start with repeat unit {unit:?} (found {first:?})"
+                    )));
+                }
+
+                let observed = count_repeat_copies(ac, start, &unit, data_provider)?;
+                let max = *max;
+
+                if observed == max {
+                    // Already at the target copy count: nothing to change.
+                    let seq = unit.repeat(observed as usize);
+                    return Ok(format!("{}:{}:{}:{}", ac, start, seq, seq));
+                }
+
+                let del_seq = unit.repeat(observed as usize);
+                let ins_seq = unit.repeat(max as usize);
+                Ok(format!("{}:{}:{}:{}", ac, start, del_seq, ins_seq))
             }
             _ => Err(HgvsError::UnsupportedOperation(format!("Edit type {:?} not yet supported for SPDI", self)))
         }
@@ -306,6 +624,213 @@ impl EditSpdi for AaEdit {
     }
 }
 
+/// Like [`EditSpdi`], but lets a `Del`/`Dup`/large `Ins` opt out of
+/// materializing its full reference/alternate sequence by emitting a
+/// symbolic VCF allele instead. Backs [`PosEdit::to_vcf_sv`].
+pub trait EditVcf {
+    fn to_vcf(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        data_provider: &dyn crate::data::DataProvider,
+        threshold: SvThreshold,
+    ) -> Result<VcfRecord, HgvsError>;
+}
+
+/// Returns the symbolic ALT tag (`DEL`/`DUP`/`INS`), `SVLEN`, and 1-based
+/// `END` for `edit` if it's at or above `threshold`, or `None` if it should
+/// be expanded to an explicit allele as usual.
+fn symbolic_allele(edit: &NaEdit, start: i32, end: i32, threshold: SvThreshold) -> Option<(&'static str, i32, i32)> {
+    match edit {
+        NaEdit::Del { .. } => {
+            let span = (end - start).max(0);
+            (span as u32 >= threshold.0).then_some(("DEL", -span, end))
+        }
+        NaEdit::Dup { .. } => {
+            let span = (end - start).max(0);
+            (span as u32 >= threshold.0).then_some(("DUP", span, end))
+        }
+        NaEdit::Ins { alt: Some(alt), .. } => {
+            // A digit-only `alt` states the insertion's length without its
+            // sequence (e.g. from a VCF `<INS>` allele); parse it as a
+            // length instead of counting its characters as literal bases.
+            let len = if alt.chars().all(|c| c.is_ascii_digit()) {
+                alt.parse::<i32>().ok()?
+            } else {
+                alt.len() as i32
+            };
+            (len as u32 >= threshold.0).then_some(("INS", len, start + 1))
+        }
+        _ => None,
+    }
+}
+
+impl EditVcf for NaEdit {
+    fn to_vcf(
+        &self,
+        ac: &str,
+        start: i32,
+        end: i32,
+        data_provider: &dyn crate::data::DataProvider,
+        threshold: SvThreshold,
+    ) -> Result<VcfRecord, HgvsError> {
+        if let Some((tag, svlen, end_1based)) = symbolic_allele(self, start, end, threshold) {
+            // A symbolic ALT still needs a single anchoring REF base (the
+            // VCF convention, same as how SV callers emit `N\t<DEL>`), but
+            // never the full (possibly multi-kilobase) span.
+            let ref_base = data_provider.get_seq(ac, start, start + 1, IdentifierType::Unknown)?;
+            return Ok(VcfRecord {
+                chrom: ac.to_string(),
+                pos: start + 1,
+                ref_: ref_base,
+                alt: format!("<{tag}>"),
+                info: vec![
+                    ("END".to_string(), end_1based.to_string()),
+                    ("SVLEN".to_string(), svlen.to_string()),
+                ],
+            });
+        }
+
+        let spdi = EditSpdi::to_spdi(self, ac, start, end, data_provider)?;
+        spdi_to_vcf(&spdi, data_provider)
+    }
+}
+
+impl EditVcf for AaEdit {
+    fn to_vcf(
+        &self,
+        _ac: &str,
+        _start: i32,
+        _end: i32,
+        _data_provider: &dyn crate::data::DataProvider,
+        _threshold: SvThreshold,
+    ) -> Result<VcfRecord, HgvsError> {
+        Err(HgvsError::UnsupportedOperation("VCF not supported for protein variants (yet)".into()))
+    }
+}
+
+/// Checks an edit's explicit `ref_` sequence (if any) against the actual
+/// reference at its resolved interval. Backs [`PosEdit::validate`].
+pub trait ValidateRef {
+    fn validate(&self, ac: &str, start: i32, end: i32, data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError>;
+}
+
+/// Returns the IUPAC ambiguity set (as uppercase ASCII bases) a code stands
+/// for; `T`/`U` are treated as equivalent so RNA reference sequences compare
+/// cleanly against DNA-style `ref_` text. Unrecognized characters match
+/// nothing.
+fn iupac_bases(c: char) -> &'static [u8] {
+    match c.to_ascii_uppercase() {
+        'A' => b"A",
+        'C' => b"C",
+        'G' => b"G",
+        'T' | 'U' => b"T",
+        'R' => b"AG",
+        'Y' => b"CT",
+        'S' => b"GC",
+        'W' => b"AT",
+        'K' => b"GT",
+        'M' => b"AC",
+        'B' => b"CGT",
+        'D' => b"AGT",
+        'H' => b"ACT",
+        'V' => b"ACG",
+        'N' => b"ACGT",
+        _ => b"",
+    }
+}
+
+pub(crate) fn iupac_compatible(a: char, b: char) -> bool {
+    iupac_bases(a).iter().any(|base| iupac_bases(b).contains(base))
+}
+
+/// Same-length, position-by-position IUPAC comparison of two sequences.
+/// Used where the caller wants a plain bool (e.g. to decide whether to
+/// tolerate a mismatch) rather than [`require_iupac_match`]'s descriptive
+/// `Err`.
+pub(crate) fn iupac_seq_matches(a: &str, b: &str) -> bool {
+    a.chars().count() == b.chars().count()
+        && a.chars().zip(b.chars()).all(|(x, y)| iupac_compatible(x, y))
+}
+
+/// Requires `stated` and `actual` to be the same length and every position
+/// to be IUPAC-compatible; otherwise returns a descriptive `ValidationError`
+/// naming both sequences.
+fn require_iupac_match(stated: &str, actual: &str, ac: &str, start: i32) -> Result<(), HgvsError> {
+    let mismatch = || {
+        HgvsError::ValidationError(format!(
+            "reference mismatch at {ac}:{start}: expected {stated:?}, found {actual:?}"
+        ))
+    };
+    if stated.chars().count() != actual.chars().count() {
+        return Err(mismatch());
+    }
+    if stated.chars().zip(actual.chars()).all(|(s, a)| iupac_compatible(s, a)) {
+        Ok(())
+    } else {
+        Err(mismatch())
+    }
+}
+
+/// Returns the `ref_` field of any `NaEdit` variant that carries one, or
+/// `None` for variants (`Ins`, `None`, ...) that don't.
+fn na_edit_ref(edit: &NaEdit) -> Option<&str> {
+    match edit {
+        NaEdit::RefAlt { ref_, .. } => ref_.as_deref(),
+        NaEdit::Del { ref_, .. } => ref_.as_deref(),
+        NaEdit::Dup { ref_, .. } => ref_.as_deref(),
+        NaEdit::Inv { ref_, .. } => ref_.as_deref(),
+        _ => None,
+    }
+}
+
+impl ValidateRef for NaEdit {
+    fn validate(&self, ac: &str, start: i32, end: i32, data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError> {
+        // A `Repeat`'s `ref_` is the repeat unit, not the full interval's
+        // sequence, so it's checked against just the one unit at `start`
+        // rather than against `[start, end)` like every other variant.
+        if let NaEdit::Repeat { ref_: Some(unit), .. } = self {
+            if unit.is_empty() {
+                return Ok(());
+            }
+            let unit_len = unit.len() as i32;
+            let actual = data_provider.get_seq(ac, start, start + unit_len, IdentifierType::Unknown)?;
+            return require_iupac_match(unit, &actual, ac, start);
+        }
+
+        let Some(stated) = na_edit_ref(self) else {
+            return Ok(());
+        };
+        if stated.is_empty() {
+            return Ok(());
+        }
+
+        if stated.chars().all(|c| c.is_ascii_digit()) {
+            let stated_len: i32 = stated
+                .parse()
+                .map_err(|_| HgvsError::ValidationError(format!("invalid reference length {stated:?}")))?;
+            let actual_len = end - start;
+            return if stated_len == actual_len {
+                Ok(())
+            } else {
+                Err(HgvsError::ValidationError(format!(
+                    "reference length mismatch at {ac}:{start}: stated {stated_len}, interval spans {actual_len}"
+                )))
+            };
+        }
+
+        let actual = data_provider.get_seq(ac, start, end, IdentifierType::Unknown)?;
+        require_iupac_match(stated, &actual, ac, start)
+    }
+}
+
+impl ValidateRef for AaEdit {
+    fn validate(&self, _ac: &str, _start: i32, _end: i32, _data_provider: &dyn crate::data::DataProvider) -> Result<(), HgvsError> {
+        Err(HgvsError::UnsupportedOperation("reference validation not supported for protein variants (yet)".into()))
+    }
+}
+
 /// An interval spanning simple genomic or mitochondrial coordinates.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct SimpleInterval {
@@ -386,3 +911,508 @@ pub struct AAPosition {
     pub aa: String,
     pub uncertain: bool,
 }
+
+// --- Unparsing: Display impls that reconstruct canonical HGVS text from the
+// structs above. Each one is the inverse of the matching `parse_*` function
+// in `parser.rs`, re-emitting the structural flags (`uncertain`, `predicted`,
+// `Anchor::CdsEnd`'s `*` prefix) those functions record rather than the raw
+// input text, so `parse(x).to_string()` is a normalized form of `x` and not
+// merely an echo.
+
+impl fmt::Display for SimplePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.uncertain {
+            match self.end {
+                Some(end) if self.base.0 != 0 || end.0 != 0 => write!(f, "({}_{})", self.base.0, end.0),
+                Some(_) => write!(f, "?"),
+                None if self.base.0 == 0 => write!(f, "?"),
+                None => write!(f, "({})", self.base.0),
+            }
+        } else {
+            write!(f, "{}", self.base.0)
+        }
+    }
+}
+
+impl fmt::Display for SimpleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = match &self.end {
+            Some(end) => format!("{}_{}", self.start, end),
+            None => self.start.to_string(),
+        };
+        if self.uncertain {
+            write!(f, "({body})")
+        } else {
+            write!(f, "{body}")
+        }
+    }
+}
+
+impl fmt::Display for BaseOffsetPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut body = String::new();
+        if self.anchor == Anchor::CdsEnd {
+            body.push('*');
+        }
+        body.push_str(&self.base.0.to_string());
+        if let Some(offset) = &self.offset {
+            if offset.0 >= 0 {
+                body.push_str(&format!("+{}", offset.0));
+            } else {
+                body.push_str(&offset.0.to_string());
+            }
+        }
+        if self.uncertain {
+            write!(f, "({body})")
+        } else {
+            write!(f, "{body}")
+        }
+    }
+}
+
+impl fmt::Display for BaseOffsetInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = match &self.end {
+            Some(end) => format!("{}_{}", self.start, end),
+            None => self.start.to_string(),
+        };
+        if self.uncertain {
+            write!(f, "({body})")
+        } else {
+            write!(f, "{body}")
+        }
+    }
+}
+
+impl fmt::Display for AAPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.uncertain {
+            write!(f, "({}{})", self.aa, self.base.0)
+        } else {
+            write!(f, "{}{}", self.aa, self.base.0)
+        }
+    }
+}
+
+impl fmt::Display for AaInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = match &self.end {
+            Some(end) => format!("{}_{}", self.start, end),
+            None => self.start.to_string(),
+        };
+        if self.uncertain {
+            write!(f, "({body})")
+        } else {
+            write!(f, "{body}")
+        }
+    }
+}
+
+impl fmt::Display for NaEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NaEdit::RefAlt { ref_, alt, .. } => match (ref_.as_deref(), alt.as_deref()) {
+                (r, a) if r == a => write!(f, "="),
+                (Some(""), Some(a)) => write!(f, "ins{a}"),
+                (Some(r), Some(a)) if r.chars().count() == 1 && a.chars().count() == 1 => {
+                    write!(f, "{r}>{a}")
+                }
+                (_, Some(a)) => write!(f, "delins{a}"),
+                (_, None) => write!(f, "delins"),
+            },
+            NaEdit::Del { ref_, .. } => write!(f, "del{}", ref_.as_deref().unwrap_or("")),
+            NaEdit::Ins { alt, .. } => write!(f, "ins{}", alt.as_deref().unwrap_or("")),
+            NaEdit::Dup { ref_, .. } => write!(f, "dup{}", ref_.as_deref().unwrap_or("")),
+            NaEdit::Inv { ref_, .. } => write!(f, "inv{}", ref_.as_deref().unwrap_or("")),
+            NaEdit::Repeat { ref_, min, max, .. } => {
+                let unit = ref_.as_deref().unwrap_or("");
+                if min == max {
+                    write!(f, "{unit}[{min}]")
+                } else {
+                    write!(f, "{unit}[{min}_{max}]")
+                }
+            }
+            NaEdit::NACopy { copy, .. } => write!(f, "[{copy}]"),
+            NaEdit::None => Ok(()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for AaEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AaEdit::Identity { .. } => write!(f, "="),
+            AaEdit::Subst { alt, .. } => write!(f, "{alt}"),
+            AaEdit::Del { .. } => write!(f, "del"),
+            AaEdit::Ins { alt, .. } => write!(f, "ins{alt}"),
+            AaEdit::Dup { .. } => write!(f, "dup"),
+            AaEdit::DelIns { alt, .. } => write!(f, "delins{alt}"),
+            AaEdit::Fs {
+                alt, term, length, ..
+            } => {
+                write!(f, "{alt}fs")?;
+                if let Some(t) = term {
+                    write!(f, "{t}")?;
+                }
+                if let Some(l) = length {
+                    write!(f, "{l}")?;
+                }
+                Ok(())
+            }
+            AaEdit::Ext {
+                alt, aaterm, length, ..
+            } => {
+                write!(f, "{alt}ext")?;
+                if let Some(t) = aaterm {
+                    write!(f, "{t}")?;
+                }
+                if let Some(l) = length {
+                    write!(f, "{l}")?;
+                }
+                Ok(())
+            }
+            AaEdit::Repeat { ref_, min, max, .. } => {
+                let unit = ref_.as_deref().unwrap_or("");
+                if min == max {
+                    write!(f, "{unit}[{min}]")
+                } else {
+                    write!(f, "{unit}[{min}_{max}]")
+                }
+            }
+            AaEdit::Special { value, .. } => write!(f, "{value}"),
+            AaEdit::None => Ok(()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<I, E> fmt::Display for PosEdit<I, E>
+where
+    I: fmt::Display,
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut body = String::new();
+        if let Some(pos) = &self.pos {
+            body.push_str(&pos.to_string());
+        }
+        body.push_str(&self.edit.to_string());
+        if self.uncertain {
+            body.push('?');
+        }
+        if self.predicted {
+            write!(f, "({body})")
+        } else {
+            write!(f, "{body}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{DataProvider, IdentifierKind, Transcript};
+
+    /// Serves `get_seq` out of a fixed in-memory reference; every other
+    /// method is unused by the `to_vcf` tests and errors out if called.
+    struct MockProvider {
+        seq: &'static str,
+    }
+
+    impl DataProvider for MockProvider {
+        fn get_transcript(&self, _ac: &str, _ref_ac: Option<&str>) -> Result<Box<dyn Transcript>, HgvsError> {
+            Err(HgvsError::UnsupportedOperation("not used by these tests".into()))
+        }
+
+        fn get_seq(&self, _ac: &str, start: i32, end: i32, _kind: IdentifierType) -> Result<String, HgvsError> {
+            let s = start as usize;
+            let e = if end == -1 { self.seq.len() } else { end as usize };
+            Ok(self.seq[s..e.min(self.seq.len())].to_string())
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            _symbol: &str,
+            _from: IdentifierKind,
+            _to: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            Ok(vec![])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::GenomicAccession)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            _pos: crate::coords::TranscriptPos,
+            _offset: IntronicOffset,
+        ) -> Result<(String, GenomicPos), HgvsError> {
+            Err(HgvsError::UnsupportedOperation("not used by these tests".into()))
+        }
+    }
+
+    #[test]
+    fn test_spdi_to_vcf_passes_through_non_empty_alleles() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let record = spdi_to_vcf("NC_000001.11:100:A:G", &dp).unwrap();
+        assert_eq!(record.chrom, "NC_000001.11");
+        assert_eq!(record.pos, 101);
+        assert_eq!(record.ref_, "A");
+        assert_eq!(record.alt, "G");
+    }
+
+    #[test]
+    fn test_spdi_to_vcf_left_pads_pure_deletion() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        // del="ACGT" at pos0=4 (the second "ACGT") -> pad with base at pos0-1=3 ("T").
+        let record = spdi_to_vcf("NC_000001.11:4:ACGT:", &dp).unwrap();
+        assert_eq!(record.pos, 4);
+        assert_eq!(record.ref_, "TACGT");
+        assert_eq!(record.alt, "T");
+    }
+
+    #[test]
+    fn test_spdi_to_vcf_left_pads_pure_insertion() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let record = spdi_to_vcf("NC_000001.11:4::GG", &dp).unwrap();
+        assert_eq!(record.pos, 4);
+        assert_eq!(record.ref_, "T");
+        assert_eq!(record.alt, "TGG");
+    }
+
+    #[test]
+    fn test_spdi_to_vcf_right_pads_at_contig_start() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let record = spdi_to_vcf("NC_000001.11:0:AC:", &dp).unwrap();
+        assert_eq!(record.pos, 1);
+        assert_eq!(record.ref_, "ACG");
+        assert_eq!(record.alt, "G");
+    }
+
+    fn del_variant(ref_start: i32, ref_end: i32) -> GVariant {
+        GVariant {
+            ac: "NC_000001.11".to_string(),
+            gene: None,
+            posedit: PosEdit {
+                pos: Some(SimpleInterval {
+                    start: SimplePosition { base: HgvsGenomicPos(ref_start), end: None, uncertain: false },
+                    end: Some(SimplePosition { base: HgvsGenomicPos(ref_end), end: None, uncertain: false }),
+                    uncertain: false,
+                }),
+                edit: NaEdit::Del { ref_: None, uncertain: false },
+                uncertain: false,
+                predicted: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_to_vcf_sv_below_threshold_expands_literal_del() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let record = del_variant(1, 10).to_vcf_sv(&dp, SvThreshold(11)).unwrap();
+        assert_eq!(record.alt, "G"); // right-pad base (contig-start del has no preceding base)
+        assert!(record.info.is_empty());
+        assert_eq!(record.ref_.len(), 11); // pad base + 10 deleted bases
+    }
+
+    #[test]
+    fn test_to_vcf_sv_above_threshold_emits_symbolic_del() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let record = del_variant(1, 10).to_vcf_sv(&dp, SvThreshold(10)).unwrap();
+        assert_eq!(record.pos, 1);
+        assert_eq!(record.alt, "<DEL>");
+        assert_eq!(record.ref_, "A");
+        assert_eq!(record.info, vec![("END".to_string(), "10".to_string()), ("SVLEN".to_string(), "-10".to_string())]);
+    }
+
+    #[test]
+    fn test_to_vcf_sv_above_threshold_emits_symbolic_dup() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let mut variant = del_variant(1, 10);
+        variant.posedit.edit = NaEdit::Dup { ref_: None, uncertain: false };
+        let record = variant.to_vcf_sv(&dp, SvThreshold(10)).unwrap();
+        assert_eq!(record.alt, "<DUP>");
+        assert_eq!(record.info, vec![("END".to_string(), "10".to_string()), ("SVLEN".to_string(), "10".to_string())]);
+    }
+
+    #[test]
+    fn test_to_vcf_sv_large_ins_is_symbolic_small_ins_is_literal() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let mut variant = del_variant(5, 6);
+        variant.posedit.edit = NaEdit::Ins { alt: Some("A".repeat(10)), uncertain: false };
+        let record = variant.to_vcf_sv(&dp, SvThreshold(10)).unwrap();
+        assert_eq!(record.alt, "<INS>");
+        assert_eq!(record.info[1], ("SVLEN".to_string(), "10".to_string()));
+
+        variant.posedit.edit = NaEdit::Ins { alt: Some("AC".to_string()), uncertain: false };
+        let record = variant.to_vcf_sv(&dp, SvThreshold(10)).unwrap();
+        assert!(record.alt != "<INS>");
+        assert!(record.info.is_empty());
+    }
+
+    #[test]
+    fn test_to_vcf_sv_stated_length_ins_is_symbolic_by_parsed_length_not_digit_count() {
+        // `ins3` states a 3-base insertion, not a literal one-character
+        // sequence "3" -- the symbolic length must come from parsing the
+        // digits, so a threshold of 3 (not 1) is what makes it symbolic.
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let mut variant = del_variant(5, 6);
+        variant.posedit.edit = NaEdit::Ins { alt: Some("3".to_string()), uncertain: false };
+        let record = variant.to_vcf_sv(&dp, SvThreshold(3)).unwrap();
+        assert_eq!(record.alt, "<INS>");
+        assert_eq!(record.info[1], ("SVLEN".to_string(), "3".to_string()));
+    }
+
+    #[test]
+    fn test_to_spdi_errors_on_stated_length_ins_without_a_sequence() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let edit = NaEdit::Ins { alt: Some("50".to_string()), uncertain: false };
+        let err = edit.to_spdi("NM_0001.1", 5, 5, &dp).unwrap_err();
+        assert!(matches!(err, HgvsError::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_to_spdi_errors_on_fully_unresolved_ins() {
+        let dp = MockProvider { seq: "ACGTACGTACGTACGTACGT" };
+        let edit = NaEdit::Ins { alt: None, uncertain: false };
+        let err = edit.to_spdi("NM_0001.1", 5, 5, &dp).unwrap_err();
+        assert!(matches!(err, HgvsError::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_repeat_spdi_counts_observed_copies_for_minimal_delins() {
+        // Reference holds exactly 3 copies of the unit; asking for 5 should
+        // delete the 3 observed copies and insert 5, not naively delete 1
+        // (the interval span) and insert 5.
+        let dp = MockProvider { seq: "TGGAACTGGAACTGGAAC" };
+        let edit = NaEdit::Repeat { ref_: Some("TGGAAC".to_string()), min: 5, max: 5, uncertain: false };
+        let spdi = edit.to_spdi("NM_001291285.3", 0, 6, &dp).unwrap();
+        assert_eq!(
+            spdi,
+            "NM_001291285.3:0:TGGAACTGGAACTGGAAC:TGGAACTGGAACTGGAACTGGAACTGGAAC"
+        );
+    }
+
+    #[test]
+    fn test_repeat_spdi_is_a_noop_when_observed_equals_max() {
+        let dp = MockProvider { seq: "TGGAACTGGAACTGGAAC" };
+        let edit = NaEdit::Repeat { ref_: Some("TGGAAC".to_string()), min: 3, max: 3, uncertain: false };
+        let spdi = edit.to_spdi("NM_001291285.3", 0, 6, &dp).unwrap();
+        assert_eq!(spdi, "NM_001291285.3:0:TGGAACTGGAACTGGAAC:TGGAACTGGAACTGGAAC");
+    }
+
+    #[test]
+    fn test_repeat_spdi_errors_when_reference_does_not_start_with_unit() {
+        let dp = MockProvider { seq: "AAAAAAAAAAAAAAAAAA" };
+        let edit = NaEdit::Repeat { ref_: Some("TGGAAC".to_string()), min: 5, max: 5, uncertain: false };
+        let err = edit.to_spdi("NM_001291285.3", 0, 6, &dp).unwrap_err();
+        assert!(matches!(err, HgvsError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_validate_passes_on_exact_reference_match() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let edit = NaEdit::RefAlt { ref_: Some("ACGT".to_string()), alt: Some("G".to_string()), uncertain: false };
+        edit.validate("NC_1", 0, 4, &dp).unwrap();
+    }
+
+    #[test]
+    fn test_validate_accepts_iupac_ambiguity_code_in_stated_ref() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        // `R` (purine, A or G) covers the actual `A` at position 0.
+        let edit = NaEdit::Del { ref_: Some("RCGT".to_string()), uncertain: false };
+        edit.validate("NC_1", 0, 4, &dp).unwrap();
+    }
+
+    #[test]
+    fn test_validate_rejects_genuine_reference_mismatch() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let edit = NaEdit::Del { ref_: Some("TTTT".to_string()), uncertain: false };
+        let err = edit.validate("NC_1", 0, 4, &dp).unwrap_err();
+        assert!(matches!(err, HgvsError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_validate_length_only_ref_checks_digit_count_against_interval() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let edit = NaEdit::Del { ref_: Some("4".to_string()), uncertain: false };
+        edit.validate("NC_1", 0, 4, &dp).unwrap();
+
+        let wrong_len = NaEdit::Del { ref_: Some("5".to_string()), uncertain: false };
+        let err = wrong_len.validate("NC_1", 0, 4, &dp).unwrap_err();
+        assert!(matches!(err, HgvsError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_validate_is_a_noop_for_implicit_reference() {
+        let dp = MockProvider { seq: "ACGTACGT" };
+        let edit = NaEdit::Del { ref_: None, uncertain: false };
+        edit.validate("NC_1", 0, 4, &dp).unwrap();
+    }
+
+    #[test]
+    fn test_simple_interval_round_trip() {
+        let v = crate::parse_hgvs_variant("NC_000001.11:g.100_200del").unwrap();
+        match v {
+            SequenceVariant::Genomic(v) => assert_eq!(v.posedit.to_string(), "100_200del"),
+            _ => panic!("expected genomic variant"),
+        }
+    }
+
+    #[test]
+    fn test_base_offset_interval_round_trip_with_intron_offset() {
+        let v = crate::parse_hgvs_variant("NM_000123.4:c.76-5_76-3del").unwrap();
+        match v {
+            SequenceVariant::Coding(v) => assert_eq!(v.posedit.to_string(), "76-5_76-3del"),
+            _ => panic!("expected coding variant"),
+        }
+    }
+
+    #[test]
+    fn test_repeat_round_trip() {
+        let v = crate::parse_hgvs_variant("NM_001291285.3:c.7035TGGAAC[3]").unwrap();
+        match v {
+            SequenceVariant::Coding(v) => {
+                assert_eq!(v.posedit.to_string(), "7035TGGAAC[3]")
+            }
+            _ => panic!("expected coding variant"),
+        }
+    }
+
+    #[test]
+    fn test_extension_round_trip() {
+        let v = crate::parse_hgvs_variant("NP_001116078.1:p.Ter312Argext*5").unwrap();
+        match v {
+            SequenceVariant::Protein(v) => {
+                assert_eq!(v.posedit.to_string(), "Ter312Argext*5")
+            }
+            _ => panic!("expected protein variant"),
+        }
+    }
+
+    #[test]
+    fn test_predicted_posedit_wraps_in_parens() {
+        let pos_edit: PosEdit<SimpleInterval, NaEdit> = PosEdit {
+            pos: Some(SimpleInterval {
+                start: SimplePosition {
+                    base: HgvsGenomicPos(76),
+                    end: None,
+                    uncertain: false,
+                },
+                end: None,
+                uncertain: false,
+            }),
+            edit: NaEdit::RefAlt {
+                ref_: Some("A".to_string()),
+                alt: Some("G".to_string()),
+                uncertain: false,
+            },
+            uncertain: false,
+            predicted: true,
+        };
+        assert_eq!(pos_edit.to_string(), "(76A>G)");
+    }
+}
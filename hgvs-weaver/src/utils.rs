@@ -28,6 +28,7 @@ pub enum Residue {
     Glx, // Z
     Xaa, // X
     Sec, // U
+    Pyl, // O
     Ter, // *
 }
 
@@ -58,6 +59,7 @@ impl Display for Residue {
             Residue::Glx => "Z",
             Residue::Xaa => "X",
             Residue::Sec => "U",
+            Residue::Pyl => "O",
             Residue::Ter => "*",
         };
         write!(f, "{}", s)
@@ -109,6 +111,8 @@ pub fn aa1_to_aa3(aa1: char) -> &'static str {
         'Y' => "Tyr",
         'V' => "Val",
         '*' => "Ter",
+        'U' => "Sec",
+        'O' => "Pyl",
         'X' => "Xaa",
         _ => "Xaa",
     }
@@ -118,39 +122,40 @@ pub fn seq1_to_aa3(seq1: &str) -> String {
     seq1.chars().map(aa1_to_aa3).collect()
 }
 
+/// Translates a CDS using the standard genetic code. Equivalent to
+/// `translate_cds_with(cds, GeneticCodeTable::Standard, false)`.
 pub fn translate_cds(cds: &str) -> String {
+    translate_cds_with(cds, crate::genetic_code::GeneticCodeTable::Standard, false)
+}
+
+/// Translates a CDS under a specific [`crate::genetic_code::GeneticCodeTable`],
+/// stopping after appending the first in-frame stop codon as `*`.
+///
+/// When `translate_as_orf` is set, the first codon is forced to `M`
+/// (initiator methionine) if it is one of `table`'s valid start codons,
+/// per the usual convention for translating an open reading frame rather
+/// than an arbitrary internal window of a CDS.
+pub fn translate_cds_with(
+    cds: &str,
+    table: crate::genetic_code::GeneticCodeTable,
+    translate_as_orf: bool,
+) -> String {
     let mut aa = String::new();
-    for i in (0..cds.len()).step_by(3) {
+    for (codon_idx, i) in (0..cds.len()).step_by(3).enumerate() {
         if i + 3 > cds.len() {
             break;
         }
-        let codon = &cds[i..i + 3];
-        let res = match codon.to_uppercase().as_str() {
-            "TTT" | "TTC" => 'F',
-            "TTA" | "TTG" => 'L',
-            "CTT" | "CTC" | "CTA" | "CTG" => 'L',
-            "ATT" | "ATC" | "ATA" => 'I',
-            "ATG" => 'M',
-            "GTT" | "GTC" | "GTA" | "GTG" => 'V',
-            "TCT" | "TCC" | "TCA" | "TCG" => 'S',
-            "CCT" | "CCC" | "CCA" | "CCG" => 'P',
-            "ACT" | "ACC" | "ACA" | "ACG" => 'T',
-            "GCT" | "GCC" | "GCA" | "GCG" => 'A',
-            "TAT" | "TAC" => 'Y',
-            "TAA" | "TAG" | "TGA" => '*',
-            "CAT" | "CAC" => 'H',
-            "CAA" | "CAG" => 'Q',
-            "AAT" | "AAC" => 'N',
-            "AAA" | "AAG" => 'K',
-            "GAT" | "GAC" => 'D',
-            "GAA" | "GAG" => 'E',
-            "TGT" | "TGC" => 'C',
-            "TGG" => 'W',
-            "CGT" | "CGC" | "CGA" | "CGG" => 'R',
-            "AGT" | "AGC" => 'S',
-            "AGA" | "AGG" => 'R',
-            "GGT" | "GGC" | "GGA" | "GGG" => 'G',
-            _ => 'X',
+        let upper = cds[i..i + 3].to_uppercase();
+        let mut chars = upper.chars();
+        let codon = [
+            chars.next().unwrap_or('X'),
+            chars.next().unwrap_or('X'),
+            chars.next().unwrap_or('X'),
+        ];
+        let res = if codon_idx == 0 && translate_as_orf && table.is_start_codon(codon) {
+            'M'
+        } else {
+            table.translate_codon(codon)
         };
         aa.push(res);
         if res == '*' {
@@ -185,6 +190,8 @@ pub fn aa3_to_aa1(aa3: &str) -> String {
         "asx" => "B".to_string(),
         "glx" => "Z".to_string(),
         "xaa" => "X".to_string(),
+        "sec" => "U".to_string(),
+        "pyl" => "O".to_string(),
         "ter" | "stop" | "*" => "*".to_string(),
         // 1-letter codes are returned as-is (uppercase)
         s if s.len() == 1 => s.to_uppercase(),
@@ -196,6 +203,201 @@ pub fn normalize_aa(s: &str) -> String {
     aa3_to_aa1(s)
 }
 
+impl Residue {
+    pub fn three_letter(&self) -> &'static str {
+        match self {
+            Residue::Ala => "Ala",
+            Residue::Arg => "Arg",
+            Residue::Asn => "Asn",
+            Residue::Asp => "Asp",
+            Residue::Cys => "Cys",
+            Residue::Gln => "Gln",
+            Residue::Glu => "Glu",
+            Residue::Gly => "Gly",
+            Residue::His => "His",
+            Residue::Ile => "Ile",
+            Residue::Leu => "Leu",
+            Residue::Lys => "Lys",
+            Residue::Met => "Met",
+            Residue::Phe => "Phe",
+            Residue::Pro => "Pro",
+            Residue::Ser => "Ser",
+            Residue::Thr => "Thr",
+            Residue::Trp => "Trp",
+            Residue::Tyr => "Tyr",
+            Residue::Val => "Val",
+            Residue::Asx => "Asx",
+            Residue::Glx => "Glx",
+            Residue::Xaa => "Xaa",
+            Residue::Sec => "Sec",
+            Residue::Pyl => "Pyl",
+            Residue::Ter => "Ter",
+        }
+    }
+
+    pub fn render(&self, notation: AaNotation) -> String {
+        match notation {
+            AaNotation::OneLetter => self.to_string(),
+            AaNotation::ThreeLetter => self.three_letter().to_string(),
+        }
+    }
+}
+
+/// One-letter vs three-letter amino-acid display. HGVS recommends 3-letter
+/// codes for human-readable reports; most pipelines emit 1-letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AaNotation {
+    OneLetter,
+    ThreeLetter,
+}
+
+/// Rewrites a residue string (one or more codes, already in either
+/// alphabet) into `notation`. Returns the input unchanged if it can't be
+/// decomposed into residues, since [`render_protein`] calls this on fields
+/// that are sometimes blank (e.g. `Del`'s unused `ref_`).
+fn render_residues(s: &str, notation: AaNotation) -> String {
+    if s.is_empty() {
+        return s.to_string();
+    }
+    match decompose_aa(s) {
+        Ok(residues) => residues.iter().map(|r| r.render(notation)).collect(),
+        Err(_) => s.to_string(),
+    }
+}
+
+fn render_aa_position(
+    pos: &crate::structs::AAPosition,
+    notation: AaNotation,
+) -> crate::structs::AAPosition {
+    crate::structs::AAPosition {
+        base: pos.base.clone(),
+        aa: render_residues(&pos.aa, notation),
+        uncertain: pos.uncertain,
+    }
+}
+
+/// Rewrites the residue codes embedded in an [`crate::structs::AaEdit`]
+/// into `notation`, leaving positions/lengths/uncertainty untouched.
+/// Edit kinds this doesn't yet recognize are passed through unchanged
+/// rather than guessed at.
+fn render_aa_edit(edit: &crate::structs::AaEdit, notation: AaNotation) -> crate::structs::AaEdit {
+    use crate::structs::AaEdit;
+    match edit {
+        AaEdit::Identity { uncertain } => AaEdit::Identity {
+            uncertain: *uncertain,
+        },
+        AaEdit::Subst {
+            ref_,
+            alt,
+            uncertain,
+        } => AaEdit::Subst {
+            ref_: render_residues(ref_, notation),
+            alt: render_residues(alt, notation),
+            uncertain: *uncertain,
+        },
+        AaEdit::Del { ref_, uncertain } => AaEdit::Del {
+            ref_: render_residues(ref_, notation),
+            uncertain: *uncertain,
+        },
+        AaEdit::Ins { alt, uncertain } => AaEdit::Ins {
+            alt: render_residues(alt, notation),
+            uncertain: *uncertain,
+        },
+        AaEdit::Dup { ref_, uncertain } => AaEdit::Dup {
+            ref_: ref_.as_deref().map(|s| render_residues(s, notation)),
+            uncertain: *uncertain,
+        },
+        AaEdit::DelIns {
+            ref_,
+            alt,
+            uncertain,
+        } => AaEdit::DelIns {
+            ref_: render_residues(ref_, notation),
+            alt: render_residues(alt, notation),
+            uncertain: *uncertain,
+        },
+        AaEdit::Fs {
+            ref_,
+            alt,
+            term,
+            length,
+            uncertain,
+        } => AaEdit::Fs {
+            ref_: render_residues(ref_, notation),
+            alt: render_residues(alt, notation),
+            term: term.as_deref().map(|s| render_residues(s, notation)),
+            length: length.clone(),
+            uncertain: *uncertain,
+        },
+        AaEdit::Ext {
+            ref_,
+            alt,
+            aaterm,
+            length,
+            uncertain,
+        } => AaEdit::Ext {
+            ref_: render_residues(ref_, notation),
+            alt: render_residues(alt, notation),
+            aaterm: aaterm.as_deref().map(|s| render_residues(s, notation)),
+            length: length.clone(),
+            uncertain: *uncertain,
+        },
+        AaEdit::Repeat {
+            ref_,
+            min,
+            max,
+            uncertain,
+        } => AaEdit::Repeat {
+            ref_: ref_.as_deref().map(|s| render_residues(s, notation)),
+            min: *min,
+            max: *max,
+            uncertain: *uncertain,
+        },
+        AaEdit::Special { value, uncertain } => AaEdit::Special {
+            value: value.clone(),
+            uncertain: *uncertain,
+        },
+        other => other.clone(),
+    }
+}
+
+/// Renders a parsed protein variant's residue codes in `notation`
+/// (1-letter or 3-letter), preserving uncertainty parentheses (`(...)`/`?`)
+/// and every other part of the notation untouched.
+///
+/// This rewrites the *parsed* [`crate::structs::AaEdit`]/`AAPosition`
+/// structure -- never the rendered string -- so a substring inside an
+/// accession or gene symbol that merely looks like a residue code is never
+/// mistaken for one. Contrast with
+/// [`crate::equivalence::VariantEquivalence::normalize_format`], whose
+/// one-way 3-to-1-letter collapse (and dropped parens/`?`) is a lossy
+/// comparison key, not a display form.
+pub fn render_protein(var: &crate::structs::PVariant, notation: AaNotation) -> String {
+    use crate::structs::{PosEdit, SequenceVariant};
+
+    let pos = var.posedit.pos.as_ref().map(|interval| crate::structs::AaInterval {
+        start: render_aa_position(&interval.start, notation),
+        end: interval
+            .end
+            .as_ref()
+            .map(|end| render_aa_position(end, notation)),
+        uncertain: interval.uncertain,
+    });
+
+    let rendered = crate::structs::PVariant {
+        ac: var.ac.clone(),
+        gene: var.gene.clone(),
+        posedit: PosEdit {
+            pos,
+            edit: render_aa_edit(&var.posedit.edit, notation),
+            uncertain: var.posedit.uncertain,
+            predicted: var.posedit.predicted,
+        },
+    };
+
+    SequenceVariant::Protein(rendered).to_string()
+}
+
 pub fn decompose_aa(s: &str) -> Result<Vec<Residue>, HgvsError> {
     if s.is_empty() {
         return Ok(Vec::new());
@@ -236,6 +438,7 @@ pub fn decompose_aa(s: &str) -> Result<Vec<Residue>, HgvsError> {
                 "xaa" => Some(Residue::Xaa),
                 "ter" | "stop" => Some(Residue::Ter),
                 "sec" => Some(Residue::Sec),
+                "pyl" => Some(Residue::Pyl),
                 _ => None,
             };
 
@@ -282,6 +485,7 @@ pub fn decompose_aa(s: &str) -> Result<Vec<Residue>, HgvsError> {
             'Z' => Some(Residue::Glx),
             'X' => Some(Residue::Xaa),
             'U' => Some(Residue::Sec),
+            'O' => Some(Residue::Pyl),
             '*' => Some(Residue::Ter),
             _ => None,
         };
@@ -303,3 +507,408 @@ pub fn decompose_aa(s: &str) -> Result<Vec<Residue>, HgvsError> {
         s
     )))
 }
+
+/// A single `ExonData::cigar` operation relevant to transcript<->genome
+/// coordinate projection: `=`/`X`/legacy `M` advance both cursors, `I`
+/// (a transcript base with no genomic counterpart) advances only the
+/// transcript cursor, and `D`/`N` (a genomic gap) advance only the genomic
+/// one. `D` and `N` are kept distinct so callers can tell an alignment gap
+/// from an intron retained in the exon's own CIGAR, even though both walk
+/// the same way.
+///
+/// This is the walking primitive `c_to_g`-style projection needs to stop
+/// assuming every exon is a single co-linear `N=` block; wiring it into
+/// `TranscriptMapper::n_to_g` itself is out of scope here since that type
+/// lives outside this crate's currently checked-out sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CigarOp {
+    Match,
+    Mismatch,
+    /// Relative to the genome: the transcript has a base the genome doesn't.
+    Ins,
+    /// Relative to the genome: the genome has bases the transcript doesn't.
+    Del,
+    /// Like `Del`, but an intron rather than an alignment gap.
+    Intron,
+}
+
+impl CigarOp {
+    fn consumes_transcript(self) -> bool {
+        matches!(self, CigarOp::Match | CigarOp::Mismatch | CigarOp::Ins)
+    }
+
+    fn consumes_genome(self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match | CigarOp::Mismatch | CigarOp::Del | CigarOp::Intron
+        )
+    }
+}
+
+/// Parses an exon alignment CIGAR string, e.g. `"50=1I40=2D30="`, into its
+/// run-length-encoded operations. Legacy `M` runs (as emitted by providers
+/// that don't distinguish match from mismatch) are treated as `Match`.
+pub fn parse_cigar(cigar: &str) -> Result<Vec<(CigarOp, u32)>, HgvsError> {
+    let mut ops = Vec::new();
+    let mut len_digits = String::new();
+    for c in cigar.chars() {
+        if c.is_ascii_digit() {
+            len_digits.push(c);
+            continue;
+        }
+        if len_digits.is_empty() {
+            return Err(HgvsError::ValidationError(format!(
+                "CIGAR op '{c}' with no preceding run length in {cigar:?}"
+            )));
+        }
+        let len: u32 = len_digits.parse().map_err(|_| {
+            HgvsError::ValidationError(format!("invalid CIGAR run length in {cigar:?}"))
+        })?;
+        len_digits.clear();
+        let op = match c {
+            '=' | 'M' => CigarOp::Match,
+            'X' => CigarOp::Mismatch,
+            'I' => CigarOp::Ins,
+            'D' => CigarOp::Del,
+            'N' => CigarOp::Intron,
+            other => {
+                return Err(HgvsError::ValidationError(format!(
+                    "unsupported CIGAR op '{other}' in {cigar:?}"
+                )))
+            }
+        };
+        ops.push((op, len));
+    }
+    if !len_digits.is_empty() {
+        return Err(HgvsError::ValidationError(format!(
+            "trailing run length with no operator in {cigar:?}"
+        )));
+    }
+    Ok(ops)
+}
+
+/// Sum of the `=`/`X`/`I` run lengths in `ops`, i.e. the transcript span the
+/// CIGAR covers. Exon projection relies on this matching the exon's own
+/// `transcript_end - transcript_start`; mismatches there mean the provider's
+/// CIGAR disagrees with its own exon boundaries.
+pub fn cigar_transcript_span(ops: &[(CigarOp, u32)]) -> i64 {
+    ops.iter()
+        .filter(|(op, _)| op.consumes_transcript())
+        .map(|(_, len)| *len as i64)
+        .sum()
+}
+
+/// Sum of the `=`/`X`/`D`/`N` run lengths in `ops`, i.e. the genomic span
+/// the CIGAR covers.
+pub fn cigar_genomic_span(ops: &[(CigarOp, u32)]) -> i64 {
+    ops.iter()
+        .filter(|(op, _)| op.consumes_genome())
+        .map(|(_, len)| *len as i64)
+        .sum()
+}
+
+/// Where a 0-based transcript-relative position lands within a parsed
+/// CIGAR op list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CigarLookup {
+    pub op: CigarOp,
+    /// 0-based offset from the start of the alignment's genomic span.
+    pub genomic_offset: i64,
+    /// Set when `transcript_pos` falls inside an `Ins` block: there is no
+    /// single genomic coordinate for it, so `genomic_offset` is the
+    /// flanking genomic position and this is the 1-based offset into the
+    /// insertion run.
+    pub insertion_offset: Option<i64>,
+}
+
+/// Walks `ops` to find which operation a 0-based transcript-relative
+/// position falls into and the genomic offset it projects to. A position
+/// inside an `Ins` block has no single genomic coordinate and is reported
+/// against the flanking genomic offset instead; a `Del` block has no
+/// transcript-relative position at all and is simply walked over, so the
+/// genomic offsets of everything downstream of it account for the missing
+/// bases. Returns `None` if `transcript_pos` is past the end of every
+/// transcript-consuming block.
+pub fn cigar_op_at(ops: &[(CigarOp, u32)], transcript_pos: i64) -> Option<CigarLookup> {
+    let mut t_cursor: i64 = 0;
+    let mut g_cursor: i64 = 0;
+    for &(op, len) in ops {
+        let len = len as i64;
+        let t_span = if op.consumes_transcript() { len } else { 0 };
+        if t_span > 0 && transcript_pos >= t_cursor && transcript_pos < t_cursor + t_span {
+            let within = transcript_pos - t_cursor;
+            return Some(CigarLookup {
+                op,
+                genomic_offset: if op.consumes_genome() {
+                    g_cursor + within
+                } else {
+                    g_cursor
+                },
+                insertion_offset: if op.consumes_genome() {
+                    None
+                } else {
+                    Some(within + 1)
+                },
+            });
+        }
+        t_cursor += t_span;
+        if op.consumes_genome() {
+            g_cursor += len;
+        }
+    }
+    None
+}
+
+/// Where a transcript-relative position lands once an exon's CIGAR-aware
+/// genomic offset ([`cigar_op_at`]) is resolved against the exon's own
+/// genomic span and strand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExonProjection {
+    /// `transcript_pos` aligns 1:1 to this 0-based genomic position.
+    Aligned(i64),
+    /// `transcript_pos` falls inside a transcript-side gap (an `I` run with
+    /// no genomic counterpart). `flank` is the neighboring genomic position
+    /// and `offset` is the 1-based distance into the insertion.
+    Gap { flank: i64, offset: i64 },
+}
+
+/// Projects a 0-based transcript-relative position onto a 0-based genomic
+/// position via an exon's alignment CIGAR, per the `I`/`D`/`N`/`=`/`X`
+/// walk in [`cigar_op_at`]. `exon_genomic_start` is the 0-based genomic
+/// coordinate of the exon's first aligned base on the plus strand; when
+/// `alt_strand` is negative, offsets are mirrored from the far end of the
+/// exon's genomic span instead of added to its start.
+///
+/// Returns `None` if `transcript_pos` doesn't fall within this exon's
+/// transcript span at all.
+pub fn project_exon_position(
+    cigar: &str,
+    exon_genomic_start: i64,
+    alt_strand: i32,
+    transcript_pos: i64,
+) -> Result<Option<ExonProjection>, HgvsError> {
+    let ops = parse_cigar(cigar)?;
+    let hit = match cigar_op_at(&ops, transcript_pos) {
+        Some(h) => h,
+        None => return Ok(None),
+    };
+    let to_genomic = |offset: i64| -> i64 {
+        if alt_strand < 0 {
+            exon_genomic_start + cigar_genomic_span(&ops) - 1 - offset
+        } else {
+            exon_genomic_start + offset
+        }
+    };
+    Ok(Some(match hit.insertion_offset {
+        Some(offset) => ExonProjection::Gap {
+            flank: to_genomic(hit.genomic_offset),
+            offset,
+        },
+        None => ExonProjection::Aligned(to_genomic(hit.genomic_offset)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genetic_code::GeneticCodeTable;
+
+    #[test]
+    fn test_translate_cds_standard_table_matches_translate_cds() {
+        assert_eq!(translate_cds("ATGGCTTAA"), "MA*");
+        assert_eq!(
+            translate_cds_with("ATGGCTTAA", GeneticCodeTable::Standard, false),
+            "MA*"
+        );
+    }
+
+    #[test]
+    fn test_translate_cds_with_picks_up_table_differences() {
+        // ATA is Ile under the standard table but Met under vertebrate mito.
+        assert_eq!(
+            translate_cds_with("ATAGCTTGA", GeneticCodeTable::Standard, false),
+            "IA*"
+        );
+        assert_eq!(
+            translate_cds_with("ATAGCTTGA", GeneticCodeTable::VertebrateMitochondrial, false),
+            "MAW"
+        );
+    }
+
+    #[test]
+    fn test_translate_cds_with_orf_forces_leading_start_codon_to_met() {
+        // GTG isn't a start codon under the standard table, so the flag has no effect there...
+        assert_eq!(
+            translate_cds_with("GTGGCTTAA", GeneticCodeTable::Standard, true),
+            "VA*"
+        );
+        // ...but it is under vertebrate mito, so the first residue becomes M.
+        assert_eq!(
+            translate_cds_with("GTGGCTTAA", GeneticCodeTable::VertebrateMitochondrial, true),
+            "MA*"
+        );
+    }
+
+    #[test]
+    fn test_aa1_to_aa3_round_trips_sec_and_pyl() {
+        assert_eq!(aa1_to_aa3('U'), "Sec");
+        assert_eq!(aa1_to_aa3('O'), "Pyl");
+        assert_eq!(aa3_to_aa1("Sec"), "U");
+        assert_eq!(aa3_to_aa1("Pyl"), "O");
+    }
+
+    #[test]
+    fn test_decompose_aa_handles_sec_and_pyl_in_both_notations() {
+        assert_eq!(
+            decompose_aa("UO").unwrap(),
+            vec![Residue::Sec, Residue::Pyl]
+        );
+        assert_eq!(
+            decompose_aa("SecPyl").unwrap(),
+            vec![Residue::Sec, Residue::Pyl]
+        );
+    }
+
+    #[test]
+    fn test_parse_cigar_reads_indel_bearing_alignment() {
+        let ops = parse_cigar("50=1I40=2D30=").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                (CigarOp::Match, 50),
+                (CigarOp::Ins, 1),
+                (CigarOp::Match, 40),
+                (CigarOp::Del, 2),
+                (CigarOp::Match, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cigar_rejects_unsupported_op() {
+        assert!(parse_cigar("10Z").is_err());
+        assert!(parse_cigar("Z").is_err());
+    }
+
+    #[test]
+    fn test_cigar_op_at_walks_match_blocks() {
+        let ops = parse_cigar("50=1I40=2D30=").unwrap();
+        let hit = cigar_op_at(&ops, 10).unwrap();
+        assert_eq!(hit.op, CigarOp::Match);
+        assert_eq!(hit.genomic_offset, 10);
+        assert_eq!(hit.insertion_offset, None);
+    }
+
+    #[test]
+    fn test_cigar_op_at_reports_insertion_offset_against_flanking_genomic_position() {
+        let ops = parse_cigar("50=1I40=2D30=").unwrap();
+        let hit = cigar_op_at(&ops, 50).unwrap();
+        assert_eq!(hit.op, CigarOp::Ins);
+        assert_eq!(hit.genomic_offset, 50);
+        assert_eq!(hit.insertion_offset, Some(1));
+
+        // The base right after the insertion aligns back to the same
+        // genomic position, since the insertion consumed no genome.
+        let after = cigar_op_at(&ops, 51).unwrap();
+        assert_eq!(after.op, CigarOp::Match);
+        assert_eq!(after.genomic_offset, 50);
+    }
+
+    #[test]
+    fn test_cigar_op_at_accounts_for_genomic_deletion_between_transcript_bases() {
+        let ops = parse_cigar("50=1I40=2D30=").unwrap();
+        // Last base of the second match block (transcript position 90)
+        // sits right before the 2bp genomic deletion.
+        let before = cigar_op_at(&ops, 90).unwrap();
+        assert_eq!(before.genomic_offset, 89);
+
+        // The next transcript base (91) has no transcript-side gap, but
+        // its genomic offset jumps by 3 (1 for the base itself, 2 for the
+        // skipped deletion) rather than 1.
+        let after = cigar_op_at(&ops, 91).unwrap();
+        assert_eq!(after.op, CigarOp::Match);
+        assert_eq!(after.genomic_offset, 92);
+    }
+
+    #[test]
+    fn test_cigar_op_at_past_the_end_returns_none() {
+        let ops = parse_cigar("50=1I40=2D30=").unwrap();
+        assert!(cigar_op_at(&ops, 121).is_none());
+    }
+
+    #[test]
+    fn test_parse_cigar_reads_intron_op_distinctly_from_deletion() {
+        let ops = parse_cigar("10=500N10=2D10=").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                (CigarOp::Match, 10),
+                (CigarOp::Intron, 500),
+                (CigarOp::Match, 10),
+                (CigarOp::Del, 2),
+                (CigarOp::Match, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cigar_op_at_walks_over_an_intron_like_a_deletion() {
+        let ops = parse_cigar("10=500N10=").unwrap();
+        let before = cigar_op_at(&ops, 9).unwrap();
+        assert_eq!(before.genomic_offset, 9);
+        let after = cigar_op_at(&ops, 10).unwrap();
+        assert_eq!(after.op, CigarOp::Match);
+        assert_eq!(after.genomic_offset, 510);
+    }
+
+    #[test]
+    fn test_cigar_transcript_and_genomic_span_account_for_indels_and_introns() {
+        let ops = parse_cigar("50=1I40=2D30=500N20=").unwrap();
+        // =/X/I lengths: 50 + 40 + 30 + 20, plus the 1bp insertion.
+        assert_eq!(cigar_transcript_span(&ops), 50 + 1 + 40 + 30 + 20);
+        // =/X/D/N lengths: the insertion contributes nothing to the genome.
+        assert_eq!(cigar_genomic_span(&ops), 50 + 40 + 2 + 30 + 500 + 20);
+    }
+
+    #[test]
+    fn test_project_exon_position_plus_strand_matches_cigar_op_at() {
+        let hit = project_exon_position("50=1I40=2D30=", 1000, 1, 90)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hit, ExonProjection::Aligned(1000 + 89));
+
+        let gap = project_exon_position("50=1I40=2D30=", 1000, 1, 50)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            gap,
+            ExonProjection::Gap {
+                flank: 1000 + 50,
+                offset: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_project_exon_position_minus_strand_mirrors_offsets_from_the_far_end() {
+        // A 10bp ungapped exon on the minus strand: transcript position 0
+        // (the 5' end of the transcript) aligns to the genomic end of the
+        // exon's span, not its start.
+        let first = project_exon_position("10=", 1000, -1, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first, ExonProjection::Aligned(1009));
+
+        let last = project_exon_position("10=", 1000, -1, 9)
+            .unwrap()
+            .unwrap();
+        assert_eq!(last, ExonProjection::Aligned(1000));
+    }
+
+    #[test]
+    fn test_project_exon_position_past_exon_returns_none() {
+        assert!(project_exon_position("10=", 1000, 1, 10)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,61 @@
+//! VarFish-compatible TSV serialization for [`annotate::AnnotationRecord`]s.
+//!
+//! VarFish's variant-import format is a plain tab-separated table with one
+//! row per annotated allele. This writer covers the columns this crate can
+//! actually populate (coordinates, gene, transcript, `c.`/`p.`); it does not
+//! attempt to reproduce VarFish's full genotype/quality column set.
+
+use crate::annotate::AnnotationRecord;
+use std::io::{self, Write};
+
+const HEADER: &[&str] = &[
+    "chromosome",
+    "pos",
+    "reference",
+    "alternative",
+    "gene",
+    "transcript",
+    "hgvs_c",
+    "hgvs_p",
+    "consequence",
+];
+
+/// Writes the VarFish-style TSV header row.
+pub fn write_header<W: Write>(sink: &mut W) -> io::Result<()> {
+    writeln!(sink, "{}", HEADER.join("\t"))
+}
+
+/// Writes one TSV row per `AnnotationRecord`, degrading empty when a variant
+/// is non-coding or protein projection failed.
+pub fn write_rows<'a, W, I>(sink: &mut W, records: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a AnnotationRecord>,
+{
+    for rec in records {
+        writeln!(
+            sink,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            rec.reference_ac,
+            rec.pos,
+            rec.reference_bases,
+            rec.alt_bases,
+            rec.gene.as_deref().unwrap_or(""),
+            rec.transcript_ac,
+            rec.hgvs_c.as_deref().unwrap_or(""),
+            rec.hgvs_p.as_deref().unwrap_or(""),
+            rec.consequence,
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes a full VarFish-style TSV (header + one row per record).
+pub fn write_tsv<'a, W, I>(sink: &mut W, records: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a AnnotationRecord>,
+{
+    write_header(sink)?;
+    write_rows(sink, records)
+}
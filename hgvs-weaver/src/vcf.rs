@@ -0,0 +1,300 @@
+//! Real VCF/BCF ingestion via `noodles`, projecting records straight onto
+//! transcripts and writing a flattened, gene-keyed annotation table.
+//!
+//! [`crate::annotate`] covers the "already-split CHROM/POS/REF/ALT" half of
+//! this workflow against `crate::varfish`'s narrower TSV; this module reads
+//! an actual `noodles_vcf` file end to end (bgzipped transparently, since
+//! `noodles_vcf::io::reader::Builder` sniffs the BGZF magic) and, for each
+//! ALT allele, fans out through [`crate::mapper::VariantMapper::g_to_consequences_all`]
+//! to get every overlapping transcript's `c.`/`p.` projection in one pass --
+//! the same call [`crate::mapper::VariantMapper::g_to_consequences_all`]'s
+//! own doc comment describes. BCF isn't wired up yet: swap the
+//! `vcf::io::reader::Builder` below for `noodles_bcf`'s equivalent to add it.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use noodles_vcf as vcf;
+
+use crate::annotate::{is_skippable_alt, vcf_to_genomic_variant};
+use crate::data::{DataProvider, IdentifierKind, TranscriptSearch};
+use crate::error::HgvsError;
+use crate::mapper::VariantMapper;
+use crate::structs::{GVariant, SequenceVariant, Variant};
+
+/// One transcript's flattened annotation for a single ALT allele: the
+/// genomic, coding and (if the transcript is coding) protein HGVS strings,
+/// plus the gene symbol and protein accession used to render the latter.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct VcfAnnotationRow {
+    pub hgvs_g: String,
+    pub hgvs_c: String,
+    pub hgvs_p: Option<String>,
+    pub gene: Option<String>,
+    pub protein_ac: Option<String>,
+}
+
+const HEADER: &[&str] = &["hgvs_g", "hgvs_c", "hgvs_p", "gene", "protein_ac"];
+
+/// Writes [`HEADER`] as a tab-separated row.
+pub fn write_header<W: Write>(sink: &mut W) -> io::Result<()> {
+    writeln!(sink, "{}", HEADER.join("\t"))
+}
+
+/// Writes one TSV row per [`VcfAnnotationRow`].
+pub fn write_rows<'a, W, I>(sink: &mut W, rows: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a VcfAnnotationRow>,
+{
+    for row in rows {
+        writeln!(
+            sink,
+            "{}\t{}\t{}\t{}\t{}",
+            row.hgvs_g,
+            row.hgvs_c,
+            row.hgvs_p.as_deref().unwrap_or(""),
+            row.gene.as_deref().unwrap_or(""),
+            row.protein_ac.as_deref().unwrap_or(""),
+        )?;
+    }
+    Ok(())
+}
+
+/// Projects one already-built `g.` variant onto every transcript `searcher`
+/// reports overlapping its locus, rendering a [`VcfAnnotationRow`] per
+/// transcript. A transcript [`VariantMapper::g_to_c`] fails on is silently
+/// skipped, same as [`VariantMapper::g_to_consequences_all`] itself.
+pub fn annotate_genomic_variant(
+    mapper: &VariantMapper,
+    hdp: &dyn DataProvider,
+    searcher: &dyn TranscriptSearch,
+    var_g: &GVariant,
+) -> Result<Vec<VcfAnnotationRow>, HgvsError> {
+    let hgvs_g = SequenceVariant::Genomic(var_g.clone()).to_string();
+    let mut rows = Vec::new();
+
+    for consequence in mapper.g_to_consequences_all(var_g, searcher)? {
+        let gene = consequence.c_variant.gene().map(str::to_string);
+        let hgvs_c = SequenceVariant::Coding(consequence.c_variant.clone()).to_string();
+        let (hgvs_p, protein_ac) = match consequence.p_variant {
+            Some(p_variant) => {
+                let protein_ac = Some(p_variant.ac().to_string());
+                (
+                    Some(SequenceVariant::Protein(p_variant).to_string()),
+                    protein_ac,
+                )
+            }
+            None => {
+                let protein_ac = hdp
+                    .get_symbol_accessions(
+                        consequence.c_variant.ac(),
+                        IdentifierKind::Transcript,
+                        IdentifierKind::Protein,
+                    )
+                    .ok()
+                    .and_then(|accs| accs.into_iter().next().map(|(_, ac)| ac));
+                (None, protein_ac)
+            }
+        };
+
+        rows.push(VcfAnnotationRow {
+            hgvs_g: hgvs_g.clone(),
+            hgvs_c,
+            hgvs_p,
+            gene,
+            protein_ac,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Opens a VCF (optionally BGZF-compressed) at `path`, and streams a
+/// [`VcfAnnotationRow`] TSV -- header plus one row per ALT allele × overlapping
+/// transcript -- to `sink`. Each record is projected and written before the
+/// next is read, so annotating a whole-genome VCF never holds more than one
+/// record's rows in memory.
+///
+/// Multi-sample VCFs are read for their sites alone; genotype columns aren't
+/// consulted. No-call (`.`) and symbolic/breakend ALTs are skipped, matching
+/// [`crate::annotate::split_alts`].
+pub fn annotate_vcf_to_tsv<W: Write>(
+    path: impl AsRef<Path>,
+    hdp: &dyn DataProvider,
+    searcher: &dyn TranscriptSearch,
+    sink: &mut W,
+) -> Result<usize, HgvsError> {
+    let mut reader = vcf::io::reader::Builder::default()
+        .build_from_path(path)
+        .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+    let header = reader
+        .read_header()
+        .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+
+    let mapper = VariantMapper::new(hdp);
+    write_header(sink).map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+
+    let mut count = 0;
+    for result in reader.records() {
+        let record = result.map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        let reference_ac = record.reference_sequence_name(&header).to_string();
+        let pos = record
+            .variant_start()
+            .ok_or_else(|| HgvsError::ValidationError("VCF record missing POS".into()))?
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+        let reference_bases = record.reference_bases().to_string();
+
+        for alt in record
+            .alternate_bases()
+            .iter()
+            .collect::<io::Result<Vec<_>>>()
+            .map_err(|e| HgvsError::DataProviderError(e.to_string()))?
+        {
+            if is_skippable_alt(alt) {
+                continue;
+            }
+            let var_g =
+                vcf_to_genomic_variant(&reference_ac, usize::from(pos) as i32, &reference_bases, alt)?;
+            let rows = annotate_genomic_variant(&mapper, hdp, searcher, &var_g)?;
+            write_rows(sink, &rows).map_err(|e| HgvsError::DataProviderError(e.to_string()))?;
+            count += rows.len();
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::{GenomicPos, IntronicOffset, TranscriptPos};
+    use crate::data::{ExonData, IdentifierType, Transcript, TranscriptData};
+
+    struct MockDataProvider;
+
+    impl DataProvider for MockDataProvider {
+        fn get_seq(
+            &self,
+            _ac: &str,
+            start: i32,
+            end: i32,
+            _kind: IdentifierType,
+        ) -> Result<String, HgvsError> {
+            let mut s = String::new();
+            s.push_str("AAAAAAAAAA"); // 10 A's
+            s.push_str("ATG"); // n.11 is c.1
+            for _ in 0..25 {
+                s.push_str("ATGC");
+            }
+            let s_idx = start.max(0) as usize;
+            let e_idx = if end == -1 { s.len() } else { end as usize };
+            Ok(s[s_idx..e_idx.min(s.len())].to_string())
+        }
+
+        fn get_transcript(
+            &self,
+            ac: &str,
+            _reference_ac: Option<&str>,
+        ) -> Result<Box<dyn Transcript>, HgvsError> {
+            if ac == "NM_0001.3" {
+                Ok(Box::new(TranscriptData {
+                    ac: "NM_0001.3".to_string(),
+                    gene: "MOCK".to_string(),
+                    cds_start_index: Some(TranscriptPos(10)),
+                    cds_end_index: Some(TranscriptPos(50)),
+                    strand: 1,
+                    reference_accession: "NC_0001.10".to_string(),
+                    exons: vec![ExonData {
+                        transcript_start: TranscriptPos(0),
+                        transcript_end: TranscriptPos(100),
+                        reference_start: GenomicPos(1000),
+                        reference_end: GenomicPos(1100),
+                        alt_strand: 1,
+                        cigar: "100M".to_string(),
+                    }],
+                }))
+            } else {
+                Err(HgvsError::ValidationError("Transcript not found".into()))
+            }
+        }
+
+        fn get_symbol_accessions(
+            &self,
+            symbol: &str,
+            _sk: IdentifierKind,
+            tk: IdentifierKind,
+        ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+            if tk == IdentifierKind::Protein && symbol == "NM_0001.3" {
+                return Ok(vec![(
+                    IdentifierType::ProteinAccession,
+                    "NP_0001.1".to_string(),
+                )]);
+            }
+            Ok(vec![])
+        }
+
+        fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+            Ok(IdentifierType::Unknown)
+        }
+
+        fn c_to_g(
+            &self,
+            _transcript_ac: &str,
+            pos: TranscriptPos,
+            offset: IntronicOffset,
+        ) -> Result<(String, GenomicPos), HgvsError> {
+            Ok(("NC_0001.10".to_string(), GenomicPos(pos.0 + offset.0)))
+        }
+    }
+
+    struct MockSearch;
+    impl TranscriptSearch for MockSearch {
+        fn get_transcripts_for_region(
+            &self,
+            _ac: &str,
+            _s: i32,
+            _e: i32,
+        ) -> Result<Vec<String>, HgvsError> {
+            Ok(vec!["NM_0001.3".to_string()])
+        }
+    }
+
+    #[test]
+    fn test_annotate_genomic_variant_renders_g_c_and_p_hgvs() {
+        let hdp = MockDataProvider;
+        let searcher = MockSearch;
+        let mapper = VariantMapper::new(&hdp);
+
+        // g.1011 (n.11, c.1) A>T: the mock transcript's start codon.
+        let var_g = vcf_to_genomic_variant("NC_0001.10", 1011, "A", "T").unwrap();
+        let rows = annotate_genomic_variant(&mapper, &hdp, &searcher, &var_g).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+        assert_eq!(row.hgvs_g, "NC_0001.10:g.1011A>T");
+        assert_eq!(row.hgvs_c, "NM_0001.3:c.1A>T");
+        assert_eq!(row.hgvs_p.as_deref(), Some("NP_0001.1:p.(Met1Leu)"));
+        assert_eq!(row.gene.as_deref(), Some("MOCK"));
+        assert_eq!(row.protein_ac.as_deref(), Some("NP_0001.1"));
+    }
+
+    #[test]
+    fn test_write_rows_degrades_missing_protein_to_empty_columns() {
+        let rows = vec![VcfAnnotationRow {
+            hgvs_g: "NC_0001.10:g.5C>T".to_string(),
+            hgvs_c: "NR_0001.1:n.5C>T".to_string(),
+            hgvs_p: None,
+            gene: None,
+            protein_ac: None,
+        }];
+
+        let mut buf = Vec::new();
+        write_header(&mut buf).unwrap();
+        write_rows(&mut buf, &rows).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "hgvs_g\thgvs_c\thgvs_p\tgene\tprotein_ac");
+        assert_eq!(lines[1], "NC_0001.10:g.5C>T\tNR_0001.1:n.5C>T\t\t\t");
+    }
+}
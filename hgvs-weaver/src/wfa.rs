@@ -0,0 +1,447 @@
+//! Gap-affine wavefront alignment (WFA), for reading a minimal edit script
+//! off a pair of sequences instead of hand-rolling prefix/suffix trimming.
+//!
+//! [`crate::altseq_to_hgvsp::AltSeqToHgvsp`] used to find the common prefix
+//! and suffix of the reference and alternate protein by walking in from both
+//! ends -- fine for a plain substitution, but a multi-residue `delins` whose
+//! aligned length differs from the reference can make that greedy walk stop
+//! short of (or past) the true minimal diff. [`align`] replaces that walk
+//! with the WFA recurrence: three score-indexed wavefronts (match/substitution,
+//! insertion, deletion), each diagonal `k = query_offset - ref_offset`
+//! storing the furthest-reaching antidiagonal offset reachable at score `s`,
+//! extended greedily along runs of matches and grown score-by-score until the
+//! bottom-right corner is reached. Backtracking the chosen wavefronts yields
+//! the edit script in [`Op`].
+//!
+//! This is the textbook WFA recurrence (Marco-Sola et al., 2021) specialized
+//! to the small (tens of residues) windows a protein-level diff needs --
+//! nothing here assumes DNA or amino acid alphabets, it only compares `char`s
+//! for equality.
+
+/// One step of an alignment between a reference and a query sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Reference and query agree at this position.
+    Match,
+    /// Reference and query both have a residue here, but they differ.
+    Mismatch,
+    /// A query residue with no counterpart in the reference (an insertion).
+    Ins,
+    /// A reference residue with no counterpart in the query (a deletion).
+    Del,
+}
+
+/// A reference/query alignment as a run-length-collapsed edit script, in
+/// reference-then-query order (i.e. the order `Op::Del` and `Op::Ins`
+/// consume their respective sequence).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    pub ops: Vec<(Op, usize)>,
+}
+
+impl Alignment {
+    /// Expands the run-length-collapsed script back into one [`Op`] per
+    /// aligned column, for callers that want to walk position by position.
+    pub fn flatten(&self) -> Vec<Op> {
+        self.ops
+            .iter()
+            .flat_map(|(op, len)| std::iter::repeat(*op).take(*len))
+            .collect()
+    }
+}
+
+/// Gap-affine scoring penalties for [`align`]. All fields are non-negative;
+/// higher values penalize the corresponding edit more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Penalties {
+    pub mismatch: i32,
+    pub gap_open: i32,
+    pub gap_extend: i32,
+}
+
+impl Default for Penalties {
+    /// The standard WFA paper defaults (mismatch 4, gap-open 6, gap-extend 2)
+    /// -- the relative ratios are what matters
+    /// for picking a minimal edit script over these short windows, not their
+    /// absolute magnitude.
+    fn default() -> Self {
+        Penalties {
+            mismatch: 4,
+            gap_open: 6,
+            gap_extend: 2,
+        }
+    }
+}
+
+/// One diagonal's furthest-reaching offset at the current score, or
+/// [`UNREACHED`] if this diagonal hasn't been reached yet at this score.
+const UNREACHED: i32 = i32::MIN;
+
+#[derive(Debug, Clone)]
+struct Wavefronts {
+    m: std::collections::HashMap<i32, i32>,
+    i: std::collections::HashMap<i32, i32>,
+    d: std::collections::HashMap<i32, i32>,
+}
+
+impl Wavefronts {
+    fn new() -> Self {
+        Wavefronts {
+            m: std::collections::HashMap::new(),
+            i: std::collections::HashMap::new(),
+            d: std::collections::HashMap::new(),
+        }
+    }
+
+    fn get(map: &std::collections::HashMap<i32, i32>, k: i32) -> i32 {
+        *map.get(&k).unwrap_or(&UNREACHED)
+    }
+}
+
+/// Aligns `reference` against `query` under gap-affine scoring, returning the
+/// minimal edit script.
+///
+/// Runs the WFA score-increment loop: at score 0, the match wavefront starts
+/// on diagonal 0 and is "extended" greedily along equal characters; at each
+/// subsequent score, the match wavefront is recomputed from the mismatch
+/// wavefront one score back (diagonal held, offset advanced by one) and the
+/// insertion/deletion wavefronts (diagonal shifted by one, offset advanced),
+/// which are themselves seeded from the match wavefront `gap_open +
+/// gap_extend` back (opening a new gap) or grown from their own wavefront
+/// `gap_extend` back (extending an existing one); each new match wavefront is
+/// then extended again along matches. The loop stops the first score at
+/// which diagonal `k = query.len() - reference.len()` reaches offset
+/// `reference.len()` (equivalently `query.len()` on that diagonal), which is
+/// the bottom-right corner of the edit matrix and therefore optimal, since
+/// WFA explores scores in non-decreasing order.
+pub fn align(reference: &[char], query: &[char], penalties: Penalties) -> Alignment {
+    let n = reference.len() as i32;
+    let m = query.len() as i32;
+    let target_k = m - n;
+
+    let extend = |mut off: i32, k: i32| -> i32 {
+        loop {
+            let ref_pos = off;
+            let query_pos = off + k;
+            if ref_pos < n
+                && query_pos < m
+                && reference[ref_pos as usize] == query[query_pos as usize]
+            {
+                off += 1;
+            } else {
+                break;
+            }
+        }
+        off
+    };
+
+    let mut history: Vec<Wavefronts> = Vec::new();
+    let mut wf = Wavefronts::new();
+    wf.m.insert(0, extend(0, 0));
+    history.push(wf.clone());
+
+    let mut score = 0;
+    loop {
+        if Wavefronts::get(&history[score as usize].m, target_k) >= n {
+            break;
+        }
+        score += 1;
+
+        let mut next = Wavefronts::new();
+        let mut diagonals = std::collections::HashSet::new();
+        for prior in [
+            score - penalties.mismatch,
+            score - penalties.gap_open - penalties.gap_extend,
+            score - penalties.gap_extend,
+        ] {
+            if prior >= 0 {
+                let w = &history[prior as usize];
+                diagonals.extend(w.m.keys().copied());
+                diagonals.extend(w.i.keys().copied());
+                diagonals.extend(w.d.keys().copied());
+            }
+        }
+
+        for &k in &diagonals {
+            // Insertion: extend an open gap on this diagonal, or open a new
+            // one from the match wavefront on the diagonal to the left.
+            let mut i_off = UNREACHED;
+            if score - penalties.gap_extend >= 0 {
+                let prev = &history[(score - penalties.gap_extend) as usize];
+                if let Some(&o) = prev.i.get(&(k - 1)) {
+                    i_off = i_off.max(o + 1);
+                }
+            }
+            if score - penalties.gap_open - penalties.gap_extend >= 0 {
+                let prev = &history[(score - penalties.gap_open - penalties.gap_extend) as usize];
+                if let Some(&o) = prev.m.get(&(k - 1)) {
+                    i_off = i_off.max(o + 1);
+                }
+            }
+            if i_off != UNREACHED {
+                next.i.insert(k, i_off);
+            }
+
+            // Deletion: extend an open gap on this diagonal, or open a new
+            // one from the match wavefront on the diagonal to the right.
+            let mut d_off = UNREACHED;
+            if score - penalties.gap_extend >= 0 {
+                let prev = &history[(score - penalties.gap_extend) as usize];
+                if let Some(&o) = prev.d.get(&(k + 1)) {
+                    d_off = d_off.max(o);
+                }
+            }
+            if score - penalties.gap_open - penalties.gap_extend >= 0 {
+                let prev = &history[(score - penalties.gap_open - penalties.gap_extend) as usize];
+                if let Some(&o) = prev.m.get(&(k + 1)) {
+                    d_off = d_off.max(o);
+                }
+            }
+            if d_off != UNREACHED {
+                next.d.insert(k, d_off);
+            }
+
+            // Match/mismatch: advance one from the mismatch wavefront, or
+            // inherit whichever of the just-computed indel wavefronts is
+            // valid on this diagonal (a gap closes back into an alignment).
+            let mut m_off = UNREACHED;
+            if score - penalties.mismatch >= 0 {
+                let prev = &history[(score - penalties.mismatch) as usize];
+                if let Some(&o) = prev.m.get(&k) {
+                    m_off = m_off.max(o + 1);
+                }
+            }
+            if let Some(&o) = next.i.get(&k) {
+                m_off = m_off.max(o);
+            }
+            if let Some(&o) = next.d.get(&k) {
+                m_off = m_off.max(o);
+            }
+            if m_off != UNREACHED {
+                next.m.insert(k, extend(m_off, k));
+            }
+        }
+
+        history.push(next);
+
+        // A window this small (protein diff spans, not whole-chromosome
+        // alignment) always resolves in a handful of score increments; this
+        // backstop only guards against a malformed call (e.g. mismatched
+        // alphabets guaranteeing no match ever extends) turning into an
+        // unbounded loop.
+        if score > 4 * (n + m).max(1) {
+            break;
+        }
+    }
+
+    backtrack(&history, reference, query, penalties, target_k)
+}
+
+fn backtrack(
+    history: &[Wavefronts],
+    reference: &[char],
+    query: &[char],
+    penalties: Penalties,
+    target_k: i32,
+) -> Alignment {
+    let n = reference.len() as i32;
+    let mut score = (history.len() - 1) as i32;
+    let mut k = target_k;
+    let mut state = 'M';
+    let mut steps: Vec<Op> = Vec::new();
+
+    loop {
+        let off = match state {
+            'M' => Wavefronts::get(&history[score as usize].m, k),
+            'I' => Wavefronts::get(&history[score as usize].i, k),
+            _ => Wavefronts::get(&history[score as usize].d, k),
+        };
+
+        if state == 'M' {
+            // Unwind the greedy extension: every position where this
+            // diagonal's offset could have been reached by matching
+            // characters is a `Match`, walking back until we hit the
+            // non-extended seed offset for this score/diagonal.
+            let seed = m_seed_offset(history, score, k, penalties);
+            let mut cur = off;
+            while cur > seed {
+                steps.push(Op::Match);
+                cur -= 1;
+            }
+            if score == 0 && seed == 0 && cur == 0 {
+                break;
+            }
+            // The seed step itself: either a mismatch (from M one score
+            // back) or a gap closing (inherited from I/D at this score).
+            if let Some(&o) = history[score as usize].i.get(&k) {
+                if o == seed {
+                    state = 'I';
+                    continue;
+                }
+            }
+            if let Some(&o) = history[score as usize].d.get(&k) {
+                if o == seed {
+                    state = 'D';
+                    continue;
+                }
+            }
+            steps.push(Op::Mismatch);
+            score -= penalties.mismatch;
+            if score < 0 {
+                break;
+            }
+        } else if state == 'I' {
+            steps.push(Op::Ins);
+            let opened = score - penalties.gap_open - penalties.gap_extend >= 0
+                && Wavefronts::get(&history[(score - penalties.gap_open - penalties.gap_extend) as usize].m, k - 1)
+                    == off - 1;
+            k -= 1;
+            if opened {
+                score -= penalties.gap_open + penalties.gap_extend;
+                state = 'M';
+            } else {
+                score -= penalties.gap_extend;
+            }
+        } else {
+            steps.push(Op::Del);
+            let opened = score - penalties.gap_open - penalties.gap_extend >= 0
+                && Wavefronts::get(&history[(score - penalties.gap_open - penalties.gap_extend) as usize].m, k + 1)
+                    == off;
+            k += 1;
+            if opened {
+                score -= penalties.gap_open + penalties.gap_extend;
+                state = 'M';
+            } else {
+                score -= penalties.gap_extend;
+            }
+        }
+
+        if score < 0 {
+            break;
+        }
+        if state == 'M' && score == 0 && k == 0 && Wavefronts::get(&history[0].m, 0) == 0 && n == 0 {
+            break;
+        }
+    }
+
+    steps.reverse();
+    collapse(steps)
+}
+
+/// The match wavefront's pre-extension ("seed") offset at `(score, k)`: the
+/// best of a mismatch one score back, or a gap (insertion/deletion) closing
+/// at this score -- whichever produced the value actually stored, before
+/// [`align`]'s greedy match-extension ran.
+fn m_seed_offset(
+    history: &[Wavefronts],
+    score: i32,
+    k: i32,
+    penalties: Penalties,
+) -> i32 {
+    let mut seed = UNREACHED;
+    if score - penalties.mismatch >= 0 {
+        if let Some(&o) = history[(score - penalties.mismatch) as usize].m.get(&k) {
+            seed = seed.max(o + 1);
+        }
+    }
+    if let Some(&o) = history[score as usize].i.get(&k) {
+        seed = seed.max(o);
+    }
+    if let Some(&o) = history[score as usize].d.get(&k) {
+        seed = seed.max(o);
+    }
+    if seed == UNREACHED {
+        0
+    } else {
+        seed
+    }
+}
+
+fn collapse(ops: Vec<Op>) -> Alignment {
+    let mut collapsed: Vec<(Op, usize)> = Vec::new();
+    for op in ops {
+        match collapsed.last_mut() {
+            Some((last_op, len)) if *last_op == op => *len += 1,
+            _ => collapsed.push((op, 1)),
+        }
+    }
+    Alignment { ops: collapsed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn test_align_identical_sequences_is_all_match() {
+        let a = chars("MQQQ");
+        let aln = align(&a, &a, Penalties::default());
+        assert_eq!(aln.ops, vec![(Op::Match, 4)]);
+    }
+
+    #[test]
+    fn test_align_single_substitution() {
+        let reference = chars("MPRS");
+        let query = chars("MIRS");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(
+            aln.ops,
+            vec![(Op::Match, 1), (Op::Mismatch, 1), (Op::Match, 2)]
+        );
+    }
+
+    #[test]
+    fn test_align_single_deletion() {
+        let reference = chars("MQQQTAG");
+        let query = chars("MQQTAG");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(
+            aln.ops,
+            vec![(Op::Match, 2), (Op::Del, 1), (Op::Match, 4)]
+        );
+    }
+
+    #[test]
+    fn test_align_single_insertion() {
+        let reference = chars("MQQTAG");
+        let query = chars("MQQQTAG");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(
+            aln.ops,
+            vec![(Op::Match, 2), (Op::Ins, 1), (Op::Match, 4)]
+        );
+    }
+
+    #[test]
+    fn test_align_delins_of_two_residues() {
+        let reference = chars("MAARS");
+        let query = chars("MKKRS");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(
+            aln.ops,
+            vec![(Op::Match, 1), (Op::Mismatch, 2), (Op::Match, 2)]
+        );
+    }
+
+    #[test]
+    fn test_align_flatten_expands_runs() {
+        let reference = chars("AB");
+        let query = chars("AC");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(aln.flatten(), vec![Op::Match, Op::Mismatch]);
+    }
+
+    #[test]
+    fn test_align_trailing_stop_gained_reads_as_mismatch_not_frameshift() {
+        // A stop-gain substitution near the end of a window must not be
+        // mistaken for an insertion/deletion -- it's a single mismatch.
+        let reference = chars("QQR");
+        let query = chars("QQ*");
+        let aln = align(&reference, &query, Penalties::default());
+        assert_eq!(aln.ops, vec![(Op::Match, 2), (Op::Mismatch, 1)]);
+    }
+}
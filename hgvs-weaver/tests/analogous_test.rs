@@ -1,6 +1,6 @@
 use hgvs_weaver::analogous_edit::{
     apply_aa_edit_to_sparse, apply_na_edit_to_sparse, project_aa_variant, reconcile_projections,
-    ResidueToken, SparseReference,
+    ProjectedSequence, ResidueToken, SparseReference,
 };
 use hgvs_weaver::structs::{AaEdit, NaEdit};
 
@@ -728,3 +728,68 @@ fn test_multi_unit_repeat_equivalence() -> Result<(), hgvs_weaver::error::HgvsEr
 
     Ok(())
 }
+
+#[test]
+fn test_apply_na_edit_to_sparse_stated_length_insertion_projects_any_tokens() {
+    let sref = SparseReference::new();
+    let edit = NaEdit::Ins {
+        alt: Some("3".to_string()),
+        uncertain: false,
+    };
+
+    let projected = apply_na_edit_to_sparse(&edit, 10, 10, &sref);
+    assert_eq!(
+        projected.0,
+        vec![ResidueToken::Any, ResidueToken::Any, ResidueToken::Any]
+    );
+}
+
+#[test]
+fn test_apply_na_edit_to_sparse_unresolved_insertion_projects_a_wildcard() {
+    let sref = SparseReference::new();
+    let edit = NaEdit::Ins {
+        alt: None,
+        uncertain: false,
+    };
+
+    let projected = apply_na_edit_to_sparse(&edit, 10, 10, &sref);
+    assert_eq!(projected.0, vec![ResidueToken::Wildcard]);
+}
+
+#[test]
+fn test_is_equivalent_to_accepts_a_length_mismatch_via_a_single_gap() {
+    // Same length would make this a plain `is_analogous_to` case; the
+    // extra `Unknown` on the right is only reconciled by aligning around
+    // it, not by zipping position-by-position.
+    let v1 = ProjectedSequence(vec![ResidueToken::Unknown(1), ResidueToken::Unknown(2)]);
+    let v2 = ProjectedSequence(vec![
+        ResidueToken::Unknown(3),
+        ResidueToken::Unknown(3),
+        ResidueToken::Unknown(4),
+    ]);
+
+    let alignment = v1.is_equivalent_to(&v2);
+    assert!(alignment.equivalent);
+    assert!(alignment.gap.is_some());
+}
+
+#[test]
+fn test_is_equivalent_to_is_unconfirmed_when_no_known_residue_ever_lines_up() {
+    // Every aligned pair compares `Unknown` to `Unknown` -- compatible, but
+    // neither side ever names a concrete residue the other can be checked
+    // against, so this is weaker evidence than an alignment where a `Known`
+    // residue on each side actually agrees.
+    let v1 = ProjectedSequence(vec![ResidueToken::Unknown(1), ResidueToken::Unknown(2)]);
+    let v2 = ProjectedSequence(vec![
+        ResidueToken::Unknown(3),
+        ResidueToken::Unknown(3),
+        ResidueToken::Unknown(4),
+    ]);
+    assert!(!v1.is_equivalent_to(&v2).confirmed);
+
+    // Swapping one `Unknown` for a `Known` that the other side also pins
+    // down at the same aligned position makes it a confirmed match.
+    let v3 = ProjectedSequence(vec![ResidueToken::Known("A".into()), ResidueToken::Unknown(2)]);
+    let v4 = ProjectedSequence(vec![ResidueToken::Known("A".into()), ResidueToken::Unknown(4)]);
+    assert!(v3.is_equivalent_to(&v4).confirmed);
+}
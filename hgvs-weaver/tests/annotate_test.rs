@@ -0,0 +1,233 @@
+use hgvs_weaver::annotate::{
+    annotate_record, classify_consequence, is_skippable_alt, split_alts, symbolic_sv_kind,
+    vcf_symbolic_to_genomic_variant, vcf_to_genomic_variant, SymbolicSvKind, VariantAnnotator,
+    VcfRecord,
+};
+use hgvs_weaver::data::{
+    DataProvider, ExonData, IdentifierKind, IdentifierType, Transcript, TranscriptData,
+    TranscriptSearch,
+};
+use hgvs_weaver::error::HgvsError;
+use hgvs_weaver::structs::{GenomicPos, IntronicOffset, NaEdit, TranscriptPos};
+
+#[test]
+fn test_split_alts_skips_symbolic_and_no_call() {
+    assert_eq!(split_alts("A,C,T"), vec!["A", "C", "T"]);
+    assert_eq!(split_alts("A,<DEL>,*,."), vec!["A"]);
+    assert_eq!(split_alts("A,]chr1:123]T"), vec!["A"]);
+}
+
+#[test]
+fn test_is_skippable_alt() {
+    assert!(is_skippable_alt("."));
+    assert!(is_skippable_alt("*"));
+    assert!(is_skippable_alt("<INS>"));
+    assert!(!is_skippable_alt("A"));
+    assert!(!is_skippable_alt("ACGT"));
+}
+
+#[test]
+fn test_vcf_to_genomic_variant_snv() {
+    let var = vcf_to_genomic_variant("NC_000001.11", 100, "A", "G").unwrap();
+    assert_eq!(var.ac, "NC_000001.11");
+    let pos = var.posedit.pos.unwrap();
+    assert_eq!(pos.start.base.0, 100);
+    assert!(pos.end.is_none());
+}
+
+#[test]
+fn test_vcf_to_genomic_variant_indel_spans_range() {
+    let var = vcf_to_genomic_variant("NC_000001.11", 100, "ACG", "A").unwrap();
+    let pos = var.posedit.pos.unwrap();
+    assert_eq!(pos.start.base.0, 100);
+    assert_eq!(pos.end.unwrap().base.0, 102);
+}
+
+#[test]
+fn test_symbolic_sv_kind() {
+    assert_eq!(symbolic_sv_kind("<DEL>"), Some(SymbolicSvKind::Del));
+    assert_eq!(symbolic_sv_kind("<DUP:TANDEM>"), Some(SymbolicSvKind::Dup));
+    assert_eq!(symbolic_sv_kind("<INS>"), Some(SymbolicSvKind::Ins));
+    assert_eq!(symbolic_sv_kind("]chr1:123]T"), None);
+    assert_eq!(symbolic_sv_kind("A"), None);
+}
+
+#[test]
+fn test_vcf_symbolic_to_genomic_variant_del_has_no_explicit_ref() {
+    let var = vcf_symbolic_to_genomic_variant("NC_000001.11", 100, 200, SymbolicSvKind::Del);
+    assert!(var.posedit.uncertain);
+    assert_eq!(var.posedit.pos.unwrap().end.unwrap().base.0, 200);
+    assert!(matches!(var.posedit.edit, NaEdit::Del { ref_: None, .. }));
+}
+
+#[test]
+fn test_vcf_symbolic_to_genomic_variant_ins_has_no_interval_end() {
+    let var = vcf_symbolic_to_genomic_variant("NC_000001.11", 100, 100, SymbolicSvKind::Ins);
+    assert!(var.posedit.pos.unwrap().end.is_none());
+    assert!(matches!(var.posedit.edit, NaEdit::Ins { alt: None, .. }));
+}
+
+#[test]
+fn test_classify_consequence() {
+    assert_eq!(classify_consequence(None, Some("boom")), "unknown");
+    assert_eq!(classify_consequence(None, None), "non_coding_transcript");
+    assert_eq!(
+        classify_consequence(Some("NP_0001.1:p.Met1?"), None),
+        "unknown"
+    );
+    assert_eq!(
+        classify_consequence(Some("NP_0001.1:p.Gly12="), None),
+        "synonymous"
+    );
+    assert_eq!(
+        classify_consequence(Some("NP_0001.1:p.Gly12ValfsTer12"), None),
+        "frameshift"
+    );
+    assert_eq!(
+        classify_consequence(Some("NP_0001.1:p.Gln5Ter"), None),
+        "nonsense"
+    );
+    assert_eq!(
+        classify_consequence(Some("NP_0001.1:p.(Met1Leu)"), None),
+        "missense"
+    );
+}
+
+struct MockDataProvider;
+impl DataProvider for MockDataProvider {
+    fn get_seq(
+        &self,
+        _ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let mut s = String::new();
+        s.push_str("AAAAAAAAAA"); // 10 A's
+        s.push_str("ATG"); // n.11 is c.1
+        for _ in 0..25 {
+            s.push_str("ATGC");
+        }
+
+        let start = start as usize;
+        let end = if end == -1 { s.len() } else { end as usize };
+        if start > s.len() {
+            return Ok("".into());
+        }
+        let end = end.min(s.len());
+        Ok(s[start..end].to_string())
+    }
+
+    fn get_transcript(
+        &self,
+        transcript_ac: &str,
+        _reference_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        if transcript_ac == "NM_0001.3" {
+            let exons = vec![ExonData {
+                transcript_start: TranscriptPos(0),
+                transcript_end: TranscriptPos(100),
+                reference_start: GenomicPos(1000),
+                reference_end: GenomicPos(1100),
+                alt_strand: 1,
+                cigar: "100M".to_string(),
+            }];
+            return Ok(Box::new(TranscriptData {
+                ac: "NM_0001.3".to_string(),
+                gene: "MOCK".to_string(),
+                cds_start_index: Some(TranscriptPos(10)), // n.11 is c.1
+                cds_end_index: Some(TranscriptPos(50)),
+                strand: 1,
+                reference_accession: "NC_0001.10".to_string(),
+                exons,
+            }));
+        }
+        Err(HgvsError::ValidationError("Transcript not found".into()))
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        _sk: IdentifierKind,
+        tk: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        if tk == IdentifierKind::Protein && symbol == "NM_0001.3" {
+            return Ok(vec![(IdentifierType::ProteinAccession, "NP_0001.1".to_string())]);
+        }
+        Ok(vec![])
+    }
+
+    fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+        Ok(IdentifierType::Unknown)
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: TranscriptPos,
+        offset: IntronicOffset,
+    ) -> Result<(String, GenomicPos), HgvsError> {
+        let tx = self.get_transcript(transcript_ac, None)?;
+        Ok((tx.reference_accession().to_string(), GenomicPos(pos.0 + offset.0)))
+    }
+}
+
+struct MockSearch;
+impl TranscriptSearch for MockSearch {
+    fn get_transcripts_for_region(
+        &self,
+        _ac: &str,
+        _start: i32,
+        _end: i32,
+    ) -> Result<Vec<String>, HgvsError> {
+        Ok(vec!["NM_0001.3".to_string()])
+    }
+}
+
+#[test]
+fn test_annotate_record_reports_missense_consequence() -> Result<(), HgvsError> {
+    let hdp = MockDataProvider;
+    let search = MockSearch;
+
+    // g.1011 (index 1010) is n.11, i.e. c.1 -- the transcript's start codon.
+    let records = annotate_record(&hdp, &search, "NC_0001.10", 1011, "A", "T")?;
+
+    assert_eq!(records.len(), 1);
+    let rec = &records[0];
+    assert_eq!(rec.transcript_ac, "NM_0001.3");
+    assert_eq!(rec.gene.as_deref(), Some("MOCK"));
+    assert_eq!(rec.hgvs_c.as_deref(), Some("NM_0001.3:c.1A>T"));
+    assert_eq!(rec.hgvs_p.as_deref(), Some("NP_0001.1:p.(Met1Leu)"));
+    assert_eq!(rec.consequence, "missense");
+    assert!(rec.error.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_variant_annotator_streams_across_multiple_records() -> Result<(), HgvsError> {
+    let hdp = MockDataProvider;
+    let search = MockSearch;
+
+    let records = vec![
+        VcfRecord {
+            reference_ac: "NC_0001.10".to_string(),
+            pos: 1011,
+            reference_bases: "A".to_string(),
+            alt_field: "T".to_string(),
+        },
+        VcfRecord {
+            reference_ac: "NC_0001.10".to_string(),
+            pos: 1011,
+            reference_bases: "A".to_string(),
+            alt_field: "<DEL>".to_string(), // skipped: not a representable allele
+        },
+    ];
+
+    let annotator = VariantAnnotator::new(&hdp, &search, records.into_iter());
+    let annotations: Result<Vec<_>, HgvsError> = annotator.collect();
+    let annotations = annotations?;
+
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].consequence, "missense");
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use hgvs_weaver::genetic_code::GeneticCodeTable;
+
+#[test]
+fn test_mitochondrial_table_selected_for_rcrs() {
+    assert_eq!(
+        GeneticCodeTable::for_reference_accession("NC_012920.1"),
+        GeneticCodeTable::VertebrateMitochondrial
+    );
+    assert_eq!(
+        GeneticCodeTable::for_reference_accession("NC_012920"),
+        GeneticCodeTable::VertebrateMitochondrial
+    );
+}
+
+#[test]
+fn test_standard_table_for_nuclear_contig() {
+    assert_eq!(
+        GeneticCodeTable::for_reference_accession("NC_000001.11"),
+        GeneticCodeTable::Standard
+    );
+}
+
+#[test]
+fn test_mitochondrial_recoding() {
+    let t = GeneticCodeTable::VertebrateMitochondrial;
+    assert_eq!(t.translate_codon(['A', 'G', 'A']), '*');
+    assert_eq!(t.translate_codon(['A', 'G', 'G']), '*');
+    assert_eq!(t.translate_codon(['A', 'T', 'A']), 'M');
+    assert_eq!(t.translate_codon(['T', 'G', 'A']), 'W');
+
+    let standard = GeneticCodeTable::Standard;
+    assert_eq!(standard.translate_codon(['A', 'G', 'A']), 'R');
+    assert_eq!(standard.translate_codon(['T', 'G', 'A']), '*');
+}
+
+#[test]
+fn test_mitochondrial_extra_start_codons() {
+    let t = GeneticCodeTable::VertebrateMitochondrial;
+    assert!(t.is_start_codon(['A', 'T', 'T']));
+    assert!(t.is_start_codon(['A', 'T', 'A']));
+    assert!(!GeneticCodeTable::Standard.is_start_codon(['A', 'T', 'T']));
+}
@@ -0,0 +1,64 @@
+use hgvs_weaver::structs::SequenceVariant;
+use hgvs_weaver::utils::{render_protein, AaNotation};
+
+fn as_protein(var: SequenceVariant) -> hgvs_weaver::structs::PVariant {
+    match var {
+        SequenceVariant::Protein(vp) => vp,
+        other => panic!("expected a protein variant, got {other}"),
+    }
+}
+
+#[test]
+fn test_render_protein_three_letter_to_one_letter() -> Result<(), hgvs_weaver::error::HgvsError> {
+    let vp = as_protein(hgvs_weaver::parse_hgvs_variant("NP_000042.3:p.Val600Glu")?);
+
+    assert_eq!(
+        render_protein(&vp, AaNotation::OneLetter),
+        "NP_000042.3:p.V600E"
+    );
+    // Round-trips back to the original 3-letter form.
+    assert_eq!(
+        render_protein(&vp, AaNotation::ThreeLetter),
+        "NP_000042.3:p.Val600Glu"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_render_protein_preserves_uncertainty_parens() -> Result<(), hgvs_weaver::error::HgvsError> {
+    let vp = as_protein(hgvs_weaver::parse_hgvs_variant("NP_000042.3:p.(Val600Glu)")?);
+
+    // The conversion must not discard the `(...)` prediction marker, unlike
+    // `VariantEquivalence::normalize_format`'s lossy comparison key.
+    let rendered = render_protein(&vp, AaNotation::OneLetter);
+    assert!(rendered.contains('('));
+    assert!(rendered.contains(')'));
+    assert!(rendered.contains("V600E"));
+    Ok(())
+}
+
+#[test]
+fn test_render_protein_handles_nonsense_ter() -> Result<(), hgvs_weaver::error::HgvsError> {
+    let vp = as_protein(hgvs_weaver::parse_hgvs_variant("NP_0001.1:p.Trp2Ter")?);
+
+    assert_eq!(render_protein(&vp, AaNotation::OneLetter), "NP_0001.1:p.W2*");
+    assert_eq!(
+        render_protein(&vp, AaNotation::ThreeLetter),
+        "NP_0001.1:p.Trp2Ter"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_render_protein_does_not_rewrite_accession() -> Result<(), hgvs_weaver::error::HgvsError> {
+    // The accession itself contains no residue-shaped substrings here, but
+    // the point of operating on the parsed edit (not the rendered string)
+    // is that `render_protein` never even looks at `ac` when rewriting --
+    // only `posedit` is touched.
+    let vp = as_protein(hgvs_weaver::parse_hgvs_variant("NP_000042.3:p.Cys15Ter")?);
+
+    let rendered = render_protein(&vp, AaNotation::ThreeLetter);
+    assert!(rendered.starts_with("NP_000042.3:p."));
+    assert!(rendered.contains("Cys15Ter"));
+    Ok(())
+}
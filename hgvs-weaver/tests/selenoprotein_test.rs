@@ -0,0 +1,155 @@
+use hgvs_weaver::coords::{GenomicPos, IntronicOffset, TranscriptPos};
+use hgvs_weaver::data::{
+    DataProvider, ExonData, IdentifierKind, IdentifierType, Transcript, TranscriptData,
+};
+use hgvs_weaver::error::HgvsError;
+use hgvs_weaver::genetic_code::SelenocysteineSites;
+use hgvs_weaver::mapper::VariantMapper;
+use hgvs_weaver::SequenceVariant;
+
+/// Serves a fixed CDS so tests can place an in-frame `TGA` at a known codon.
+struct FixedSeqProvider(&'static str);
+impl DataProvider for FixedSeqProvider {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        _ref_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        let len = self.0.len() as i32;
+        Ok(Box::new(TranscriptData {
+            ac: ac.to_string(),
+            gene: "TEST".to_string(),
+            cds_start_index: Some(TranscriptPos(0)),
+            cds_end_index: Some(TranscriptPos(len)),
+            strand: 1,
+            reference_accession: "NC_TEST.1".to_string(),
+            exons: vec![ExonData {
+                transcript_start: TranscriptPos(0),
+                transcript_end: TranscriptPos(len),
+                reference_start: GenomicPos(0),
+                reference_end: GenomicPos(len),
+                alt_strand: 1,
+                cigar: format!("{len}M"),
+            }],
+        }))
+    }
+    fn get_seq(
+        &self,
+        _ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let seq = self.0;
+        let s = start.max(0) as usize;
+        let e = (end.max(0) as usize).min(seq.len());
+        if s >= e {
+            return Ok(String::new());
+        }
+        Ok(seq[s..e].to_string())
+    }
+    fn get_symbol_accessions(
+        &self,
+        _s: &str,
+        _f: IdentifierKind,
+        _t: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        Ok(vec![])
+    }
+    fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+        Ok(IdentifierType::GenomicAccession)
+    }
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: TranscriptPos,
+        offset: IntronicOffset,
+    ) -> Result<(String, GenomicPos), HgvsError> {
+        let tx = self.get_transcript(transcript_ac, None)?;
+        Ok((
+            tx.reference_accession().to_string(),
+            GenomicPos(pos.0 + offset.0),
+        ))
+    }
+}
+
+// CDS: ATG(Met) AAA(Lys) TGA(Sec, recoded) CCC(Pro) TAA(stop)
+const SELENOPROTEIN_CDS: &str = "ATGAAATGACCCTAA";
+
+#[test]
+fn test_downstream_missense_translates_through_recoded_stop_in_ref_and_alt() -> Result<(), HgvsError> {
+    let hdp = FixedSeqProvider(SELENOPROTEIN_CDS);
+    let mapper = VariantMapper::new(&hdp);
+    let sites = SelenocysteineSites::new([2]); // third codon (0-based) is the recoding site
+
+    // c.11C>G: middle base of the Pro codon (c.10-12, "CCC") -> "CGC" (Arg).
+    // This variant doesn't touch the recoded codon, but the only way to
+    // reach it at all is for both the reference and altered CDS to read
+    // through the recoded "TGA" as Sec instead of stopping there.
+    let var_c = hgvs_weaver::parse_hgvs_variant("NM_TEST.1:c.11C>G")?;
+    let SequenceVariant::Coding(v) = var_c else {
+        panic!("expected a coding variant");
+    };
+    let var_p = mapper.c_to_p_full(
+        &v,
+        Some("NP_TEST.1"),
+        None,
+        hgvs_weaver::altseq_to_hgvsp::ProteinNormalizationMode::Simplified,
+        &sites,
+        hgvs_weaver::altseq::RefMismatchPolicy::Strict,
+    )?;
+    assert_eq!(var_p.to_string(), "NP_TEST.1:p.(Pro4Arg)");
+    Ok(())
+}
+
+#[test]
+fn test_variant_destroying_the_recoded_codon_reads_as_a_plain_substitution() -> Result<(), HgvsError> {
+    let hdp = FixedSeqProvider(SELENOPROTEIN_CDS);
+    let mapper = VariantMapper::new(&hdp);
+    let sites = SelenocysteineSites::new([2]);
+
+    // c.9A>C: last base of the recoded "TGA" codon -> "TGC" (Cys). The
+    // codon is no longer TGA, so even though this position is an annotated
+    // recoding site, the variant destroys it -- the result is a plain
+    // Sec->Cys substitution, not a stop and not a second Sec.
+    let var_c = hgvs_weaver::parse_hgvs_variant("NM_TEST.1:c.9A>C")?;
+    let SequenceVariant::Coding(v) = var_c else {
+        panic!("expected a coding variant");
+    };
+    let var_p = mapper.c_to_p_full(
+        &v,
+        Some("NP_TEST.1"),
+        None,
+        hgvs_weaver::altseq_to_hgvsp::ProteinNormalizationMode::Simplified,
+        &sites,
+        hgvs_weaver::altseq::RefMismatchPolicy::Strict,
+    )?;
+    assert_eq!(var_p.to_string(), "NP_TEST.1:p.(Sec3Cys)");
+    Ok(())
+}
+
+#[test]
+fn test_variant_introducing_an_unannotated_tga_still_calls_a_stop() -> Result<(), HgvsError> {
+    // CDS: ATG(Met) AAA(Lys) TGA(Sec, recoded) TCA(Ser) TAA(stop)
+    let hdp = FixedSeqProvider("ATGAAATGATCATAA");
+    let mapper = VariantMapper::new(&hdp);
+    let sites = SelenocysteineSites::new([2]); // only the third codon is annotated
+
+    // c.11C>G: middle base of the Ser codon (c.10-12, "TCA") -> "TGA". This
+    // creates a brand new in-frame TGA at an unannotated position, so it
+    // must still terminate translation rather than being read as Sec.
+    let var_c = hgvs_weaver::parse_hgvs_variant("NM_TEST.1:c.11C>G")?;
+    let SequenceVariant::Coding(v) = var_c else {
+        panic!("expected a coding variant");
+    };
+    let var_p = mapper.c_to_p_full(
+        &v,
+        Some("NP_TEST.1"),
+        None,
+        hgvs_weaver::altseq_to_hgvsp::ProteinNormalizationMode::Simplified,
+        &sites,
+        hgvs_weaver::altseq::RefMismatchPolicy::Strict,
+    )?;
+    assert_eq!(var_p.to_string(), "NP_TEST.1:p.(Ser4Ter)");
+    Ok(())
+}
@@ -95,4 +95,24 @@ fn test_ins_3_prime_shifting() -> Result<(), HgvsError> {
     Ok(())
 }
 
+#[test]
+fn test_ins_into_homopolymer_normalizes_to_dup() -> Result<(), HgvsError> {
+    let hdp = HomopolymerProvider;
+    let mapper = VariantMapper::new(&hdp);
+
+    // Inserting another 'A' into a run of 'A's is indistinguishable from
+    // duplicating the preceding base, so HGVS prefers c./g. dup notation.
+    let v = hgvs_weaver::parse_hgvs_variant("NC_TEST.1:g.1005_1006insA")?;
+    let SequenceVariant::Genomic(v_g) = v else {
+        panic!()
+    };
+    let normalized = mapper.normalize_variant(SequenceVariant::Genomic(v_g))?;
+    assert!(
+        normalized.to_string().contains("dup"),
+        "expected dup notation, got {}",
+        normalized
+    );
+    Ok(())
+}
+
 use hgvs_weaver::SequenceVariant;
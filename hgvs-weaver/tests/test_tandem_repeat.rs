@@ -0,0 +1,192 @@
+use hgvs_weaver::coords::{GenomicPos, IntronicOffset, TranscriptPos};
+use hgvs_weaver::data::{
+    DataProvider, ExonData, IdentifierKind, IdentifierType, Transcript, TranscriptData,
+};
+use hgvs_weaver::error::HgvsError;
+use hgvs_weaver::mapper::VariantMapper;
+use hgvs_weaver::structs::NaEdit;
+use hgvs_weaver::SequenceVariant;
+
+/// Serves a fixed reference sequence so tests can control exactly how many
+/// upstream tandem copies of a repeat unit are present.
+struct FixedSeqProvider(&'static str);
+impl DataProvider for FixedSeqProvider {
+    fn get_transcript(
+        &self,
+        ac: &str,
+        _ref_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        Ok(Box::new(TranscriptData {
+            ac: ac.to_string(),
+            gene: "TEST".to_string(),
+            cds_start_index: Some(TranscriptPos(0)),
+            cds_end_index: Some(TranscriptPos(100)),
+            strand: 1,
+            reference_accession: "NC_TEST.1".to_string(),
+            exons: vec![ExonData {
+                transcript_start: TranscriptPos(0),
+                transcript_end: TranscriptPos(100),
+                reference_start: GenomicPos(0),
+                reference_end: GenomicPos(100),
+                alt_strand: 1,
+                cigar: "100M".to_string(),
+            }],
+        }))
+    }
+    fn get_seq(
+        &self,
+        _ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let seq = self.0;
+        let s = start.max(0) as usize;
+        let e = (end.max(0) as usize).min(seq.len());
+        if s >= e {
+            return Ok(String::new());
+        }
+        Ok(seq[s..e].to_string())
+    }
+    fn get_symbol_accessions(
+        &self,
+        _s: &str,
+        _f: IdentifierKind,
+        _t: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        Ok(vec![])
+    }
+    fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+        Ok(IdentifierType::GenomicAccession)
+    }
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: TranscriptPos,
+        offset: IntronicOffset,
+    ) -> Result<(String, GenomicPos), HgvsError> {
+        let tx = self.get_transcript(transcript_ac, None)?;
+        Ok((
+            tx.reference_accession().to_string(),
+            GenomicPos(pos.0 + offset.0),
+        ))
+    }
+}
+
+#[test]
+fn test_ins_with_two_existing_copies_normalizes_to_repeat() -> Result<(), HgvsError> {
+    // "NNNNNNNNNN" (0..10) + "CAG" (10..13) + "CAG" (13..16), then insert
+    // another "CAG" right after base 16 (0-based index 15).
+    let hdp = FixedSeqProvider("NNNNNNNNNNCAGCAG");
+    let mapper = VariantMapper::new(&hdp);
+
+    let v = hgvs_weaver::parse_hgvs_variant("NC_TEST.1:g.16_17insCAG")?;
+    let SequenceVariant::Genomic(v_g) = v else {
+        panic!()
+    };
+    let normalized = mapper.normalize_variant(SequenceVariant::Genomic(v_g))?;
+    let SequenceVariant::Genomic(ng) = normalized else {
+        panic!()
+    };
+    assert!(
+        matches!(
+            ng.posedit.edit,
+            NaEdit::Repeat {
+                ref_: Some(ref unit),
+                min: 3,
+                max: 3,
+                ..
+            } if unit == "CAG"
+        ),
+        "expected a 3-copy CAG repeat, got {:?}",
+        ng.posedit.edit
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ins_with_single_existing_copy_still_normalizes_to_dup() -> Result<(), HgvsError> {
+    // Only one upstream copy of "CAG" (preceded by non-matching filler), so
+    // this remains plain `dup` notation rather than a repeat.
+    let hdp = FixedSeqProvider("NNNNNNNNNNCAG");
+    let mapper = VariantMapper::new(&hdp);
+
+    let v = hgvs_weaver::parse_hgvs_variant("NC_TEST.1:g.13_14insCAG")?;
+    let SequenceVariant::Genomic(v_g) = v else {
+        panic!()
+    };
+    let normalized = mapper.normalize_variant(SequenceVariant::Genomic(v_g))?;
+    let SequenceVariant::Genomic(ng) = normalized else {
+        panic!()
+    };
+    assert!(
+        matches!(ng.posedit.edit, NaEdit::Dup { ref_: Some(ref s), .. } if s == "CAG"),
+        "expected plain dup notation, got {:?}",
+        ng.posedit.edit
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ins_reduces_to_smallest_repeating_unit() -> Result<(), HgvsError> {
+    // The inserted sequence "ATAT" is itself two copies of the primitive
+    // unit "AT", and two more copies of "AT" already precede it, so this
+    // should normalize to a 4-copy "AT" repeat rather than a dup of "ATAT".
+    let hdp = FixedSeqProvider("NNNNNNNNNNATAT");
+    let mapper = VariantMapper::new(&hdp);
+
+    let v = hgvs_weaver::parse_hgvs_variant("NC_TEST.1:g.14_15insATAT")?;
+    let SequenceVariant::Genomic(v_g) = v else {
+        panic!()
+    };
+    let normalized = mapper.normalize_variant(SequenceVariant::Genomic(v_g))?;
+    let SequenceVariant::Genomic(ng) = normalized else {
+        panic!()
+    };
+    assert!(
+        matches!(
+            ng.posedit.edit,
+            NaEdit::Repeat {
+                ref_: Some(ref unit),
+                min: 4,
+                max: 4,
+                ..
+            } if unit == "AT"
+        ),
+        "expected a 4-copy AT repeat, got {:?}",
+        ng.posedit.edit
+    );
+    Ok(())
+}
+
+#[test]
+fn test_repeat_scan_stops_at_5_prime_end() -> Result<(), HgvsError> {
+    // The reference begins with two tandem "CAG" copies at the very start
+    // of the accession (index 0), so the backward scan must stop cleanly
+    // at the 5' boundary instead of reading a negative index.
+    let hdp = FixedSeqProvider("CAGCAG");
+    let mapper = VariantMapper::new(&hdp);
+
+    let v = hgvs_weaver::parse_hgvs_variant("NC_TEST.1:g.6_7insCAG")?;
+    let SequenceVariant::Genomic(v_g) = v else {
+        panic!()
+    };
+    let normalized = mapper.normalize_variant(SequenceVariant::Genomic(v_g))?;
+    let SequenceVariant::Genomic(ng) = normalized else {
+        panic!()
+    };
+    assert!(
+        matches!(
+            ng.posedit.edit,
+            NaEdit::Repeat {
+                ref_: Some(ref unit),
+                min: 3,
+                max: 3,
+                ..
+            } if unit == "CAG"
+        ),
+        "expected a 3-copy CAG repeat, got {:?}",
+        ng.posedit.edit
+    );
+    Ok(())
+}
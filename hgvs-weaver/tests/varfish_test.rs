@@ -0,0 +1,178 @@
+use hgvs_weaver::annotate::{annotate_record, AnnotationRecord};
+use hgvs_weaver::data::{
+    DataProvider, ExonData, IdentifierKind, IdentifierType, Transcript, TranscriptData,
+    TranscriptSearch,
+};
+use hgvs_weaver::error::HgvsError;
+use hgvs_weaver::structs::{GenomicPos, IntronicOffset, TranscriptPos};
+use hgvs_weaver::varfish::write_tsv;
+
+#[test]
+fn test_write_tsv_degrades_missing_protein() {
+    let records = vec![
+        AnnotationRecord {
+            reference_ac: "NC_000001.11".to_string(),
+            pos: 100,
+            reference_bases: "A".to_string(),
+            alt_bases: "G".to_string(),
+            transcript_ac: "NM_000051.3".to_string(),
+            gene: Some("ATM".to_string()),
+            strand: Some(1),
+            hgvs_c: Some("NM_000051.3:c.1A>G".to_string()),
+            hgvs_p: Some("NP_000042.3:p.Met1?".to_string()),
+            consequence: "unknown".to_string(),
+            error: None,
+        },
+        AnnotationRecord {
+            reference_ac: "NC_000001.11".to_string(),
+            pos: 200,
+            reference_bases: "C".to_string(),
+            alt_bases: "T".to_string(),
+            transcript_ac: "NR_000001.1".to_string(),
+            gene: None,
+            strand: None,
+            hgvs_c: Some("NR_000001.1:n.10C>T".to_string()),
+            hgvs_p: None,
+            consequence: "non_coding_transcript".to_string(),
+            error: None,
+        },
+    ];
+
+    let mut buf = Vec::new();
+    write_tsv(&mut buf, &records).unwrap();
+    let out = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[0],
+        "chromosome\tpos\treference\talternative\tgene\ttranscript\thgvs_c\thgvs_p\tconsequence"
+    );
+    assert_eq!(
+        lines[1],
+        "NC_000001.11\t100\tA\tG\tATM\tNM_000051.3\tNM_000051.3:c.1A>G\tNP_000042.3:p.Met1?\tunknown"
+    );
+    assert_eq!(
+        lines[2],
+        "NC_000001.11\t200\tC\tT\t\tNR_000001.1\tNR_000001.1:n.10C>T\t\tnon_coding_transcript"
+    );
+}
+
+struct MockDataProvider;
+impl DataProvider for MockDataProvider {
+    fn get_seq(
+        &self,
+        _ac: &str,
+        start: i32,
+        end: i32,
+        _kind: IdentifierType,
+    ) -> Result<String, HgvsError> {
+        let mut s = String::new();
+        s.push_str("AAAAAAAAAA"); // 10 A's
+        s.push_str("ATG"); // n.11 is c.1
+        for _ in 0..25 {
+            s.push_str("ATGC");
+        }
+
+        let start = start as usize;
+        let end = if end == -1 { s.len() } else { end as usize };
+        if start > s.len() {
+            return Ok("".into());
+        }
+        let end = end.min(s.len());
+        Ok(s[start..end].to_string())
+    }
+
+    fn get_transcript(
+        &self,
+        transcript_ac: &str,
+        _reference_ac: Option<&str>,
+    ) -> Result<Box<dyn Transcript>, HgvsError> {
+        if transcript_ac == "NM_0001.3" {
+            let exons = vec![ExonData {
+                transcript_start: TranscriptPos(0),
+                transcript_end: TranscriptPos(100),
+                reference_start: GenomicPos(1000),
+                reference_end: GenomicPos(1100),
+                alt_strand: 1,
+                cigar: "100M".to_string(),
+            }];
+            return Ok(Box::new(TranscriptData {
+                ac: "NM_0001.3".to_string(),
+                gene: "MOCK".to_string(),
+                cds_start_index: Some(TranscriptPos(10)), // n.11 is c.1
+                cds_end_index: Some(TranscriptPos(50)),
+                strand: 1,
+                reference_accession: "NC_0001.10".to_string(),
+                exons,
+            }));
+        }
+        Err(HgvsError::ValidationError("Transcript not found".into()))
+    }
+
+    fn get_symbol_accessions(
+        &self,
+        symbol: &str,
+        _sk: IdentifierKind,
+        tk: IdentifierKind,
+    ) -> Result<Vec<(IdentifierType, String)>, HgvsError> {
+        if tk == IdentifierKind::Protein && symbol == "NM_0001.3" {
+            return Ok(vec![(IdentifierType::ProteinAccession, "NP_0001.1".to_string())]);
+        }
+        Ok(vec![])
+    }
+
+    fn get_identifier_type(&self, _id: &str) -> Result<IdentifierType, HgvsError> {
+        Ok(IdentifierType::Unknown)
+    }
+
+    fn c_to_g(
+        &self,
+        transcript_ac: &str,
+        pos: TranscriptPos,
+        offset: IntronicOffset,
+    ) -> Result<(String, GenomicPos), HgvsError> {
+        let tx = self.get_transcript(transcript_ac, None)?;
+        Ok((tx.reference_accession().to_string(), GenomicPos(pos.0 + offset.0)))
+    }
+}
+
+struct MockSearch;
+impl TranscriptSearch for MockSearch {
+    fn get_transcripts_for_region(
+        &self,
+        _ac: &str,
+        _start: i32,
+        _end: i32,
+    ) -> Result<Vec<String>, HgvsError> {
+        Ok(vec!["NM_0001.3".to_string()])
+    }
+}
+
+/// Mirrors `annotate_test.rs`'s missense fixture: g.1011 (n.11, c.1) A>T on
+/// the mock transcript's start codon projects to `p.(Met1Leu)`. Kept as its
+/// own regression case here so the TSV round-trip test below has a known
+/// non-trivial `p.` value to check against.
+fn run_regression_test() -> AnnotationRecord {
+    let hdp = MockDataProvider;
+    let search = MockSearch;
+    let records = annotate_record(&hdp, &search, "NC_0001.10", 1011, "A", "T").unwrap();
+    assert_eq!(records.len(), 1);
+    records.into_iter().next().unwrap()
+}
+
+#[test]
+fn test_write_tsv_round_trips_regression_record_through_parsing() {
+    let rec = run_regression_test();
+    assert_eq!(rec.hgvs_p.as_deref(), Some("NP_0001.1:p.(Met1Leu)"));
+
+    let mut buf = Vec::new();
+    write_tsv(&mut buf, &[rec.clone()]).unwrap();
+    let out = String::from_utf8(buf).unwrap();
+    let mut lines = out.lines();
+
+    let header: Vec<&str> = lines.next().unwrap().split('\t').collect();
+    let row: Vec<&str> = lines.next().unwrap().split('\t').collect();
+    assert_eq!(header.len(), row.len());
+
+    let hgvs_p_col = header.iter().position(|&h| h == "hgvs_p").unwrap();
+    assert_eq!(row[hgvs_p_col], rec.hgvs_p.unwrap());
+}
@@ -98,8 +98,13 @@ impl PyIdentifierType {
 pub enum PyEquivalenceLevel {
     Identity,
     Analogous,
+    /// Consistent under alignment, but no position actually confirmed
+    /// agreement between the two descriptions.
+    Weak,
     Different,
     Unknown,
+    /// Same cis-allele component set, different ordering/representation.
+    AlleleReordered,
 }
 
 impl From<::hgvs_weaver::equivalence::EquivalenceLevel> for PyEquivalenceLevel {
@@ -107,8 +112,12 @@ impl From<::hgvs_weaver::equivalence::EquivalenceLevel> for PyEquivalenceLevel {
         match el {
             ::hgvs_weaver::equivalence::EquivalenceLevel::Identity => Self::Identity,
             ::hgvs_weaver::equivalence::EquivalenceLevel::Analogous => Self::Analogous,
+            ::hgvs_weaver::equivalence::EquivalenceLevel::Weak => Self::Weak,
             ::hgvs_weaver::equivalence::EquivalenceLevel::Different => Self::Different,
             ::hgvs_weaver::equivalence::EquivalenceLevel::Unknown => Self::Unknown,
+            ::hgvs_weaver::equivalence::EquivalenceLevel::AlleleReordered => {
+                Self::AlleleReordered
+            }
         }
     }
 }
@@ -129,6 +138,55 @@ impl PyEquivalenceLevel {
     }
 }
 
+#[gen_stub_pyclass_enum]
+#[pyclass(name = "ValidationLevel", module = "weaver._weaver")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationLevel {
+    Valid,
+    Warning,
+    Error,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl ValidationLevel {
+    fn __repr__(&self) -> String {
+        format!("ValidationLevel.{:?}", self)
+    }
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+    fn __hash__(&self) -> u64 {
+        let mut s = std::collections::hash_map::DefaultHasher::new();
+        std::hash::Hash::hash(self, &mut s);
+        std::hash::Hasher::finish(&s)
+    }
+}
+
+#[gen_stub_pyclass_enum]
+#[pyclass(name = "ShuffleDirection", module = "weaver._weaver")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShuffleDirection {
+    ThreePrime,
+    FivePrime,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl ShuffleDirection {
+    fn __repr__(&self) -> String {
+        format!("ShuffleDirection.{:?}", self)
+    }
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+    fn __hash__(&self) -> u64 {
+        let mut s = std::collections::hash_map::DefaultHasher::new();
+        std::hash::Hash::hash(self, &mut s);
+        std::hash::Hasher::finish(&s)
+    }
+}
+
 #[gen_stub_pyclass]
 #[pyclass(name = "Variant", module = "weaver._weaver")]
 #[doc = "Represents a parsed HGVS variant.\n\nProvides access to the variant's accession, gene symbol, and coordinate type.\nVariants can be formatted back to HGVS strings or converted to JSON/dict representations."]
@@ -185,20 +243,27 @@ impl PyVariant {
         format!("<weaver.Variant {}>", self.format())
     }
 
-    #[doc = "Validates the variant's reference sequence against the provided DataProvider.\n\nReturns True if the reference sequence matches, False otherwise.\nMay raise ValueError if coordinates are out of bounds."]
-    fn validate(&self, _py: Python, provider: Py<PyAny>) -> PyResult<bool> {
+    #[pyo3(signature = (provider, strict = false))]
+    #[doc = "Validates the variant against the provided DataProvider.\n\nRuns a reference-match check, a transcript-bounds check (for c. variants,\nafter mapping onto transcript-relative coordinates) and a CDS-bounds check\n(flagging UTR or intronic positions), collecting a diagnostic for each issue\nfound instead of stopping at the first one.\n\nArgs:\n    provider: The DataProvider to validate against.\n    strict: If True, the intronic-offset and outside-CDS warnings are raised as a\n        ValueError instead of being collected, and an out-of-bounds position is\n        reported as an ERROR diagnostic instead of a WARNING.\n\nReturns:\n    A list of (ValidationLevel, message) diagnostics. Empty if the variant is fully valid."]
+    fn validate(
+        &self,
+        _py: Python,
+        provider: Py<PyAny>,
+        strict: bool,
+    ) -> PyResult<Vec<(ValidationLevel, String)>> {
         let bridge = PyDataProviderBridge { provider };
+        let mut diagnostics = Vec::new();
         let result = match &self.inner {
-            SequenceVariant::Genomic(v) => self.validate_genomic(v, &bridge),
-            SequenceVariant::Coding(v) => self.validate_coding(v, &bridge),
+            SequenceVariant::Genomic(v) => self.validate_genomic(v, &bridge, &mut diagnostics),
+            SequenceVariant::Coding(v) => {
+                self.validate_coding(v, &bridge, strict, &mut diagnostics)
+            }
             _ => Err(HgvsError::UnsupportedOperation(
                 "Validation not implemented for this variant type".into(),
             )),
         };
-        match result {
-            Ok(is_valid) => Ok(is_valid),
-            Err(e) => Err(map_hgvs_error(e)),
-        }
+        result.map_err(map_hgvs_error)?;
+        Ok(diagnostics)
     }
 
     #[doc = "Converts the variant to an SPDI string representation."]
@@ -213,7 +278,8 @@ impl PyVariant {
         &self,
         v: &::hgvs_weaver::GVariant,
         bridge: &PyDataProviderBridge,
-    ) -> Result<bool, HgvsError> {
+        diagnostics: &mut Vec<(ValidationLevel, String)>,
+    ) -> Result<(), HgvsError> {
         let pos = v
             .posedit
             .pos
@@ -232,22 +298,27 @@ impl PyVariant {
             IdentifierKind::Genomic.into_identifier_type(),
         )?;
 
-        match &v.posedit.edit {
-            ::hgvs_weaver::edits::NaEdit::RefAlt { ref_: Some(r), .. } => {
-                if r.is_empty() || r.chars().all(|c| c.is_ascii_digit()) {
-                    return Ok(true);
-                }
-                Ok(r == &ref_seq)
+        if let ::hgvs_weaver::edits::NaEdit::RefAlt { ref_: Some(r), .. } = &v.posedit.edit {
+            if !(r.is_empty() || r.chars().all(|c| c.is_ascii_digit())) && r != &ref_seq {
+                diagnostics.push((
+                    ValidationLevel::Error,
+                    format!(
+                        "reference mismatch: expected '{}', found '{}'",
+                        r, ref_seq
+                    ),
+                ));
             }
-            _ => Ok(true),
         }
+        Ok(())
     }
 
     fn validate_coding(
         &self,
         v: &::hgvs_weaver::CVariant,
         bridge: &PyDataProviderBridge,
-    ) -> Result<bool, HgvsError> {
+        strict: bool,
+        diagnostics: &mut Vec<(ValidationLevel, String)>,
+    ) -> Result<(), HgvsError> {
         let transcript = bridge.get_transcript(&v.ac, None)?;
 
         let pos = v
@@ -264,7 +335,13 @@ impl PyVariant {
         )?;
 
         if pos.start.offset.is_some() || pos.end.as_ref().and_then(|e| e.offset).is_some() {
-            return Ok(true);
+            let msg = "position has an intronic offset; reference sequence was not checked"
+                .to_string();
+            if strict {
+                return Err(HgvsError::ValidationError(msg));
+            }
+            diagnostics.push((ValidationLevel::Warning, msg));
+            return Ok(());
         }
 
         let tm = ::hgvs_weaver::transcript_mapper::TranscriptMapper::new(transcript)?;
@@ -278,21 +355,51 @@ impl PyVariant {
         let start_idx = n_start.0 as usize;
         let end_idx = (n_end.0 + 1) as usize;
         if start_idx >= ref_seq.len() || end_idx > ref_seq.len() {
-            return Err(HgvsError::ValidationError(
-                "Transcript sequence too short".into(),
+            let level = if strict {
+                ValidationLevel::Error
+            } else {
+                ValidationLevel::Warning
+            };
+            diagnostics.push((
+                level,
+                format!(
+                    "position {}..{} is outside the transcript sequence bounds (len={})",
+                    start_idx,
+                    end_idx,
+                    ref_seq.len()
+                ),
             ));
+            return Ok(());
         }
-        let sub_seq = &ref_seq[start_idx..end_idx];
 
-        match &v.posedit.edit {
-            ::hgvs_weaver::edits::NaEdit::RefAlt { ref_: Some(r), .. } => {
-                if r.is_empty() || r.chars().all(|c| c.is_ascii_digit()) {
-                    return Ok(true);
+        if let (Some(cds_start), Some(cds_end)) = (
+            tm.transcript.cds_start_index(),
+            tm.transcript.cds_end_index(),
+        ) {
+            let (cds_start, cds_end) = (cds_start.0 as usize, cds_end.0 as usize);
+            if start_idx < cds_start || end_idx > cds_end {
+                let msg = format!(
+                    "position {}..{} falls outside the annotated CDS ({}..{}); likely UTR",
+                    start_idx, end_idx, cds_start, cds_end
+                );
+                if strict {
+                    return Err(HgvsError::ValidationError(msg));
                 }
-                Ok(r == sub_seq)
+                diagnostics.push((ValidationLevel::Warning, msg));
             }
-            _ => Ok(true),
         }
+
+        let sub_seq = &ref_seq[start_idx..end_idx];
+
+        if let ::hgvs_weaver::edits::NaEdit::RefAlt { ref_: Some(r), .. } = &v.posedit.edit {
+            if !(r.is_empty() || r.chars().all(|c| c.is_ascii_digit())) && r != sub_seq {
+                diagnostics.push((
+                    ValidationLevel::Error,
+                    format!("reference mismatch: expected '{}', found '{}'", r, sub_seq),
+                ));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -306,6 +413,24 @@ fn parse(input: &str) -> PyResult<PyVariant> {
     }
 }
 
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[doc = "Parses a cis-allele HGVS description ('ac:c.[edit1;edit2]') into its ordered list of component Variants.\n\nA plain single-edit description (no brackets) parses to a one-element list, so callers can treat every description uniformly as an allele. There is no single composite Variant type for a multi-edit allele; use equivalent_level_allele on the returned list to compare two allele descriptions as unordered sets.\n\nArgs:\n    input: The HGVS allele string to parse, e.g. 'NM_000123.4:c.[76A>C;83G>T]'.\n\nReturns:\n    A list of Variant objects, one per component edit, in input order.\n\nRaises:\n    ValueError: If the allele string is malformed."]
+fn parse_allele(input: &str) -> PyResult<Vec<PyVariant>> {
+    ::hgvs_weaver::parser::parse_allele(input)
+        .map(|vars| vars.into_iter().map(|inner| PyVariant { inner }).collect())
+        .map_err(map_hgvs_error)
+}
+
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[doc = "Parses a SPDI string (sequence:position:deletion:insertion) into a Variant.\n\nResolves whether the sequence accession is genomic or transcript via the DataProvider, producing a 'g.' or 'n.' Variant respectively.\n\nArgs:\n    spdi: The SPDI string, e.g. 'NC_000001.11:12344:A:G'.\n    provider: The DataProvider to resolve the accession's identifier type against.\n\nReturns:\n    A Variant object.\n\nRaises:\n    ValueError: If the SPDI string is malformed."]
+fn parse_spdi(spdi: &str, provider: Py<PyAny>) -> PyResult<PyVariant> {
+    let bridge = PyDataProviderBridge { provider };
+    let inner = ::hgvs_weaver::structs::spdi_to_variant(spdi, &bridge).map_err(map_hgvs_error)?;
+    Ok(PyVariant { inner })
+}
+
 // --- Mapper and DataProvider Bridge ---
 
 pub struct PyDataProviderBridge {
@@ -553,6 +678,35 @@ impl PyVariantMapper {
         }
     }
 
+    #[pyo3(signature = (var_g, searcher))]
+    #[doc = "Like g_to_c_all, but maps every overlapping transcript independently instead of silently dropping ones that fail to map.\n\nArgs:\n    var_g: The genomic Variant to map.\n    searcher: An object implementing the TranscriptSearch protocol.\n\nReturns:\n    A tuple of (successfully mapped 'c.' Variant list, list of (transcript_ac, error message) failures), the latter sorted by accession then message."]
+    fn g_to_c_all_with_errors(
+        &self,
+        _py: Python,
+        var_g: &PyVariant,
+        searcher: Py<PyAny>,
+    ) -> PyResult<(Vec<PyVariant>, Vec<(String, String)>)> {
+        if let SequenceVariant::Genomic(v) = &var_g.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let bridge_searcher = PyTranscriptSearchBridge { searcher };
+            let (ok, failures) = mapper
+                .g_to_c_all_with_errors(v, &bridge_searcher)
+                .map_err(map_hgvs_error)?;
+            Ok((
+                ok.into_iter()
+                    .map(|v| PyVariant {
+                        inner: SequenceVariant::Coding(v),
+                    })
+                    .collect(),
+                failures,
+            ))
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            ))
+        }
+    }
+
     #[pyo3(signature = (var_c, reference_ac = None))]
     #[doc = "Maps a coding cDNA variant (c.) to a genomic variant (g.).\n\nArgs:\n    var_c: The coding Variant to map.\n    reference_ac: Optional chromosomal accession. If not provided, the primary chromosome for the transcript will be used.\n\nReturns:\n    A new Variant object in 'g.' coordinates."]
     fn c_to_g(
@@ -599,6 +753,54 @@ impl PyVariantMapper {
         }
     }
 
+    #[pyo3(signature = (var_c))]
+    #[doc = "Rebases a coding cDNA variant (c.) onto transcript-relative (n.) coordinates.\n\nArgs:\n    var_c: The coding Variant to rebase.\n\nReturns:\n    A new Variant object in 'n.' coordinates."]
+    fn c_to_n(&self, _py: Python, var_c: &PyVariant) -> PyResult<PyVariant> {
+        if let SequenceVariant::Coding(v) = &var_c.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper.c_to_n(v).map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::NonCoding(res),
+            })
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a coding variant (c.)",
+            ))
+        }
+    }
+
+    #[pyo3(signature = (var_n))]
+    #[doc = "Rebases a transcript-relative (n.) variant onto coding cDNA (c.) coordinates.\n\nArgs:\n    var_n: The non-coding Variant to rebase.\n\nReturns:\n    A new Variant object in 'c.' coordinates."]
+    fn n_to_c(&self, _py: Python, var_n: &PyVariant) -> PyResult<PyVariant> {
+        if let SequenceVariant::NonCoding(v) = &var_n.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper.n_to_c(v).map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::Coding(res),
+            })
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a non-coding variant (n.)",
+            ))
+        }
+    }
+
+    #[pyo3(signature = (var_g, transcript_ac))]
+    #[doc = "Maps a genomic variant (g.) to transcript-relative (n.) coordinates for a specific transcript.\n\nArgs:\n    var_g: The genomic Variant to map.\n    transcript_ac: The accession of the target transcript.\n\nReturns:\n    A new Variant object in 'n.' coordinates."]
+    fn g_to_n(&self, _py: Python, var_g: &PyVariant, transcript_ac: String) -> PyResult<PyVariant> {
+        if let SequenceVariant::Genomic(v) = &var_g.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper.g_to_n(v, &transcript_ac).map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::NonCoding(res),
+            })
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            ))
+        }
+    }
+
     #[pyo3(signature = (var_c, protein_ac=None))]
     #[doc = "Projects a coding cDNA variant (c.) to its protein consequence (p.).\n\nArgs:\n    var_c: The coding Variant to project.\n    protein_ac: Optional protein accession. If not provided, it will be retrieved from the DataProvider.\n\nReturns:\n    A new Variant object in 'p.' coordinates."]
     fn c_to_p(
@@ -622,16 +824,177 @@ impl PyVariantMapper {
         }
     }
 
-    #[pyo3(signature = (var))]
-    #[doc = "Normalizes a variant by shifting it to its 3'-most position.\n\nNormalization is performed in the coordinate space of the input variant.\n\nArgs:\n    var: The Variant object to normalize.\n\nReturns:\n    A new normalized Variant object."]
-    fn normalize_variant(&self, _py: Python, var: &PyVariant) -> PyResult<PyVariant> {
+    #[pyo3(signature = (var_g, transcript_ac, protein_ac=None))]
+    #[doc = "One-shot convenience chaining g_to_c then c_to_p.\n\nArgs:\n    var_g: The genomic Variant to map.\n    transcript_ac: The accession of the target transcript.\n    protein_ac: Optional protein accession. If not provided, it will be retrieved from the DataProvider.\n\nReturns:\n    A new Variant object in 'p.' coordinates."]
+    fn g_to_p(
+        &self,
+        _py: Python,
+        var_g: &PyVariant,
+        transcript_ac: String,
+        protein_ac: Option<String>,
+    ) -> PyResult<PyVariant> {
+        if let SequenceVariant::Genomic(v) = &var_g.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper
+                .g_to_p(v, &transcript_ac, protein_ac.as_deref())
+                .map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::Protein(res),
+            })
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            ))
+        }
+    }
+
+    #[pyo3(signature = (var_g, searcher))]
+    #[doc = "Discovers every transcript overlapping a genomic variant's region and projects each one to its combined c./p. consequence.\n\nThe protein accession for each transcript is resolved once and reused for the c_to_p projection. A transcript that fails to map is silently skipped; one with no annotated CDS (non-coding) is still included, with its p. Variant set to None.\n\nArgs:\n    var_g: The genomic Variant to map.\n    searcher: An object implementing the TranscriptSearch protocol.\n\nReturns:\n    A list of (c. Variant, p. Variant or None, strand) tuples."]
+    fn g_to_consequences_all(
+        &self,
+        _py: Python,
+        var_g: &PyVariant,
+        searcher: Py<PyAny>,
+    ) -> PyResult<Vec<(PyVariant, Option<PyVariant>, i32)>> {
+        if let SequenceVariant::Genomic(v) = &var_g.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let bridge_searcher = PyTranscriptSearchBridge { searcher };
+            let res = mapper
+                .g_to_consequences_all(v, &bridge_searcher)
+                .map_err(map_hgvs_error)?;
+            Ok(res
+                .into_iter()
+                .map(|c| {
+                    (
+                        PyVariant {
+                            inner: SequenceVariant::Coding(c.c_variant),
+                        },
+                        c.p_variant.map(|p| PyVariant {
+                            inner: SequenceVariant::Protein(p),
+                        }),
+                        c.strand,
+                    )
+                })
+                .collect())
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            ))
+        }
+    }
+
+    #[pyo3(signature = (var, shift_3prime = true, cross_boundaries = false))]
+    #[doc = "Normalizes a variant by shifting it to its most 3' (or 5') position.\n\nNormalization is performed in the coordinate space of the input variant.\n\nArgs:\n    var: The Variant object to normalize.\n    shift_3prime: If True (the default), shift to the 3'-most representation, as HGVS requires. If False, shift to the 5'-most representation instead.\n    cross_boundaries: Only affects c. variants. If False (the default), a shift that would move the edit outside the transcript's annotated CDS is skipped entirely, leaving the variant at its original position. If True, the shift is allowed to cross into the UTR.\n\nReturns:\n    A new normalized Variant object."]
+    fn normalize_variant(
+        &self,
+        _py: Python,
+        var: &PyVariant,
+        shift_3prime: bool,
+        cross_boundaries: bool,
+    ) -> PyResult<PyVariant> {
+        let mapper = VariantMapper::new(self.bridge.as_ref());
+        let res = mapper
+            .normalize_variant_with_options(var.inner.clone(), shift_3prime, cross_boundaries)
+            .map_err(map_hgvs_error)?;
+        Ok(PyVariant { inner: res })
+    }
+
+    #[pyo3(signature = (var, searcher = None, shift_3prime = ShuffleDirection::ThreePrime))]
+    #[doc = "Normalizes a variant to a canonical Variant object, without collapsing to a string.\n\nLike normalize_variant, but takes the shift direction as a ShuffleDirection enum rather than a bool, for consumers that want a normalized Variant they can further map or re-render. Always allows the shift to cross the transcript's CDS boundary; use normalize_variant directly if boundary-clamping is required.\n\nArgs:\n    var: The Variant object to normalize.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods, since normalization only ever needs the variant's own accession, not transcript discovery.\n    shift_3prime: The boundary of the ambiguous region to shift to. Defaults to ShuffleDirection.ThreePrime, as HGVS requires.\n\nReturns:\n    A new normalized Variant object."]
+    fn normalize(
+        &self,
+        _py: Python,
+        var: &PyVariant,
+        searcher: Option<Py<PyAny>>,
+        shift_3prime: ShuffleDirection,
+    ) -> PyResult<PyVariant> {
+        let _ = searcher;
+        let mapper = VariantMapper::new(self.bridge.as_ref());
+        let res = mapper
+            .normalize_variant_with_options(
+                var.inner.clone(),
+                shift_3prime == ShuffleDirection::ThreePrime,
+                true,
+            )
+            .map_err(map_hgvs_error)?;
+        Ok(PyVariant { inner: res })
+    }
+
+    #[pyo3(signature = (var, searcher = None))]
+    #[doc = "Fills in a missing reference allele on a del/dup/delins edit.\n\nFor c./n. variants whose span touches an intronic offset (e.g. c.123+5del), the transcript's own spliced sequence has no bases there, so the reference is sourced from genomic sequence instead. Purely exonic c./n. spans, and all g. spans, are filled directly from their own accession.\n\nArgs:\n    var: The Variant object whose edit is missing a reference allele.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods, since resolving the reference span here only ever needs the variant's own accession, not transcript discovery.\n\nReturns:\n    A new Variant object with the reference allele filled in, suitable for equivalent()/to_spdi()."]
+    fn fill_ref(
+        &self,
+        _py: Python,
+        var: &PyVariant,
+        searcher: Option<Py<PyAny>>,
+    ) -> PyResult<PyVariant> {
+        let _ = searcher;
         let mapper = VariantMapper::new(self.bridge.as_ref());
         let res = mapper
-            .normalize_variant(var.inner.clone())
+            .fill_ref(var.inner.clone())
             .map_err(map_hgvs_error)?;
         Ok(PyVariant { inner: res })
     }
 
+    #[pyo3(signature = (spdi, searcher = None))]
+    #[doc = "Parses a SPDI string (sequence:position:deletion:insertion) into a Variant.\n\nResolves whether the sequence accession is genomic or transcript via the DataProvider, producing a 'g.' or 'n.' Variant respectively.\n\nArgs:\n    spdi: The SPDI string, e.g. 'NC_000001.11:12344:A:G'.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods, since resolving the SPDI accession only ever needs the DataProvider, not transcript discovery.\n\nReturns:\n    A new Variant object."]
+    fn from_spdi(
+        &self,
+        _py: Python,
+        spdi: &str,
+        searcher: Option<Py<PyAny>>,
+    ) -> PyResult<PyVariant> {
+        let _ = searcher;
+        let inner =
+            ::hgvs_weaver::structs::spdi_to_variant(spdi, self.bridge.as_ref()).map_err(map_hgvs_error)?;
+        Ok(PyVariant { inner })
+    }
+
+    #[pyo3(signature = (var, searcher = None))]
+    #[doc = "Maps any variant to genomic coordinates and renders it as a VCF-style record.\n\nUses 1-based leftmost position, left-anchors indels to the preceding reference base, and fetches any implicit reference allele from genomic sequence.\n\nArgs:\n    var: The Variant object to project.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods, since projecting to genomic coordinates only ever needs the DataProvider, not transcript discovery.\n\nReturns:\n    A (chrom, pos, ref, alt) tuple."]
+    fn to_vcf(
+        &self,
+        _py: Python,
+        var: &PyVariant,
+        searcher: Option<Py<PyAny>>,
+    ) -> PyResult<(String, i32, String, String)> {
+        let _ = searcher;
+        let mapper = VariantMapper::new(self.bridge.as_ref());
+        let rec = mapper.to_vcf(&var.inner).map_err(map_hgvs_error)?;
+        Ok((rec.chrom, rec.pos, rec.ref_, rec.alt))
+    }
+
+    #[pyo3(signature = (var, searcher = None))]
+    #[doc = "Like to_vcf, but rolls the variant to its left-aligned (5'-most) position instead of the HGVS-standard 3'-most one.\n\nUseful when feeding the result to VCF tooling (bcftools norm, GATK, etc.) that assumes left-aligned, parsimonious indels rather than HGVS's rightmost convention.\n\nArgs:\n    var: The Variant object to project.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods.\n\nReturns:\n    A (chrom, pos, ref, alt) tuple."]
+    fn to_vcf_left_aligned(
+        &self,
+        _py: Python,
+        var: &PyVariant,
+        searcher: Option<Py<PyAny>>,
+    ) -> PyResult<(String, i32, String, String)> {
+        let _ = searcher;
+        let mapper = VariantMapper::new(self.bridge.as_ref());
+        let rec = mapper.to_vcf_left_aligned(&var.inner).map_err(map_hgvs_error)?;
+        Ok((rec.chrom, rec.pos, rec.ref_, rec.alt))
+    }
+
+    #[pyo3(signature = (chrom, pos, ref_, alt, searcher = None))]
+    #[doc = "Builds a genomic Variant from a VCF-style (chrom, pos, ref, alt) record, the inverse of to_vcf.\n\nArgs:\n    chrom: The genomic reference accession.\n    pos: The 1-based leftmost VCF position.\n    ref_: The VCF reference allele.\n    alt: The VCF alternate allele.\n    searcher: Unused; accepted for interface symmetry with the other mapper methods.\n\nReturns:\n    A new Variant object in 'g.' coordinates."]
+    fn from_vcf(
+        &self,
+        _py: Python,
+        chrom: &str,
+        pos: i32,
+        ref_: &str,
+        alt: &str,
+        searcher: Option<Py<PyAny>>,
+    ) -> PyResult<PyVariant> {
+        let _ = searcher;
+        let mapper = VariantMapper::new(self.bridge.as_ref());
+        let inner = mapper.from_vcf(chrom, pos, ref_, alt).map_err(map_hgvs_error)?;
+        Ok(PyVariant { inner })
+    }
+
     #[pyo3(signature = (var1, var2, searcher))]
     #[doc = "Determines if two variants are biologically equivalent.\n\nHandles normalization, cross-coordinate mapping (g. vs c.), and gene symbol expansion.\n\nArgs:\n    var1: The first Variant object.\n    var2: The second Variant object.\n    searcher: An object implementing the TranscriptSearch protocol.\n\nReturns:\n    True if the variants are equivalent, False otherwise."]
     fn equivalent(
@@ -671,6 +1034,44 @@ impl PyVariantMapper {
         Ok(res.into())
     }
 
+    #[pyo3(signature = (vars, searcher))]
+    #[doc = "Partitions a batch of variants into equivalence classes.\n\nCanonicalizes each variant once to a shared genomic key and buckets by that key, only falling back to pairwise equivalent_level checks within a bucket for variants without one (protein, mitochondrial, or a failed projection). Transcript lookups are memoized per accession across the whole batch; a sequence request is served from the accession's cached span when that span covers it, and otherwise fetches a widened span from the provider.\n\nArgs:\n    vars: The list of Variant objects to cluster.\n    searcher: An object implementing the TranscriptSearch protocol.\n\nReturns:\n    A list of groups, each a list of indices into vars, of mutually-equivalent variants."]
+    fn cluster_equivalent(
+        &self,
+        _py: Python,
+        vars: Vec<PyRef<PyVariant>>,
+        searcher: Py<PyAny>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        let bridge_searcher = PyTranscriptSearchBridge { searcher };
+        let caching = ::hgvs_weaver::caching_provider::CachingDataProvider::new(self.bridge.as_ref());
+        let equiv =
+            ::hgvs_weaver::equivalence::VariantEquivalence::new(&caching, &bridge_searcher);
+        let inner: Vec<SequenceVariant> = vars.iter().map(|v| v.inner.clone()).collect();
+        equiv.cluster_equivalent(&inner).map_err(map_hgvs_error)
+    }
+
+    #[pyo3(signature = (allele1, allele2, searcher))]
+    #[doc = "Compares two cis-allele descriptions (as returned by parse_allele) as unordered sets of components.\n\nFinds a one-to-one pairing of components where every pair is equivalent; the alleles must be the same size and every component must find a match or the result is Different. AlleleReordered means the two name the same set of changes but in a different order or per-component representation; Identity means every component matches in place and exactly.\n\nArgs:\n    allele1: The first allele's ordered list of Variant components.\n    allele2: The second allele's ordered list of Variant components.\n    searcher: An object implementing the TranscriptSearch protocol.\n\nReturns:\n    An EquivalenceLevel enum value."]
+    fn equivalent_level_allele(
+        &self,
+        _py: Python,
+        allele1: Vec<PyRef<PyVariant>>,
+        allele2: Vec<PyRef<PyVariant>>,
+        searcher: Py<PyAny>,
+    ) -> PyResult<PyEquivalenceLevel> {
+        let bridge_searcher = PyTranscriptSearchBridge { searcher };
+        let equiv = ::hgvs_weaver::equivalence::VariantEquivalence::new(
+            self.bridge.as_ref(),
+            &bridge_searcher,
+        );
+        let a1: Vec<SequenceVariant> = allele1.iter().map(|v| v.inner.clone()).collect();
+        let a2: Vec<SequenceVariant> = allele2.iter().map(|v| v.inner.clone()).collect();
+        let res = equiv
+            .allele_equivalent_level(&a1, &a2)
+            .map_err(map_hgvs_error)?;
+        Ok(res.into())
+    }
+
     #[pyo3(signature = (var, unambiguous = false))]
     #[doc = "Converts a variant to a SPDI string format.\n\nArgs:\n    var: The Variant object to convert.\n    unambiguous: If True, expands the variant range to cover the entire ambiguous region of a repeat or homopolymer. Default is False."]
     fn to_spdi(&self, _py: Python, var: &PyVariant, unambiguous: bool) -> PyResult<String> {
@@ -688,13 +1089,382 @@ impl PyVariantMapper {
     }
 }
 
+// --- Assembly-aware mapping ---
+
+/// RefSeq chromosomal accessions for the GRCh38 primary assembly, keyed by
+/// bare chromosome name (no `chr` prefix).
+const GRCH38_CHROMS: &[(&str, &str)] = &[
+    ("1", "NC_000001.11"),
+    ("2", "NC_000002.12"),
+    ("3", "NC_000003.12"),
+    ("4", "NC_000004.12"),
+    ("5", "NC_000005.10"),
+    ("6", "NC_000006.12"),
+    ("7", "NC_000007.14"),
+    ("8", "NC_000008.11"),
+    ("9", "NC_000009.12"),
+    ("10", "NC_000010.11"),
+    ("11", "NC_000011.10"),
+    ("12", "NC_000012.12"),
+    ("13", "NC_000013.11"),
+    ("14", "NC_000014.9"),
+    ("15", "NC_000015.10"),
+    ("16", "NC_000016.10"),
+    ("17", "NC_000017.11"),
+    ("18", "NC_000018.10"),
+    ("19", "NC_000019.10"),
+    ("20", "NC_000020.11"),
+    ("21", "NC_000021.9"),
+    ("22", "NC_000022.11"),
+    ("X", "NC_000023.11"),
+    ("Y", "NC_000024.10"),
+    ("MT", "NC_012920.1"),
+];
+
+/// RefSeq chromosomal accessions for the GRCh37 primary assembly, keyed by
+/// bare chromosome name (no `chr` prefix).
+const GRCH37_CHROMS: &[(&str, &str)] = &[
+    ("1", "NC_000001.10"),
+    ("2", "NC_000002.11"),
+    ("3", "NC_000003.11"),
+    ("4", "NC_000004.11"),
+    ("5", "NC_000005.9"),
+    ("6", "NC_000006.11"),
+    ("7", "NC_000007.13"),
+    ("8", "NC_000008.10"),
+    ("9", "NC_000009.11"),
+    ("10", "NC_000010.10"),
+    ("11", "NC_000011.9"),
+    ("12", "NC_000012.11"),
+    ("13", "NC_000013.10"),
+    ("14", "NC_000014.8"),
+    ("15", "NC_000015.9"),
+    ("16", "NC_000016.9"),
+    ("17", "NC_000017.10"),
+    ("18", "NC_000018.9"),
+    ("19", "NC_000019.9"),
+    ("20", "NC_000020.10"),
+    ("21", "NC_000021.8"),
+    ("22", "NC_000022.10"),
+    ("X", "NC_000023.10"),
+    ("Y", "NC_000024.9"),
+    ("MT", "NC_012920.1"),
+];
+
+/// Strips a leading `chr` prefix, so `"chr1"` and `"1"` resolve the same way.
+fn normalize_chrom(chrom: &str) -> &str {
+    chrom.strip_prefix("chr").unwrap_or(chrom)
+}
+
+/// Looks up the chromosomal accession for `chrom` under `assembly`.
+/// Recognizes `"GRCh38"`/`"hg38"` and `"GRCh37"`/`"hg19"`; anything else
+/// returns `None` rather than silently guessing an assembly.
+fn assembly_accession(assembly: &str, chrom: &str) -> Option<&'static str> {
+    let table = match assembly {
+        "GRCh38" | "hg38" => GRCH38_CHROMS,
+        "GRCh37" | "hg19" => GRCH37_CHROMS,
+        _ => return None,
+    };
+    let chrom = normalize_chrom(chrom);
+    table
+        .iter()
+        .find(|(c, _)| *c == chrom)
+        .map(|(_, acc)| *acc)
+}
+
+#[gen_stub_pyclass]
+#[pyclass(name = "AssemblyMapper", module = "weaver._weaver")]
+#[doc = "Variant mapper that auto-selects the chromosomal accession and relevant transcripts for a named assembly (e.g. 'GRCh38').\n\nWraps a DataProvider and a TranscriptSearch so callers can work with plain\nchromosome names ('1', 'chrX') instead of versioned RefSeq accessions."]
+pub struct PyAssemblyMapper {
+    bridge: std::sync::Arc<PyDataProviderBridge>,
+    searcher: std::sync::Arc<PyTranscriptSearchBridge>,
+    assembly: String,
+}
+
+impl PyAssemblyMapper {
+    /// Resolves `chrom` to this mapper's assembly-specific accession.
+    fn resolve_chrom(&self, chrom: &str) -> PyResult<String> {
+        assembly_accession(&self.assembly, chrom)
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown chromosome '{}' for assembly '{}'",
+                    chrom, self.assembly
+                ))
+            })
+    }
+
+    /// Returns `var_g` with its accession rewritten to the resolved
+    /// chromosomal accession, unless it already looks like one (`NC_...`).
+    fn resolve_genomic(&self, var_g: &PyVariant) -> PyResult<PyVariant> {
+        match &var_g.inner {
+            SequenceVariant::Genomic(v) => {
+                if v.ac.starts_with("NC_") {
+                    return Ok(var_g.clone());
+                }
+                let mut v = v.clone();
+                v.ac = self.resolve_chrom(&v.ac)?;
+                Ok(PyVariant {
+                    inner: SequenceVariant::Genomic(v),
+                })
+            }
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            )),
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyAssemblyMapper {
+    #[new]
+    #[doc = "Creates a new AssemblyMapper for the given assembly (e.g. 'GRCh38', 'GRCh37')."]
+    fn new(provider: Py<PyAny>, searcher: Py<PyAny>, assembly: String) -> Self {
+        PyAssemblyMapper {
+            bridge: std::sync::Arc::new(PyDataProviderBridge { provider }),
+            searcher: std::sync::Arc::new(PyTranscriptSearchBridge { searcher }),
+            assembly,
+        }
+    }
+
+    #[doc = "Resolves a chromosome name (e.g. '1', 'chrX') to the genomic accession used by this mapper's assembly."]
+    fn chromosome_accession(&self, _py: Python, chrom: &str) -> PyResult<String> {
+        self.resolve_chrom(chrom)
+    }
+
+    #[doc = "Lists the transcript accessions overlapping a genomic variant's position, as discovered via the TranscriptSearch.\n\nArgs:\n    var_g: The genomic Variant to search around.\n\nReturns:\n    A list of transcript accessions."]
+    fn relevant_transcripts(&self, _py: Python, var_g: &PyVariant) -> PyResult<Vec<String>> {
+        if let SequenceVariant::Genomic(v) = &var_g.inner {
+            let pos = v.posedit.pos.as_ref().ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err("Missing genomic position")
+            })?;
+            let start_0 = pos.start.base.to_index().0;
+            let end_0 = pos
+                .end
+                .as_ref()
+                .map_or(start_0 + 1, |e| e.base.to_index().0 + 1);
+            self.searcher
+                .get_transcripts_for_region(&v.ac, start_0, end_0)
+                .map_err(map_hgvs_error)
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a genomic variant (g.)",
+            ))
+        }
+    }
+
+    #[pyo3(signature = (var_g, transcript_ac))]
+    #[doc = "Maps a genomic variant (g.) to a coding cDNA variant (c.) for a specific transcript.\n\nUnlike VariantMapper.g_to_c, var_g's accession may be a bare chromosome name; it is resolved against this mapper's assembly first."]
+    fn g_to_c(&self, _py: Python, var_g: &PyVariant, transcript_ac: String) -> PyResult<PyVariant> {
+        let resolved = self.resolve_genomic(var_g)?;
+        if let SequenceVariant::Genomic(v) = &resolved.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper.g_to_c(v, &transcript_ac).map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::Coding(res),
+            })
+        } else {
+            unreachable!("resolve_genomic always returns a Genomic variant or an error")
+        }
+    }
+
+    #[doc = "Maps a coding cDNA variant (c.) to a genomic variant (g.), resolving the chromosomal accession from this mapper's assembly.\n\nArgs:\n    var_c: The coding Variant to map.\n    chrom: The chromosome name (e.g. '1', 'chrX') to map onto."]
+    fn c_to_g(&self, _py: Python, var_c: &PyVariant, chrom: &str) -> PyResult<PyVariant> {
+        let reference_ac = self.resolve_chrom(chrom)?;
+        if let SequenceVariant::Coding(v) = &var_c.inner {
+            let mapper = VariantMapper::new(self.bridge.as_ref());
+            let res = mapper
+                .c_to_g(v, Some(&reference_ac))
+                .map_err(map_hgvs_error)?;
+            Ok(PyVariant {
+                inner: SequenceVariant::Genomic(res),
+            })
+        } else {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected a coding variant (c.)",
+            ))
+        }
+    }
+}
+
+// --- Batch VCF annotation ---
+
+#[gen_stub_pyclass]
+#[pyclass(name = "AnnotationRow", module = "weaver._weaver")]
+#[doc = "One (allele, transcript) row of a batch annotation run.\n\nSee [`annotate_records`]."]
+#[derive(Clone)]
+pub struct PyAnnotationRow {
+    chrom: String,
+    pos: i32,
+    reference_bases: String,
+    alt_bases: String,
+    transcript_ac: String,
+    gene: Option<String>,
+    hgvs_c: Option<String>,
+    hgvs_p: Option<String>,
+    error: Option<String>,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyAnnotationRow {
+    #[getter]
+    #[doc = "The input chromosome name, as passed to annotate_records."]
+    fn chrom(&self) -> String {
+        self.chrom.clone()
+    }
+    #[getter]
+    #[doc = "The input 1-based VCF position."]
+    fn pos(&self) -> i32 {
+        self.pos
+    }
+    #[getter]
+    #[doc = "The input REF bases."]
+    fn reference_bases(&self) -> String {
+        self.reference_bases.clone()
+    }
+    #[getter]
+    #[doc = "The input ALT bases for this row's allele."]
+    fn alt_bases(&self) -> String {
+        self.alt_bases.clone()
+    }
+    #[getter]
+    #[doc = "The transcript accession this row's c./p. projection is onto."]
+    fn transcript_ac(&self) -> String {
+        self.transcript_ac.clone()
+    }
+    #[getter]
+    #[doc = "The gene symbol for transcript_ac, if available."]
+    fn gene(&self) -> Option<String> {
+        self.gene.clone()
+    }
+    #[getter]
+    #[doc = "The HGVS c. string for this allele on transcript_ac, if mapping succeeded."]
+    fn hgvs_c(&self) -> Option<String> {
+        self.hgvs_c.clone()
+    }
+    #[getter]
+    #[doc = "The HGVS p. string for this allele, if protein projection succeeded."]
+    fn hgvs_p(&self) -> Option<String> {
+        self.hgvs_p.clone()
+    }
+    #[getter]
+    #[doc = "The error message if mapping or protein projection failed for this row."]
+    fn error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<weaver.AnnotationRow {}:{}{}>{} {}>",
+            self.chrom,
+            self.pos,
+            self.reference_bases,
+            self.alt_bases,
+            self.hgvs_c.as_deref().unwrap_or("?"),
+        )
+    }
+
+
+}
+
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[doc = "Annotates a batch of VCF-style records against every transcript overlapping each locus.\n\nFor each (chrom, pos, ref, alt) record, resolves chrom to a chromosomal accession\nunder assembly, maps it onto every overlapping transcript via g_to_c_all, and\nprojects each resulting c. variant to its protein consequence via c_to_p.\nTranscript lookups are memoized per accession across the whole batch; a\nsequence request is served from the accession's cached span when that span\ncovers it, and otherwise fetches a widened span from provider.\n\nArgs:\n    provider: The DataProvider to fetch transcripts/sequences from.\n    searcher: An object implementing the TranscriptSearch protocol.\n    records: A list of (chrom, pos, ref, alt) tuples.\n    assembly: The assembly name used to resolve chrom to an accession (e.g. 'GRCh38').\n\nReturns:\n    A list of AnnotationRow objects, one per (allele, transcript) pair."]
+fn annotate_records(
+    provider: Py<PyAny>,
+    searcher: Py<PyAny>,
+    records: Vec<(String, i32, String, String)>,
+    assembly: String,
+) -> PyResult<Vec<PyAnnotationRow>> {
+    let bridge = PyDataProviderBridge { provider };
+    let bridge_searcher = PyTranscriptSearchBridge { searcher };
+    let caching = ::hgvs_weaver::caching_provider::CachingDataProvider::new(&bridge);
+
+    let mut rows = Vec::new();
+    for (chrom, pos, reference_bases, alt_bases) in records {
+        let reference_ac = assembly_accession(&assembly, &chrom)
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown chromosome '{}' for assembly '{}'",
+                    chrom, assembly
+                ))
+            })?;
+
+        let annotated = ::hgvs_weaver::annotate::annotate_record(
+            &caching,
+            &bridge_searcher,
+            &reference_ac,
+            pos,
+            &reference_bases,
+            &alt_bases,
+        )
+        .map_err(map_hgvs_error)?;
+
+        rows.extend(annotated.into_iter().map(|r| PyAnnotationRow {
+            chrom: chrom.clone(),
+            pos: r.pos,
+            reference_bases: r.reference_bases,
+            alt_bases: r.alt_bases,
+            transcript_ac: r.transcript_ac,
+            gene: r.gene,
+            hgvs_c: r.hgvs_c,
+            hgvs_p: r.hgvs_p,
+            error: r.error,
+        }));
+    }
+    Ok(rows)
+}
+
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[doc = "Writes annotation rows (as returned by annotate_records) to a TSV file.\n\nArgs:\n    rows: The AnnotationRow objects to write, e.g. from annotate_records.\n    path: Destination file path.\n\nRaises:\n    OSError: If the file could not be written."]
+fn write_tsv(rows: Vec<PyAnnotationRow>, path: &str) -> PyResult<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::File::create(path)
+        .map_err(|e| pyo3::exceptions::PyOSError::new_err(e.to_string()))?;
+    writeln!(
+        file,
+        "chrom\tpos\tref\talt\ttranscript_ac\tgene\thgvs_c\thgvs_p\terror"
+    )
+    .map_err(|e| pyo3::exceptions::PyOSError::new_err(e.to_string()))?;
+    for row in rows {
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            row.chrom,
+            row.pos,
+            row.reference_bases,
+            row.alt_bases,
+            row.transcript_ac,
+            row.gene.as_deref().unwrap_or(""),
+            row.hgvs_c.as_deref().unwrap_or(""),
+            row.hgvs_p.as_deref().unwrap_or(""),
+            row.error.as_deref().unwrap_or(""),
+        )
+        .map_err(|e| pyo3::exceptions::PyOSError::new_err(e.to_string()))?;
+    }
+    Ok(())
+}
+
 #[pymodule]
 fn _weaver(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_allele, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_spdi, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_records, m)?)?;
+    m.add_function(wrap_pyfunction!(write_tsv, m)?)?;
     m.add_class::<PyVariant>()?;
     m.add_class::<PyVariantMapper>()?;
+    m.add_class::<PyAssemblyMapper>()?;
+    m.add_class::<PyAnnotationRow>()?;
     m.add_class::<PyIdentifierType>()?;
     m.add_class::<PyEquivalenceLevel>()?;
+    m.add_class::<ValidationLevel>()?;
+    m.add_class::<ShuffleDirection>()?;
     m.add(
         "TranscriptMismatchError",
         m.py().get_type::<TranscriptMismatchError>(),